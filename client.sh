@@ -1,2 +1,2 @@
 clear
-cargo run --bin client --release
+cargo run --bin client --release -- "$@"
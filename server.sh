@@ -1,4 +1,4 @@
 clear
-cargo run --bin server --release
+cargo run --bin server --release -- "$@"
 
 #172.16.1.103
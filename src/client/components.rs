@@ -0,0 +1,104 @@
+// src/client/components.rs
+
+// Import des modules standard pour la gestion des collections
+use std::collections::VecDeque;
+
+// Import des modules Bevy pour les composants et les mathématiques
+use bevy::{
+    ecs::component::Component,
+    math::{Quat, Vec3},
+};
+
+/// Nombre maximal d'échantillons conservés par joueur distant
+const MAX_SNAPSHOT_SAMPLES: usize = 64;
+
+/// Échantillon de l'état d'un joueur distant reçu du serveur
+#[derive(Clone, Copy, Debug)]
+pub struct RemoteSample {
+    pub tick: u32,      // Tick serveur de l'échantillon
+    pub position: Vec3, // Position du joueur à ce tick
+    pub rotation: Quat, // Rotation du joueur à ce tick
+}
+
+/// Composant contenant la posture répliquée d'un joueur
+/// Permet de ne reconstruire les zones du corps (collider) qu'au changement de posture
+#[derive(Component, Default)]
+pub struct PlayerStance {
+    pub crouched: bool, // Joueur accroupi
+}
+
+/// Composant contenant les derniers états reçus d'un joueur distant
+/// Permet d'afficher le joueur avec un léger retard en interpolant entre deux états
+#[derive(Component, Default)]
+pub struct SnapshotBuffer(pub VecDeque<RemoteSample>);
+
+impl SnapshotBuffer {
+    /// Ajoute un échantillon en ignorant ceux arrivés dans le désordre
+    ///
+    /// # Arguments
+    /// * `sample` - Échantillon reçu du serveur
+    pub fn push(&mut self, sample: RemoteSample) {
+        if self.0.back().is_some_and(|last| last.tick >= sample.tick) {
+            return;
+        }
+        self.0.push_back(sample);
+        while self.0.len() > MAX_SNAPSHOT_SAMPLES {
+            self.0.pop_front();
+        }
+    }
+
+    /// Calcule l'état à afficher pour un tick de rendu donné
+    /// Interpole entre les deux échantillons qui encadrent le tick, ou extrapole brièvement
+    /// à partir des deux derniers quand les échantillons sont en retard
+    ///
+    /// # Arguments
+    /// * `render_tick` - Tick serveur (fractionnaire) à afficher
+    /// * `max_extrapolation` - Nombre maximal de ticks d'extrapolation
+    ///
+    /// # Returns
+    /// * `Option<(Vec3, Quat)>` - Position et rotation à afficher, ou None sans échantillon
+    pub fn sample(&self, render_tick: f64, max_extrapolation: f64) -> Option<(Vec3, Quat)> {
+        let first = self.0.front()?;
+        let last = self.0.back()?;
+
+        // Tick de rendu plus ancien que tous les échantillons
+        if render_tick <= first.tick as f64 {
+            return Some((first.position, first.rotation));
+        }
+
+        // Interpolation entre les deux échantillons encadrant le tick de rendu
+        for (from, to) in self.0.iter().zip(self.0.iter().skip(1)) {
+            if (from.tick as f64) <= render_tick && render_tick <= to.tick as f64 {
+                let t = ((render_tick - from.tick as f64) / (to.tick - from.tick) as f64) as f32;
+                return Some((
+                    from.position.lerp(to.position, t),
+                    from.rotation.slerp(to.rotation, t),
+                ));
+            }
+        }
+
+        // Échantillons en retard : extrapolation limitée à partir des deux derniers
+        let ahead = (render_tick - last.tick as f64).min(max_extrapolation);
+        let previous = self.0.iter().rev().nth(1);
+        let position = match previous {
+            Some(previous) if previous.tick < last.tick => {
+                let velocity =
+                    (last.position - previous.position) / (last.tick - previous.tick) as f32;
+                last.position + velocity * ahead as f32
+            }
+            _ => last.position,
+        };
+        Some((position, last.rotation))
+    }
+
+    /// Supprime les échantillons devenus inutiles pour le tick de rendu
+    /// Un échantillon antérieur au tick est conservé pour pouvoir interpoler
+    ///
+    /// # Arguments
+    /// * `render_tick` - Tick serveur (fractionnaire) affiché
+    pub fn prune(&mut self, render_tick: f64) {
+        while self.0.len() > 2 && (self.0[1].tick as f64) <= render_tick {
+            self.0.pop_front();
+        }
+    }
+}
@@ -0,0 +1,226 @@
+// src/client/config.rs
+
+// Configuration du client : fichier RON optionnel puis options de ligne de commande
+// Quand le serveur et le nom d'utilisateur sont connus, le client démarre sans aucune question
+
+// Import des modules standard pour le réseau et les durées
+use std::{
+    net::{SocketAddr, ToSocketAddrs},
+    time::Duration,
+};
+
+// Import du mode d'affichage de la fenêtre Bevy
+use bevy::window::WindowMode;
+// Import du chargement de configuration, des constantes et des vérifications partagés
+use multiplayer_demo::{
+    config::{parse_value, ConfigError, ConfigFile},
+    protocol::{validate_username, DEFAULT_SERVER_PORT, DEFAULT_TOKEN_PORT},
+};
+// Import de serde pour la lecture du fichier de configuration
+use serde::Deserialize;
+
+/// Fichier de configuration lu par défaut s'il existe dans le répertoire courant
+pub const DEFAULT_CONFIG_PATH: &str = "client.ron";
+
+/// Retard d'affichage maximal des joueurs distants (millisecondes)
+const MAX_INTERPOLATION_DELAY_MS: u64 = 1000;
+/// Durée d'extrapolation maximale des joueurs distants (millisecondes)
+const MAX_EXTRAPOLATION_MS: u64 = 1000;
+
+/// Aide affichée par `--help`
+pub const USAGE: &str = "\
+Usage : client [OPTIONS]
+
+Options :
+  --config <FICHIER>           Fichier de configuration RON (défaut : client.ron s'il existe)
+  --server <HÔTE[:PORT]>       Serveur de jeu (IP ou nom DNS, port 5000 par défaut)
+  --username <NOM>             Nom d'utilisateur
+  --window <MODE>              Mode de la fenêtre : windowed, borderless ou fullscreen
+  --secure                     Demande un jeton de connexion à l'émetteur du serveur
+  --token-port <PORT>          Port HTTP de l'émetteur de jetons
+  --interpolation-delay <MS>   Retard d'affichage des joueurs distants (défaut : 100)
+  --max-extrapolation <MS>     Extrapolation maximale des joueurs distants (défaut : 250)
+  --no-prompt                  N'interroge jamais le terminal (erreur si une valeur manque)
+  --help                       Affiche cette aide";
+
+/// Modes d'affichage de la fenêtre du jeu
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowModeSetting {
+    Windowed,   // Fenêtre classique
+    Borderless, // Plein écran sans bordure (défaut)
+    Fullscreen, // Plein écran exclusif
+}
+
+impl WindowModeSetting {
+    /// Mode de fenêtre Bevy correspondant
+    pub fn window_mode(self) -> WindowMode {
+        match self {
+            WindowModeSetting::Windowed => WindowMode::Windowed,
+            WindowModeSetting::Borderless => WindowMode::BorderlessFullscreen,
+            WindowModeSetting::Fullscreen => WindowMode::Fullscreen,
+        }
+    }
+}
+
+/// Configuration complète du client
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct ClientSettings {
+    pub server: Option<String>,         // Serveur de jeu (`hôte` ou `hôte:port`)
+    pub username: Option<String>,       // Nom d'utilisateur
+    pub window_mode: WindowModeSetting, // Mode de la fenêtre
+    pub secure: bool,                   // Mode sécurisé (jetons de connexion)
+    pub token_port: u16,                // Port HTTP de l'émetteur de jetons
+    pub interpolation_delay_ms: u64,    // Retard d'affichage des joueurs distants
+    pub max_extrapolation_ms: u64,      // Extrapolation maximale quand les instantanés sont en retard
+    pub no_prompt: bool,                // Démarrage sans aucune question sur le terminal
+}
+
+impl Default for ClientSettings {
+    fn default() -> Self {
+        Self {
+            server: None,
+            username: None,
+            window_mode: WindowModeSetting::Borderless,
+            secure: false,
+            token_port: DEFAULT_TOKEN_PORT,
+            interpolation_delay_ms: 100,
+            max_extrapolation_ms: 250,
+            no_prompt: false,
+        }
+    }
+}
+
+impl ConfigFile for ClientSettings {
+    const DEFAULT_PATH: &'static str = DEFAULT_CONFIG_PATH;
+
+    fn apply_args(&mut self, args: &[String]) -> Result<(), ConfigError> {
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .cloned()
+                    .ok_or_else(|| ConfigError::Cli(format!("valeur manquante pour {name}")))
+            };
+            match arg.as_str() {
+                "--config" => {
+                    value("--config")?;
+                }
+                "--server" => self.server = Some(value("--server")?),
+                "--username" => self.username = Some(value("--username")?),
+                "--window" => {
+                    let mode = value("--window")?;
+                    self.window_mode = match mode.to_ascii_lowercase().as_str() {
+                        "windowed" => WindowModeSetting::Windowed,
+                        "borderless" => WindowModeSetting::Borderless,
+                        "fullscreen" => WindowModeSetting::Fullscreen,
+                        _ => {
+                            return Err(ConfigError::Cli(format!(
+                                "mode de fenêtre inconnu pour --window : {mode}"
+                            )))
+                        }
+                    };
+                }
+                "--secure" => self.secure = true,
+                "--token-port" => {
+                    self.token_port = parse_value("--token-port", &value("--token-port")?)?
+                }
+                "--interpolation-delay" => {
+                    self.interpolation_delay_ms =
+                        parse_value("--interpolation-delay", &value("--interpolation-delay")?)?
+                }
+                "--max-extrapolation" => {
+                    self.max_extrapolation_ms =
+                        parse_value("--max-extrapolation", &value("--max-extrapolation")?)?
+                }
+                "--no-prompt" => self.no_prompt = true,
+                other => return Err(ConfigError::Cli(format!("option inconnue : {other}"))),
+            }
+        }
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), ConfigError> {
+        let mut errors = Vec::new();
+
+        if let Some(username) = &self.username {
+            if let Err(err) = validate_username(username) {
+                errors.push(format!("username : {err}"));
+            }
+        }
+        if self.server.as_deref().is_some_and(|server| server.trim().is_empty()) {
+            errors.push("server : adresse vide".to_string());
+        }
+        if self.token_port == 0 {
+            errors.push("token_port : doit être compris entre 1 et 65535".to_string());
+        }
+        if !(1..=MAX_INTERPOLATION_DELAY_MS).contains(&self.interpolation_delay_ms) {
+            errors.push(format!(
+                "interpolation_delay_ms : {} ms hors de l'intervalle 1..={MAX_INTERPOLATION_DELAY_MS}",
+                self.interpolation_delay_ms
+            ));
+        }
+        if self.max_extrapolation_ms > MAX_EXTRAPOLATION_MS {
+            errors.push(format!(
+                "max_extrapolation_ms : {} ms dépasse le maximum de {MAX_EXTRAPOLATION_MS} ms",
+                self.max_extrapolation_ms
+            ));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError::Invalid(errors))
+        }
+    }
+}
+
+impl ClientSettings {
+    /// Retard d'affichage des joueurs distants
+    pub fn interpolation_delay(&self) -> Duration {
+        Duration::from_millis(self.interpolation_delay_ms)
+    }
+
+    /// Durée maximale d'extrapolation des joueurs distants
+    pub fn max_extrapolation(&self) -> Duration {
+        Duration::from_millis(self.max_extrapolation_ms)
+    }
+}
+
+/// Résout l'adresse du serveur de jeu
+/// Accepte une IP ou un nom DNS, avec ou sans port (5000 par défaut),
+/// y compris une IPv6 entre crochets (`[::1]:5000`)
+///
+/// # Arguments
+/// * `server` - Adresse saisie par le joueur
+///
+/// # Returns
+/// * `Result<SocketAddr, String>` - Adresse résolue (IPv4 de préférence), ou la raison de l'échec
+pub fn resolve_server_addr(server: &str) -> Result<SocketAddr, String> {
+    let server = server.trim();
+    if let Ok(addr) = server.parse::<SocketAddr>() {
+        return Ok(addr);
+    }
+
+    // Séparation de l'hôte et du port éventuel (une IPv6 nue contient plusieurs ':')
+    let (host, port) = match server.rsplit_once(':') {
+        Some((host, port)) if !host.contains(':') || host.ends_with(']') => {
+            let port = port
+                .parse::<u16>()
+                .map_err(|_| format!("port invalide dans '{server}'"))?;
+            (host.trim_start_matches('[').trim_end_matches(']'), port)
+        }
+        _ => (server.trim_start_matches('[').trim_end_matches(']'), DEFAULT_SERVER_PORT),
+    };
+
+    let addrs: Vec<SocketAddr> = (host, port)
+        .to_socket_addrs()
+        .map_err(|err| format!("impossible de résoudre '{host}' : {err}"))?
+        .collect();
+    addrs
+        .iter()
+        .find(|addr| addr.is_ipv4())
+        .or_else(|| addrs.first())
+        .copied()
+        .ok_or_else(|| format!("aucune adresse trouvée pour '{host}'"))
+}
@@ -1,6 +1,9 @@
 // Import des modules nécessaires pour les événements et les types de données
-use bevy::ecs::event::Event;
-use multiplayer_demo::PlayerAttributes;
+use bevy::{ecs::event::Event, math::Vec3};
+use multiplayer_demo::{
+    collision::HitZone, map::MapDefinition, movement::MovementState, pickup::PickupKind,
+    snapshot::PlayerState, Weapon,
+};
 use renet::ClientId;
 
 /// Événement déclenché quand un nouveau joueur doit être spawné
@@ -16,7 +19,83 @@ pub struct PlayerSpawnEvent(pub ClientId);
 pub struct PlayerDespawnEvent(pub ClientId);
 
 /// Événement déclenché quand le lobby des joueurs doit être synchronisé
-/// Contient une HashMap complète de tous les joueurs connectés avec leurs attributs
+/// Contient le tick serveur et l'état répliqué de tous les joueurs connectés, reconstruit
+/// à partir de l'instantané différentiel reçu
 /// Utilisé pour maintenir la cohérence des données entre le serveur et les clients
 #[derive(Event)]
-pub struct LobbySyncEvent(pub std::collections::HashMap<ClientId, PlayerAttributes>);
+pub struct LobbySyncEvent {
+    pub tick: u32,
+    pub players: std::collections::HashMap<ClientId, PlayerState>,
+}
+
+/// Événement déclenché quand le serveur renvoie l'état de déplacement autoritaire du joueur local
+/// Contient le numéro de la dernière entrée traitée et l'état résultant
+/// Utilisé pour corriger la prédiction locale du déplacement
+#[derive(Event)]
+pub struct MovementAckEvent {
+    pub tick: u32,
+    pub state: MovementState,
+}
+
+/// Événement déclenché par les messages du serveur concernant la carte jouée
+/// Utilisé pour charger la carte depuis le cache ou la demander au serveur
+#[derive(Event)]
+pub enum MapSyncEvent {
+    /// Carte annoncée par le serveur à la connexion (nom et empreinte du contenu)
+    Announced { name: String, hash: u64 },
+    /// Carte complète envoyée par le serveur
+    Received(MapDefinition),
+    /// Vote de la carte suivante ouvert par le serveur en fin de partie
+    VoteStarted {
+        candidates: Vec<String>,
+        duration_secs: u32,
+    },
+    /// Nouvelle carte jouée : le niveau actuel doit être remplacé
+    Changed { name: String, hash: u64 },
+}
+
+/// Événement déclenché par les messages du serveur concernant les objets à ramasser
+/// Utilisé pour afficher ou retirer les objets de la carte
+#[derive(Event)]
+pub enum PickupSyncEvent {
+    /// Objet apparu (identifiant, nature et position)
+    Spawned {
+        id: u32,
+        kind: PickupKind,
+        position: Vec3,
+    },
+    /// Objet ramassé, retiré jusqu'à sa réapparition
+    Despawned(u32),
+    /// Changement de carte : tous les objets de l'ancienne carte sont retirés
+    Cleared,
+}
+
+/// Événement déclenché par les messages du serveur concernant les projectiles simulés
+/// Utilisé pour afficher les projectiles en vol et leurs explosions
+#[derive(Event)]
+pub enum ProjectileEvent {
+    /// Projectile lancé (identifiant, arme, position de départ et vitesse)
+    Spawned {
+        id: u32,
+        weapon: Weapon,
+        position: Vec3,
+        velocity: Vec3,
+    },
+    /// Projectile explosé (identifiant, centre et rayon de l'explosion)
+    Exploded {
+        id: u32,
+        position: Vec3,
+        radius: f32,
+    },
+    /// Changement de carte : tous les projectiles en vol sont retirés
+    Cleared,
+}
+
+/// Événement déclenché quand le serveur confirme un impact du joueur local sur un autre joueur
+/// Contient le joueur touché et la zone du corps atteinte (None pour une explosion)
+/// Utilisé par le HUD pour afficher le retour d'impact et les tirs à la tête
+#[derive(Event)]
+pub struct HitConfirmEvent {
+    pub victim: ClientId,
+    pub zone: Option<HitZone>,
+}
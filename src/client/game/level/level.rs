@@ -10,17 +10,28 @@ use bevy::{
     },
 };
 use bevy_rapier3d::prelude::*;
+use multiplayer_demo::{
+    collision::CollisionWorld,
+    map::{SPAWN_CELL, WALL_CELL},
+};
 
-use super::targets;
+use super::{pickups, targets};
 
 pub struct LevelPlugin;
 
+/// Marqueur des entités du niveau construites à partir de la carte
+/// Elles sont détruites au changement de carte puis reconstruites avec la nouvelle
+#[derive(Component)]
+pub struct LevelEntity;
+
 impl Plugin for LevelPlugin {
     fn build(&self, app: &mut App) {
         // Assurez-vous de ne pas ajouter de plugins par défaut manuellement
-        app.add_plugins(targets::TargetsPlugin)
+        app.add_plugins((targets::TargetsPlugin, pickups::PickupsPlugin))
         // .insert_resource(SpawnSpots::default())
-        .add_systems(Startup, init_level);
+        // Le niveau est construit dès que la carte envoyée par le serveur est disponible,
+        // et reconstruit à chaque changement de carte
+        .add_systems(Update, init_level.run_if(resource_added::<MazeMap>));
     }
 }
 
@@ -33,16 +44,22 @@ fn init_level(
 ) {
 
     // Ajouter le sol avec le maillage personnalisé et le matériau transparent
-    commands.spawn({
+    commands.spawn((
         SceneBundle {
             transform: Transform::IDENTITY,
             visibility: Visibility::Visible,
             ..default()
-        }
-    });
+        },
+        LevelEntity,
+    ));
+
+    // Demi-dimensions du niveau, déduites de la carte
+    let map = &maze_map.map;
+    let half_width = map.width() as f32 * map.cell_size / 2.0;
+    let half_depth = map.height() as f32 * map.cell_size / 2.0;
 
     // Créer un maillage personnalisé avec des UV ajustées pour répéter la texture
-    let ground_mesh = Mesh::from(Plane3d::new(Vec3::Y, Vec2::splat(62.)));
+    let ground_mesh = Mesh::from(Plane3d::new(Vec3::Y, Vec2::new(half_width, half_depth)));
 
     let level_material = materials.add(StandardMaterial {
         base_color: Color::srgba(0.0, 0.0, 0.0, 0.0), // Transparent (RGBA avec alpha 0)
@@ -54,7 +71,7 @@ fn init_level(
 
     // Ajouter le sol avec le maillage personnalisé et le matériau transparent
     commands.spawn((
-        Collider::cuboid(62., 1., 62.), // Collider pour le sol
+        Collider::cuboid(half_width, 1., half_depth), // Collider pour le sol
         PbrBundle {
             material: level_material.clone(),
             transform: Transform::IDENTITY,
@@ -62,10 +79,11 @@ fn init_level(
             ..default()
         },
         Shootable,
+        LevelEntity,
     ));
 
     // Créer un maillage personnalisé avec des UV ajustées pour répéter la texture
-    let sky_mesh = Mesh::from(Plane3d::new(Vec3::Y, Vec2::splat(62.)));
+    let sky_mesh = Mesh::from(Plane3d::new(Vec3::Y, Vec2::new(half_width, half_depth)));
 
     let sky_material = materials.add(StandardMaterial {
         base_color: Color::srgba(1.0, 0.0, 0.0, 0.0), // Transparent (RGBA avec alpha 0)
@@ -75,7 +93,7 @@ fn init_level(
 
 // Ajouter le ciel avec le maillage personnalisé et le matériau transparent
 commands.spawn((
-    Collider::cuboid(62., 0.1, 62.), // Collider pour le ciel
+    Collider::cuboid(half_width, 0.1, half_depth), // Collider pour le ciel
     PbrBundle {
         material: sky_material.clone(),
         transform: Transform {
@@ -87,14 +105,15 @@ commands.spawn((
         ..default()
     },
     Shootable,
+    LevelEntity,
 ));
 
-    // Définir la grille du labyrinthe (1 = mur, 0 = chemin)
-    let maze_grid = maze_map.grid.clone();
+    // Monde de collision partagé avec le serveur pour la prédiction du déplacement
+    commands.insert_resource(CollisionWorld::from_map(map));
 
-    // Génération du labyrinthe à partir de la grille
-    let maze_size = maze_grid.len();
-    let cell_size = 4.0;
+    // Génération du labyrinthe à partir de la grille de la carte
+    let cell_size = map.cell_size;
+    let wall_height = map.wall_height;
 
     // Charger la texture du sol
     let ground_texture_handle:Handle<bevy::prelude::Image> = asset_server.load("textures/moon_ground.png");
@@ -109,7 +128,7 @@ commands.spawn((
 
     let mut spawn_spots = vec![];
 
-    for (y, row) in maze_grid.iter().enumerate() {
+    for (y, row) in map.grid.iter().enumerate() {
         for (x, &cell) in row.iter().enumerate() {
             // Créer un maillage personnalisé pour chaque case du sol
             let mut ground_mesh = Mesh::from(Plane3d::new(Vec3::Y, Vec2::splat(cell_size / 2.)));
@@ -136,52 +155,51 @@ commands.spawn((
                         reflectance: 0.0,  // Pas de reflets spéculaires
                         ..default()
                     }),
-                    transform: Transform::from_xyz(
-                        x as f32 * cell_size - (maze_grid.len() as f32 * cell_size / 2.0),
-                        0.0, // Positionner le sol à la base
-                        y as f32 * cell_size - (maze_grid[0].len() as f32 * cell_size / 2.0),
-                    ),
+                    transform: Transform::from_translation(map.cell_position(x, y)), // Sol à la base
                     mesh: meshes.add(ground_mesh), // Utiliser le maillage personnalisé pour chaque case
                     ..default()
                 },
                 Shootable,
+                LevelEntity,
             ));
 
-            if cell == 1 {
-                // Placer un mur là où la grille indique un mur (1)
+            if cell == WALL_CELL {
+                // Placer un mur là où la grille indique un mur
                 commands.spawn((
-                    Collider::cuboid(cell_size / 2.0, cell_size / 2.0, cell_size / 2.0),
+                    Collider::cuboid(cell_size / 2.0, wall_height / 2.0, cell_size / 2.0),
                     PbrBundle {
                         material: wall_material.clone(),
-                        transform: Transform::from_xyz(
-                            x as f32 * cell_size - (maze_size as f32 * cell_size / 2.0),
-                            cell_size / 2.0, // hauteur des murs
-                            y as f32 * cell_size - (maze_size as f32 * cell_size / 2.0),
+                        transform: Transform::from_translation(
+                            map.cell_position(x, y) + Vec3::Y * (wall_height / 2.0), // hauteur des murs
                         ),
-                        mesh: meshes.add(Cuboid::from_length(cell_size)),
+                        mesh: meshes.add(Cuboid::new(cell_size, wall_height, cell_size)),
                         ..default()
                     },
                     Shootable,
+                    LevelEntity,
                 ));
             }
-            if cell == 2 {
+            if cell == SPAWN_CELL {
                 spawn_spots.push(Transform::from_xyz(x as f32, 0.0, y as f32));
             }
         }
     }
 
     // Ajouter des lumières
-    commands.spawn(DirectionalLightBundle {
-        directional_light: DirectionalLight {
-            illuminance: 50_000.0,
-            shadows_enabled: true,
-            color:Color::srgba(0.737, 0.024, 0.012, 0.2),
-            // color:Color::srgba(1.,1.,1.,1.),
+    commands.spawn((
+        DirectionalLightBundle {
+            directional_light: DirectionalLight {
+                illuminance: 50_000.0,
+                shadows_enabled: true,
+                color:Color::srgba(0.737, 0.024, 0.012, 0.2),
+                // color:Color::srgba(1.,1.,1.,1.),
+                ..default()
+            },
+            transform: Transform::from_xyz(100.0, 200.0, 100.0).looking_at(Vec3::ZERO, Vec3::Y),
             ..default()
         },
-        transform: Transform::from_xyz(100.0, 200.0, 100.0).looking_at(Vec3::ZERO, Vec3::Y),
-        ..default()
-    });
+        LevelEntity,
+    ));
 }
 
 
@@ -1,2 +1,3 @@
 pub mod level;
+pub mod pickups;
 pub mod targets;
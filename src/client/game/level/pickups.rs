@@ -0,0 +1,84 @@
+use bevy::prelude::*;
+use multiplayer_demo::pickup::PickupKind;
+
+/// Vitesse de rotation des objets (radians par seconde)
+const SPIN_SPEED: f32 = 1.5;
+/// Amplitude du flottement vertical des objets
+const BOB_HEIGHT: f32 = 0.15;
+
+/// Objet à ramasser affiché sur la carte
+/// Le serveur décide seul du ramassage : le client ne fait qu'afficher les objets annoncés
+#[derive(Component)]
+pub struct PickupItem {
+    pub id: u32,      // Identifiant de l'objet côté serveur
+    pub origin: Vec3, // Position du centre de l'objet, autour de laquelle il flotte
+}
+
+pub struct PickupsPlugin;
+
+impl Plugin for PickupsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, animate_pickups);
+    }
+}
+
+/// Crée l'entité affichant un objet à ramasser
+/// Chaque nature d'objet a sa forme et sa couleur
+///
+/// # Arguments
+/// * `commands` - Commandes Bevy
+/// * `meshes` - Maillages
+/// * `materials` - Matériaux
+/// * `id` - Identifiant de l'objet côté serveur
+/// * `kind` - Nature de l'objet
+/// * `position` - Position du centre de l'objet
+///
+/// # Returns
+/// * `Entity` - Entité créée
+pub fn spawn_pickup_item(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+    id: u32,
+    kind: PickupKind,
+    position: Vec3,
+) -> Entity {
+    let (mesh, color) = match kind {
+        PickupKind::Health => (meshes.add(Cuboid::new(0.6, 0.6, 0.6)), Color::srgb(0.1, 0.9, 0.2)),
+        PickupKind::Armor => (meshes.add(Sphere::new(0.4)), Color::srgb(0.2, 0.4, 1.0)),
+        PickupKind::Ammo(_) => (meshes.add(Cuboid::new(0.5, 0.3, 0.3)), Color::srgb(0.95, 0.8, 0.1)),
+        PickupKind::Weapon(_) => (meshes.add(Capsule3d::new(0.2, 0.8)), Color::srgb(0.9, 0.3, 0.05)),
+    };
+    let material = materials.add(StandardMaterial {
+        base_color: color,
+        emissive: LinearRgba::from(color) * 2.0, // Visible dans les couloirs sombres
+        ..default()
+    });
+
+    commands
+        .spawn((
+            PbrBundle {
+                mesh,
+                material,
+                transform: Transform::from_translation(position),
+                ..default()
+            },
+            PickupItem {
+                id,
+                origin: position,
+            },
+            Name::new(format!("Pickup {id} ({kind})")),
+        ))
+        .id()
+}
+
+/// Fait tourner et flotter les objets à ramasser
+fn animate_pickups(time: Res<Time>, mut query: Query<(&PickupItem, &mut Transform)>) {
+    let elapsed = time.elapsed_seconds();
+    for (item, mut transform) in query.iter_mut() {
+        // Déphasage par objet pour que les objets ne flottent pas tous ensemble
+        let phase = item.id as f32 * 0.7;
+        transform.translation = item.origin + Vec3::Y * BOB_HEIGHT * (elapsed * 2.0 + phase).sin();
+        transform.rotation = Quat::from_rotation_y(elapsed * SPIN_SPEED + phase);
+    }
+}
@@ -4,7 +4,6 @@ use bevy::prelude::*;
 pub struct PlayerInput {
     pub movement: Vec2,
     pub jump: bool,
-    pub crouch: bool,      // Ajout de l'état accroupi
-    pub crouch_speed: f32, // Ajout de la vitesse accroupi
-    pub run: f32,          // Vitesse de course
+    pub crouch: bool, // Ajout de l'état accroupi
+    pub run: bool,    // Course demandée
 }
@@ -1,8 +1,11 @@
 use std::collections::HashMap;
 
 use bevy::{core_pipeline::Skybox, prelude::*};
-use bevy_rapier3d::prelude::*;
-use multiplayer_demo::{PlayerAttributes, PlayerLobby, Weapon};
+use multiplayer_demo::{
+    movement::{MovementState, MOVEMENT_TIMESTEP},
+    weapons::WeaponDefinitions,
+    PlayerAttributes, PlayerLobby, Weapon,
+};
 
 use crate::{
     game::{level::level::reinterpret_cubemap, player::weapon},
@@ -13,7 +16,7 @@ use super::{
     camera_controller,
     input::*,
     player_movement::*,
-    player_shooting::{update_player, Shootable, TracerSpawnSpot},
+    player_shooting::{FiringState, update_player, Shootable, TracerSpawnSpot},
 };
 use crate::game::{math::coordinates::blender_to_world, shooting};
 pub struct PlayerPlugin;
@@ -23,9 +26,13 @@ pub struct PlayerInitialized(pub bool);
 
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(shooting::tracer::TracerPlugin)
+        app.add_plugins((shooting::tracer::TracerPlugin, shooting::projectile::ProjectilePlugin))
             .init_resource::<PlayerInput>()
             .init_resource::<PlayerInitialized>()
+            .init_resource::<MovementPrediction>()
+            .init_resource::<FiringState>()
+            // Même cadence que la simulation du serveur pour que la prédiction reste identique
+            .insert_resource(Time::<Fixed>::from_seconds(MOVEMENT_TIMESTEP as f64))
             // .add_systems(Startup, setup_ui_camera)
             .add_systems(
                 Update,
@@ -33,6 +40,7 @@ impl Plugin for PlayerPlugin {
                     try_init_player,
                     reinterpret_cubemap.run_if(resource_exists::<SkyCubeMap>),
                     update_movement_input,
+                    apply_movement_ack,
                     update_player,
                     camera_controller::update_camera_controller,
                     weapon::switch_weapon,
@@ -42,6 +50,7 @@ impl Plugin for PlayerPlugin {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn try_init_player(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
@@ -49,6 +58,8 @@ pub fn try_init_player(
     my_id: Res<MyClientId>,
     mut initialized: ResMut<PlayerInitialized>,
     myusername: Res<MyUsername>,
+    mut prediction: ResMut<MovementPrediction>,
+    weapons: Res<WeaponDefinitions>,
 ) {
     // Quitte si déjà initialisé
     if initialized.0 {
@@ -88,51 +99,21 @@ pub fn try_init_player(
             ))
             .id();
 
-        // Spawn des armes, invisibles par défaut
-        let gun_entity = commands
-            .spawn(SceneBundle {
-                scene: asset_server.load("models/guntest2.glb#Scene0"),
-                transform: Transform::IDENTITY,
-                visibility: Visibility::Hidden,
-                ..Default::default()
+        // Spawn des armes, invisibles par défaut (modèles lus dans les définitions des armes)
+        let weapon_entities: HashMap<Weapon, Entity> = Weapon::ALL
+            .into_iter()
+            .map(|weapon| {
+                let entity = commands
+                    .spawn(SceneBundle {
+                        scene: asset_server.load(weapons.get(weapon).model.clone()),
+                        transform: Transform::IDENTITY,
+                        visibility: Visibility::Hidden,
+                        ..Default::default()
+                    })
+                    .id();
+                (weapon, entity)
             })
-            .id();
-
-        let shotgun_entity = commands
-            .spawn(SceneBundle {
-                scene: asset_server.load("models/test.glb#Scene0"),
-                transform: Transform::IDENTITY,
-                visibility: Visibility::Hidden,
-                ..Default::default()
-            })
-            .id();
-
-        let gatling_entity = commands
-            .spawn(SceneBundle {
-                scene: asset_server.load("models/minigun.glb#Scene0"),
-                transform: Transform::IDENTITY,
-                visibility: Visibility::Hidden,
-                ..Default::default()
-            })
-            .id();
-
-        let rocket_launcher_entity = commands
-            .spawn(SceneBundle {
-                scene: asset_server.load("models/rocket.glb#Scene0"),
-                transform: Transform::IDENTITY,
-                visibility: Visibility::Hidden,
-                ..Default::default()
-            })
-            .id();
-
-        let bfg_entity = commands
-            .spawn(SceneBundle {
-                scene: asset_server.load("models/bfg2.glb#Scene0"),
-                transform: Transform::IDENTITY,
-                visibility: Visibility::Hidden,
-                ..Default::default()
-            })
-            .id();
+            .collect();
 
         // Point d'apparition des projectiles/tracers dans l'espace du joueur
         let spawn_spot = blender_to_world(Vec3::new(0.530462, 2.10557, -0.466568));
@@ -159,43 +140,27 @@ pub fn try_init_player(
                     velocity: attr.velocity,
                     owned_weapon: attr.owned_weapon.clone(),
                     actual_weapon: attr.actual_weapon,
+                    magazine: attr.magazine.clone(),
                     ammo: attr.ammo.clone(),
-                    entities: HashMap::from([
-                        (Weapon::Gun, gun_entity),
-                        (Weapon::Shotgun, shotgun_entity),
-                        (Weapon::Gatling, gatling_entity),
-                        (Weapon::RocketLauncher, rocket_launcher_entity),
-                        (Weapon::Bfg, bfg_entity),
-                    ]),
+                    crouched: attr.crouched,
+                    entities: weapon_entities.clone(),
                 },
                 SpatialBundle {
-                    transform: Transform::from_translation(Vec3::new(
-                        spawn.0,
-                        spawn.1 + 3.,
-                        spawn.2,
-                    )),
+                    transform: Transform::from_translation(Vec3::from(spawn)),
                     inherited_visibility: InheritedVisibility::VISIBLE,
                     ..Default::default()
                 },
-                Collider::cuboid(1., 2., 1.),
-                RigidBody::KinematicPositionBased,
-                KinematicCharacterController {
-                    up: Vec3::Y,
-                    offset: CharacterLength::Absolute(0.01),
-                    ..default()
-                },
             ))
             .id();
 
+        // La prédiction du déplacement démarre au point de spawn donné par le serveur
+        prediction.state = MovementState::at(Vec3::from(spawn));
+
         // Organise la hiérarchie : caméra enfant du joueur, armes et point d'apparition enfant de la caméra
-        commands.entity(camera_entity).push_children(&[
-            tracer_spawn_entity,
-            gun_entity,
-            shotgun_entity,
-            gatling_entity,
-            rocket_launcher_entity,
-            bfg_entity,
-        ]);
+        commands.entity(camera_entity).add_child(tracer_spawn_entity);
+        for weapon in Weapon::ALL {
+            commands.entity(camera_entity).add_child(weapon_entities[&weapon]);
+        }
         commands.entity(player_entity).add_child(camera_entity);
 
         // Marque comme initialisé pour ne pas refaire la création
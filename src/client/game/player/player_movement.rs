@@ -1,9 +1,32 @@
+use std::collections::VecDeque;
+
 use bevy::prelude::*;
-use bevy_rapier3d::prelude::*;
-use multiplayer_demo::PlayerAttributes;
+use multiplayer_demo::{
+    collision::CollisionWorld,
+    movement::{self, MovementInput, MovementState},
+    PlayerAttributes,
+};
+
+use crate::events::MovementAckEvent;
 
 use super::{camera_controller::CameraController, input::*};
 
+/// Nombre maximal d'entrées conservées en attente d'acquittement (2 secondes)
+const MAX_PENDING_INPUTS: usize = 120;
+
+/// Écart au-delà duquel une correction du serveur est signalée dans les logs
+const RECONCILE_EPSILON: f32 = 0.01;
+
+/// Prédiction locale du déplacement du joueur
+/// Conserve les entrées envoyées et pas encore acquittées pour les rejouer après une correction
+#[derive(Resource, Default)]
+pub struct MovementPrediction {
+    pub state: MovementState,              // État prédit du joueur local
+    pub next_tick: u32,                    // Numéro de la prochaine entrée
+    pub last_ack: u32,                     // Numéro de la dernière entrée acquittée
+    pub pending: VecDeque<MovementInput>,  // Entrées non acquittées, de la plus ancienne à la plus récente
+}
+
 /// Système qui lit les touches clavier et met à jour les intentions de mouvement du joueur
 pub fn update_movement_input(keys: Res<ButtonInput<KeyCode>>, mut input: ResMut<PlayerInput>) {
     input.movement = Vec2::ZERO;
@@ -22,85 +45,95 @@ pub fn update_movement_input(keys: Res<ButtonInput<KeyCode>>, mut input: ResMut<
     }
 
     input.jump = keys.pressed(KeyCode::Space);
-
-    if keys.pressed(KeyCode::ControlLeft) {
-        input.crouch = true;
-        input.crouch_speed = 0.6;
-    } else {
-        input.crouch = false;
-        input.crouch_speed = 1.0;
-    }
-
-    if keys.pressed(KeyCode::ShiftLeft) {
-        input.run = 2.0 * input.crouch_speed;
-    } else {
-        input.run = 1.0 * input.crouch_speed;
-    }
+    input.crouch = keys.pressed(KeyCode::ControlLeft);
+    input.run = keys.pressed(KeyCode::ShiftLeft);
 }
 
-/// Système qui applique les déplacements physiques du joueur à chaque tick fixe (physique)
+/// Système qui prédit le déplacement du joueur local à chaque tick fixe
+/// Applique la même simulation que le serveur et mémorise l'entrée pour l'envoyer
 pub fn update_movement(
-    time: Res<Time<Fixed>>,
     input: Res<PlayerInput>,
-    mut camera_query: Query<&mut Transform, (With<Camera>, With<CameraController>)>,
+    collision_world: Res<CollisionWorld>,
+    mut prediction: ResMut<MovementPrediction>,
     camera_controller_query: Query<&CameraController>,
-    mut player_query: Query<(
-        &mut PlayerAttributes,
-        &mut KinematicCharacterController,
-        Option<&KinematicCharacterControllerOutput>,
-    )>,
+    mut camera_query: Query<&mut Transform, (With<Camera>, With<CameraController>)>,
+    mut player_query: Query<(&mut PlayerAttributes, &mut Transform), Without<Camera>>,
 ) {
     // Récupère le contrôleur de caméra (orientation du joueur)
     let Ok(camera_controller) = camera_controller_query.get_single() else {
         return;
     };
+    let Ok((mut player, mut transform)) = player_query.get_single_mut() else {
+        return;
+    };
+
+    // Construction de l'entrée du tick à partir du clavier et de la vue
+    prediction.next_tick += 1;
+    let command = MovementInput {
+        tick: prediction.next_tick,
+        movement: input.movement,
+        jump: input.jump,
+        crouch: input.crouch,
+        run: input.run,
+        yaw: camera_controller.rotation.y.to_radians(),
+        pitch: camera_controller.rotation.x.to_radians(),
+    };
 
-    let yaw_radians = camera_controller.rotation.y.to_radians();
+    // Prédiction locale avec la simulation partagée
+    prediction.state = movement::step(&prediction.state, &command, &collision_world);
+    prediction.pending.push_back(command);
+    while prediction.pending.len() > MAX_PENDING_INPUTS {
+        prediction.pending.pop_front();
+    }
 
-    // Calcule les vecteurs avant/droite en fonction du yaw
-    let forward = Vec2::new(yaw_radians.sin(), yaw_radians.cos());
-    let right = Vec2::new(forward.y, -forward.x);
+    transform.translation = prediction.state.position;
+    player.position = prediction.state.position.into();
+    player.velocity = prediction.state.velocity;
 
-    // Vitesse de transition accroupi / debout
+    // Ajuste la hauteur de la caméra (accroupi / debout)
     let crouch_transition_speed = 4.0;
+    let target_camera_y = if input.crouch {
+        movement::CROUCH_EYE_HEIGHT
+    } else {
+        movement::STANDING_EYE_HEIGHT
+    };
+    let blend = (crouch_transition_speed * movement::MOVEMENT_TIMESTEP).min(1.0);
+    for mut cam_transform in camera_query.iter_mut() {
+        let current = cam_transform.translation.y;
+        cam_transform.translation.y = current + (target_camera_y - current) * blend;
+    }
+}
 
-    for (mut player, mut controller, controller_output) in player_query.iter_mut() {
-        // Gestion du saut si le joueur est au sol
-        if let Some(output) = controller_output {
-            if output.grounded {
-                player.velocity.y = 0.0;
-                if input.jump {
-                    player.velocity.y = 12.;
-                }
-            }
-        }
-
-        // Mouvement horizontal : si un input est actif, calcule la direction et applique la vitesse
-        if let Some(dir) = (forward * input.movement.x + right * input.movement.y).try_normalize() {
-            player.velocity.x = dir.x * 4. * input.run * 2.;
-            player.velocity.z = dir.y * 4. * input.run * 2.;
-        } else {
-            // Aucun input → stop le mouvement horizontal (empêche la glisse)
-            player.velocity.x = 0.0;
-            player.velocity.z = 0.0;
-        }
-
-        // Gravité (accélération vers le bas)
-        player.velocity.y -= 40. * time.timestep().as_secs_f32();
-
-        // Application du déplacement basé sur la vélocité calculée
-        controller.translation = Some(player.velocity * time.timestep().as_secs_f32());
-
-        // Ajuste la hauteur de la caméra (accroupi / debout)
-        let target_camera_y = if input.crouch { 0.5 } else { 1.5 };
-
-        for mut cam_transform in camera_query.iter_mut() {
-            let current = cam_transform.translation.y;
-            let new = current
-                + (target_camera_y - current)
-                    * crouch_transition_speed
-                    * time.timestep().as_secs_f32();
-            cam_transform.translation.y = new - 0.14; // ⚠️ Décalage spécifique à ton modèle 3D
-        }
+/// Système de réconciliation avec l'état autoritaire du serveur
+/// Repart de l'état acquitté et rejoue les entrées que le serveur n'a pas encore traitées
+pub fn apply_movement_ack(
+    mut ack_events: EventReader<MovementAckEvent>,
+    mut prediction: ResMut<MovementPrediction>,
+    collision_world: Res<CollisionWorld>,
+) {
+    let Some(ack) = ack_events.read().max_by_key(|ack| ack.tick) else {
+        return;
+    };
+
+    // Acquittement plus ancien que le dernier traité (paquet arrivé dans le désordre)
+    if ack.tick < prediction.last_ack {
+        return;
+    }
+    prediction.last_ack = ack.tick;
+
+    let ack_tick = ack.tick;
+    prediction.pending.retain(|input| input.tick > ack_tick);
+
+    let mut state = ack.state;
+    for input in prediction.pending.iter() {
+        state = movement::step(&state, input, &collision_world);
+    }
+
+    if state.position.distance(prediction.state.position) > RECONCILE_EPSILON {
+        debug!(
+            "Correction du serveur : {:?} -> {:?}",
+            prediction.state.position, state.position
+        );
     }
-}
\ No newline at end of file
+    prediction.state = state;
+}
@@ -1,35 +1,95 @@
 use super::camera_controller::CameraController;
-use crate::game::{
-    level::targets::{DeadTarget, Target},
-    shooting,
+use crate::{
+    game::{
+        level::targets::{DeadTarget, Target},
+        shooting,
+    },
+    resources::{InterpolationSettings, MyClientId, ServerClock},
 };
 use bevy::{prelude::*, window::PrimaryWindow};
 use bevy_rapier3d::{plugin::RapierContext, prelude::*};
-use multiplayer_demo::{PlayerAttributes, PlayerEntity, PlayerShoot};
-use renet::{DefaultChannel, RenetClient};
+use multiplayer_demo::{
+    weapons::{FireMode, WeaponDefinitions},
+    ClientMessage, PlayerAttributes, PlayerEntity, PlayerShoot, Weapon,
+};
+use renet::RenetClient;
+use std::collections::HashMap;
+
+/// Distance du point visé envoyé au serveur quand le rayon local ne touche rien
+const MAX_AIM_DISTANCE: f32 = 100.0;
 
 /// Marqueur pour les entités pouvant être touchées par les tirs (targets, joueurs)
 #[derive(Component)]
 pub struct Shootable;
 
+/// État du tir du joueur local (instants en secondes depuis le lancement)
+/// Le serveur applique la même cadence, la même préparation et les mêmes rechargements, et refuse
+/// les tirs qui ne les respectent pas
+#[derive(Resource, Default)]
+pub struct FiringState {
+    pub ready_at: HashMap<Weapon, f64>, // Instant à partir duquel chaque arme peut tirer de nouveau
+    pub held: Option<(Weapon, f64)>, // Gâchette maintenue d'une arme à préparation et instant de la pression
+    pub reload: Option<(Weapon, f64)>, // Arme en cours de rechargement et instant de la fin
+}
+
+/// Annonce au serveur la pression ou le relâchement de la gâchette d'une arme à préparation
+fn send_trigger(client: &mut RenetClient, weapon: Weapon, pressed: bool) {
+    let message = ClientMessage::Trigger { weapon, pressed };
+    client.send_message(message.channel(), bincode::serialize(&message).unwrap());
+}
+
+/// Commence le rechargement de l'arme équipée s'il est utile et l'annonce au serveur
+///
+/// # Arguments
+/// * `firing` - État du tir local
+/// * `client` - Client renet
+/// * `player` - Attributs du joueur local
+/// * `weapons` - Définitions des armes (taille des chargeurs, durée)
+/// * `now` - Instant courant
+fn start_reload(
+    firing: &mut FiringState,
+    client: &mut RenetClient,
+    player: &PlayerAttributes,
+    weapons: &WeaponDefinitions,
+    now: f64,
+) {
+    let weapon = player.actual_weapon;
+    let definition = weapons.get(weapon);
+    let magazine = player.magazine.get(&weapon).copied().unwrap_or(0.);
+    let reserve = player.ammo.get(&weapon).copied().unwrap_or(0.);
+    if firing.reload.is_some()
+        || !player.has_weapon(&weapon)
+        || !definition.can_reload(magazine, reserve)
+    {
+        return;
+    }
+    firing.reload = Some((weapon, now + definition.reload_secs as f64));
+    let message = ClientMessage::Reload(weapon);
+    client.send_message(message.channel(), bincode::serialize(&message).unwrap());
+}
+
 /// Position d'apparition du tracer visuel (ex : canon de l'arme)
 #[derive(Component)]
 pub struct TracerSpawnSpot;
 
-/// Gère le tir du joueur local : collision, dégâts, envoi réseau, effets visuels
+/// Gère le tir du joueur local : rechargement, collision, dégâts, envoi réseau, effets visuels
 pub fn update_player(
-    mouse_input: Res<ButtonInput<MouseButton>>,
+    (mouse_input, keys): (Res<ButtonInput<MouseButton>>, Res<ButtonInput<KeyCode>>),
     mut commands: Commands,
     rapier_context: Res<RapierContext>,
     mut player_query: Query<(&mut PlayerAttributes, &mut Transform)>,
     camera_query: Query<(&Camera, &GlobalTransform), With<CameraController>>,
     window_query: Query<&Window, With<PrimaryWindow>>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
+    (mut meshes, mut materials): (ResMut<Assets<Mesh>>, ResMut<Assets<StandardMaterial>>),
     target_query: Query<Option<&Target>, With<Shootable>>,
     player_entity_query: Query<&PlayerEntity>,
     spawn_spot: Query<&GlobalTransform, With<TracerSpawnSpot>>,
     mut client: ResMut<RenetClient>,
+    (clock, interpolation): (Res<ServerClock>, Res<InterpolationSettings>),
+    time: Res<Time>,
+    my_id: Res<MyClientId>,
+    weapons: Res<WeaponDefinitions>,
+    mut firing: ResMut<FiringState>,
 ) {
     // Récupère la position du canon de l'arme
     let spawn_spot = match spawn_spot.get_single() {
@@ -51,18 +111,95 @@ pub fn update_player(
 
     // Récupère le joueur local
     if let Ok((mut player, _)) = player_query.get_single_mut() {
-        // Tir déclenché par clic gauche
-        if mouse_input.just_pressed(MouseButton::Left) {
-            let actual_weapon = player.actual_weapon;
-
-            // Vérifie les munitions restantes pour l'arme sélectionnée
-            if let Some(ammo) = player.ammo.get_mut(&actual_weapon) {
-                if *ammo <= 0. {
-                    return; // Pas de munitions, ne tire pas
+        let actual_weapon = player.actual_weapon;
+        let fire_mode = weapons.get(actual_weapon).fire_mode;
+        let now = time.elapsed_seconds_f64();
+        let pressed = mouse_input.pressed(MouseButton::Left);
+
+        // Rechargement : annulé au changement d'arme, achevé localement à son terme
+        // (le serveur envoie l'inventaire à jour à la fin du sien)
+        if let Some((weapon, ends_at)) = firing.reload {
+            if weapon != actual_weapon {
+                firing.reload = None;
+            } else if now >= ends_at {
+                firing.reload = None;
+                let attr = &mut *player;
+                let magazine = attr.magazine.entry(weapon).or_insert(0.);
+                let reserve = attr.ammo.entry(weapon).or_insert(0.);
+                weapons.get(weapon).reload(magazine, reserve);
+            }
+        }
+        // Rechargement demandé par le joueur (touche R)
+        if keys.just_pressed(KeyCode::KeyR) {
+            start_reload(&mut firing, &mut client, &player, &weapons, now);
+        }
+
+        // Tir déclenché selon le mode de tir de l'arme (clic gauche)
+        let held_for = firing
+            .held
+            .filter(|(weapon, _)| *weapon == actual_weapon)
+            .map(|(_, since)| now - since);
+        let fire = match fire_mode {
+            FireMode::SemiAuto => mouse_input.just_pressed(MouseButton::Left),
+            FireMode::FullAuto => pressed,
+            FireMode::SpinUp { spin_up_secs } => {
+                pressed && held_for.is_some_and(|held| held >= spin_up_secs as f64)
+            }
+            FireMode::Charge { charge_secs } => {
+                !pressed && held_for.is_some_and(|held| held >= charge_secs as f64)
+            }
+        };
+
+        // Gâchette relâchée ou arme changée : fin de la préparation
+        // Un tir chargé n'est pas suivi d'un relâchement : le serveur consomme la charge avec le tir
+        if let Some((weapon, _)) = firing.held {
+            if !pressed || weapon != actual_weapon {
+                firing.held = None;
+                if !fire {
+                    send_trigger(&mut client, weapon, false);
                 }
-                *ammo -= 1.0;
-            } else {
-                return; // Arme non trouvée dans les munitions (erreur possible)
+            }
+        }
+        // Gâchette pressée sur une arme à préparation : début de la rotation ou de la charge
+        if pressed && firing.held.is_none() && fire_mode.wind_up().is_some() {
+            firing.held = Some((actual_weapon, now));
+            send_trigger(&mut client, actual_weapon, true);
+        }
+
+        if fire {
+            if !player.has_weapon(&actual_weapon) {
+                return;
+            }
+
+            // Pas de tir pendant un rechargement
+            if firing.reload.is_some() {
+                return;
+            }
+
+            // Chargeur vide : rechargement automatique s'il reste des munitions en réserve
+            let magazine = player.magazine.get(&actual_weapon).copied().unwrap_or(0.);
+            if magazine < 1.0 {
+                start_reload(&mut firing, &mut client, &player, &weapons, now);
+                return;
+            }
+
+            // Respecte la cadence de tir de l'arme
+            let ready_at = firing.ready_at.entry(actual_weapon).or_insert(0.0);
+            if now < *ready_at {
+                return;
+            }
+            *ready_at = now + 1.0 / weapons.get(actual_weapon).fire_rate as f64;
+
+            // Décompte la munition tirée du chargeur
+            // Le décompte local n'est qu'une prédiction : le serveur corrige l'inventaire s'il diverge
+            let magazine = magazine - 1.0;
+            player.magazine.insert(actual_weapon, magazine);
+
+            // Chargeur vidé par ce tir : le serveur lance de lui-même le rechargement, le client aussi
+            let definition = weapons.get(actual_weapon);
+            let reserve = player.ammo.get(&actual_weapon).copied().unwrap_or(0.);
+            if magazine < 1.0 && definition.can_reload(magazine, reserve) {
+                firing.reload = Some((actual_weapon, now + definition.reload_secs as f64));
             }
 
             // Calcule un rayon partant du centre de l'écran (viseur)
@@ -74,7 +211,13 @@ pub fn update_player(
             };
 
             // Prépare un filtre de requête pour ne tester que les entités "Shootable"
-            let predicate = |entity: Entity| target_query.get(entity).is_ok();
+            // Le modèle du joueur local est exclu : la caméra se trouve à l'intérieur
+            let predicate = |entity: Entity| {
+                target_query.get(entity).is_ok()
+                    && player_entity_query
+                        .get(entity)
+                        .map_or(true, |player_entity| player_entity.0 != my_id.0)
+            };
             let query_filter = QueryFilter::new().predicate(&predicate);
 
             // Lance un rayon dans le monde 3D pour détecter les collisions
@@ -86,44 +229,76 @@ pub fn update_player(
                 query_filter,
             );
 
-            if let Some((entity, intersection)) = hit {
+            // Point visé : impact local ou point lointain dans la direction du viseur
+            // Le serveur ne se sert que de la direction pour retracer le tir dans son propre monde
+            let aim_point = hit
+                .map(|(_, intersection)| intersection.point)
+                .unwrap_or(ray.origin + ray.direction * MAX_AIM_DISTANCE);
+
+            // Tick auquel les joueurs distants sont affichés (interpolation), utilisé par le serveur
+            // pour replacer les cibles là où le tireur les voyait
+            let seen_tick = clock
+                .render_tick(time.elapsed_seconds_f64(), interpolation.delay)
+                .map(|tick| tick.max(0.0).floor() as u32)
+                .unwrap_or(clock.latest_tick);
+
+            // Graine de la dispersion des plombs : le serveur retrouve les mêmes plombs
+            let seed = rand::random::<u32>();
+            let muzzle = spawn_spot.translation();
+            let shoot_msg = PlayerShoot {
+                weapon: actual_weapon,
+                from: muzzle,
+                to: aim_point,
+                tick: seen_tick,
+                seed,
+            };
+            let message = ClientMessage::Shoot(shoot_msg);
+            client.send_message(message.channel(), bincode::serialize(&message).unwrap());
+
+            // Plombs tracés depuis le canon, dans la direction utilisée par le serveur
+            let Some(aim) = (aim_point - muzzle).try_normalize() else {
+                return;
+            };
+            // Projectiles simulés : affichés d'après les annonces du serveur
+            if definition.projectile.is_simulated() {
+                return;
+            }
+            let tracer_mesh = meshes.add(Cuboid::from_size(Vec3::new(0.15, 0.15, 1.0)));
+            let tracer_material = materials.add(StandardMaterial {
+                base_color: Color::srgb(1.0, 0.0, 0.0),
+                unlit: true,
+                ..default()
+            });
+            for pellet in definition.pellet_directions(aim, seed) {
+                let Some((entity, intersection)) = rapier_context.cast_ray_and_get_normal(
+                    muzzle,
+                    pellet,
+                    definition.range,
+                    true,
+                    query_filter,
+                ) else {
+                    continue;
+                };
+
                 // Cible statique : insérer un marqueur de mort (DeadTarget)
                 if let Ok(Some(_target)) = target_query.get(entity) {
                     commands.entity(entity).insert(DeadTarget);
                 }
 
-                // Joueur ennemi touché : envoi du message au serveur
+                // Joueur ennemi visé localement (les dégâts sont décidés par le serveur)
                 if let Ok(PlayerEntity(victim_id)) = player_entity_query.get(entity) {
                     info!("🎯 Joueur touché ! client_id = {:?}", victim_id);
-
-                    let shoot_msg = PlayerShoot {
-                        weapon: actual_weapon,
-                        from: spawn_spot.translation(),
-                        to: intersection.point,
-                    };
-                    let msg = bincode::serialize(&shoot_msg).unwrap();
-                    client.send_message(DefaultChannel::ReliableOrdered, msg);
                 }
 
-                // Crée un tracer visuel (lazer rouge) entre spawn et point d'impact
-                let tracer_material = StandardMaterial {
-                    base_color: Color::srgb(1.0, 0.0, 0.0),
-                    unlit: true,
-                    ..default()
-                };
-
+                // Crée un tracer visuel (lazer rouge) par plomb entre le canon et le point d'impact
                 commands.spawn((
                     PbrBundle {
                         transform: Transform::from_translation(Vec3::splat(f32::MAX)), // Hors champ initialement
-                        mesh: meshes.add(Cuboid::from_size(Vec3::new(0.15, 0.15, 1.0))),
-                        material: materials.add(tracer_material),
+                        mesh: tracer_mesh.clone(),
+                        material: tracer_material.clone(),
                         ..default()
                     },
-                    shooting::tracer::BulletTracer::new(
-                        spawn_spot.translation(),
-                        intersection.point,
-                        250.0,
-                    ),
+                    shooting::tracer::BulletTracer::new(muzzle, intersection.point, 250.0),
                 ));
             }
         }
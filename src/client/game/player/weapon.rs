@@ -2,13 +2,16 @@ use bevy::prelude::*;
 
 use bevy::input::mouse::MouseWheel;
 use bevy::input::mouse::MouseScrollUnit;
+use multiplayer_demo::ClientMessage;
 use multiplayer_demo::PlayerAttributes;
 use multiplayer_demo::Weapon;
+use renet::RenetClient;
 
 pub fn switch_weapon(
     mut player_query: Query<&mut PlayerAttributes>,
     mut scroll_events: EventReader<MouseWheel>, // Pour détecter les événements de la molette
     query_visibility: Query<&mut Visibility, With<Handle<Scene>>>,
+    mut client: ResMut<RenetClient>, // Pour annoncer le changement d'arme au serveur
 ) {
     if let Ok(mut player) = player_query.get_single_mut() {
         let mut prev = false;
@@ -30,6 +33,10 @@ pub fn switch_weapon(
                     if let Some(new_weapon) = get_next_weapon(&player, prev) {
                         // Mise à jour de l'arme actuelle du joueur
                         player.actual_weapon = new_weapon;
+
+                        // Le serveur vérifie que l'arme est bien possédée
+                        let message = ClientMessage::SwitchWeapon(new_weapon);
+                        client.send_message(message.channel(), bincode::serialize(&message).unwrap());
                     }
                 }
             }
@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use multiplayer_demo::{
+    weapons::{ProjectileKind, WeaponDefinitions},
+    Weapon,
+};
+
+use crate::events::ProjectileEvent;
+
+/// Durée d'affichage d'une explosion (secondes)
+const EXPLOSION_DURATION: f32 = 0.35;
+/// Échelle du modèle affiché pour un projectile en vol
+const PROJECTILE_SCALE: f32 = 0.5;
+/// Rayon de la sphère affichée pour une boule d'énergie
+const ENERGY_BALL_RADIUS: f32 = 0.6;
+/// Couleur des boules d'énergie et de leur détonation
+const ENERGY_BALL_COLOR: Color = Color::srgb(0.3, 1.0, 0.4);
+/// Couleur des autres explosions
+const EXPLOSION_COLOR: Color = Color::srgb(1.0, 0.5, 0.1);
+
+/// Projectile simulé par le serveur, avancé localement en ligne droite jusqu'à son explosion
+#[derive(Component)]
+pub struct ProjectileVisual {
+    pub id: u32,        // Identifiant du projectile côté serveur
+    pub weapon: Weapon, // Arme ayant tiré le projectile
+    pub velocity: Vec3, // Vitesse annoncée par le serveur
+}
+
+/// Explosion affichée : une sphère qui grandit jusqu'au rayon de l'explosion puis disparaît
+#[derive(Component)]
+pub struct ExplosionVisual {
+    pub radius: f32,     // Rayon de l'explosion
+    pub time_alive: f32, // Temps écoulé depuis l'explosion
+}
+
+pub struct ProjectilePlugin;
+
+impl Plugin for ProjectilePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                handle_projectile_events,
+                move_projectiles.after(handle_projectile_events),
+                update_explosions,
+            ),
+        );
+    }
+}
+
+/// Affiche les projectiles lancés et les explosions annoncés par le serveur
+/// Les événements sont appliqués dans l'ordre : un projectile lancé et explosé dans la même
+/// image n'est jamais laissé en vol
+fn handle_projectile_events(
+    mut commands: Commands,
+    mut events: EventReader<ProjectileEvent>,
+    projectiles: Query<(Entity, &ProjectileVisual)>,
+    asset_server: Res<AssetServer>,
+    weapons: Res<WeaponDefinitions>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // Projectiles affichés, complétés au fil des événements de cette image
+    let mut displayed: HashMap<u32, (Entity, Weapon)> = projectiles
+        .iter()
+        .map(|(entity, projectile)| (projectile.id, (entity, projectile.weapon)))
+        .collect();
+
+    for event in events.read() {
+        match event {
+            ProjectileEvent::Spawned {
+                id,
+                weapon,
+                position,
+                velocity,
+            } => {
+                let transform =
+                    Transform::from_translation(*position).looking_to(*velocity, Vec3::Y);
+                let mut entity = match weapons.get(*weapon).projectile {
+                    // Boule d'énergie : sphère lumineuse éclairant son trajet
+                    ProjectileKind::EnergyBall => {
+                        let mut entity = commands.spawn(PbrBundle {
+                            mesh: meshes.add(Sphere::new(ENERGY_BALL_RADIUS)),
+                            material: materials.add(StandardMaterial {
+                                base_color: ENERGY_BALL_COLOR,
+                                emissive: LinearRgba::from(ENERGY_BALL_COLOR) * 8.0,
+                                unlit: true,
+                                ..default()
+                            }),
+                            transform,
+                            ..default()
+                        });
+                        entity.with_children(|parent| {
+                            parent.spawn(PointLightBundle {
+                                point_light: PointLight {
+                                    color: ENERGY_BALL_COLOR,
+                                    intensity: 200_000.0,
+                                    range: 12.0,
+                                    ..default()
+                                },
+                                ..default()
+                            });
+                        });
+                        entity
+                    }
+                    _ => commands.spawn(SceneBundle {
+                        scene: asset_server.load(weapons.get(*weapon).model.clone()),
+                        transform: transform.with_scale(Vec3::splat(PROJECTILE_SCALE)),
+                        ..default()
+                    }),
+                };
+                entity.insert((
+                    ProjectileVisual {
+                        id: *id,
+                        weapon: *weapon,
+                        velocity: *velocity,
+                    },
+                    Name::new(format!("Projectile {id} ({weapon})")),
+                ));
+                displayed.insert(*id, (entity.id(), *weapon));
+            }
+            ProjectileEvent::Exploded {
+                id,
+                position,
+                radius,
+            } => {
+                let mut color = EXPLOSION_COLOR;
+                if let Some((entity, weapon)) = displayed.remove(id) {
+                    commands.entity(entity).despawn_recursive();
+                    if weapons.get(weapon).projectile == ProjectileKind::EnergyBall {
+                        color = ENERGY_BALL_COLOR;
+                    }
+                }
+                commands.spawn((
+                    PbrBundle {
+                        mesh: meshes.add(Sphere::new(1.0)),
+                        material: materials.add(StandardMaterial {
+                            base_color: color.with_alpha(0.8),
+                            emissive: LinearRgba::from(color) * 4.0,
+                            alpha_mode: AlphaMode::Blend,
+                            unlit: true,
+                            ..default()
+                        }),
+                        transform: Transform::from_translation(*position)
+                            .with_scale(Vec3::splat(0.1)),
+                        ..default()
+                    },
+                    ExplosionVisual {
+                        radius: *radius,
+                        time_alive: 0.0,
+                    },
+                ));
+            }
+            ProjectileEvent::Cleared => {
+                for (_, (entity, _)) in displayed.drain() {
+                    commands.entity(entity).despawn_recursive();
+                }
+            }
+        }
+    }
+}
+
+/// Fait avancer les projectiles en vol
+fn move_projectiles(time: Res<Time>, mut query: Query<(&ProjectileVisual, &mut Transform)>) {
+    for (projectile, mut transform) in query.iter_mut() {
+        transform.translation += projectile.velocity * time.delta_seconds();
+    }
+}
+
+/// Fait grandir puis disparaître les explosions
+fn update_explosions(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut ExplosionVisual, &mut Transform)>,
+) {
+    for (entity, mut explosion, mut transform) in query.iter_mut() {
+        explosion.time_alive += time.delta_seconds();
+        let progress = explosion.time_alive / EXPLOSION_DURATION;
+        if progress >= 1.0 {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        transform.scale = Vec3::splat(explosion.radius * progress.max(0.05));
+    }
+}
@@ -0,0 +1,80 @@
+use bevy::prelude::*;
+use multiplayer_demo::collision::HitZone;
+
+use crate::events::HitConfirmEvent;
+
+/// Durée d'affichage du retour d'impact (secondes)
+const HIT_DURATION: f64 = 0.2;
+/// Durée d'affichage de l'annonce d'un tir à la tête (secondes)
+const HEADSHOT_DURATION: f64 = 1.0;
+/// Couleur du retour d'impact
+const HIT_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.9);
+/// Couleur de l'annonce d'un tir à la tête
+const HEADSHOT_COLOR: Color = Color::srgba(0.737, 0.024, 0.012, 0.9);
+
+/// Retour d'impact affiché sous le viseur quand le serveur confirme un tir réussi
+#[derive(Component, Default)]
+pub struct HitMarker {
+    pub hit_until: f64,      // Fin de l'affichage du retour d'impact (temps local)
+    pub headshot_until: f64, // Fin de l'annonce du tir à la tête (temps local)
+}
+
+/// Crée le texte du retour d'impact, vide tant qu'aucun tir n'est confirmé
+pub fn spawn_hit_marker(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.),
+                top: Val::Percent(53.),
+                justify_content: JustifyContent::Center, // Centrage horizontal sous le viseur
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font: asset_server.load("fonts/AmazDooMLeft.ttf"),
+                        font_size: 40.0,
+                        color: HIT_COLOR,
+                    },
+                ),
+                HitMarker::default(),
+            ));
+        });
+}
+
+/// Affiche les impacts confirmés par le serveur ; un tir à la tête est annoncé plus longtemps
+/// et n'est pas masqué par les impacts suivants
+pub fn update_hit_marker(
+    time: Res<Time>,
+    mut hit_events: EventReader<HitConfirmEvent>,
+    mut query: Query<(&mut HitMarker, &mut Text)>,
+) {
+    let Ok((mut marker, mut text)) = query.get_single_mut() else {
+        return;
+    };
+
+    let now = time.elapsed_seconds_f64();
+    for event in hit_events.read() {
+        marker.hit_until = now + HIT_DURATION;
+        if event.zone == Some(HitZone::Head) {
+            marker.headshot_until = now + HEADSHOT_DURATION;
+        }
+    }
+
+    let (content, color) = if now < marker.headshot_until {
+        ("Tir à la tête !", HEADSHOT_COLOR)
+    } else if now < marker.hit_until {
+        ("x", HIT_COLOR)
+    } else {
+        ("", HIT_COLOR)
+    };
+    if text.sections[0].value != content {
+        text.sections[0].value = content.to_string();
+        text.sections[0].style.color = color;
+    }
+}
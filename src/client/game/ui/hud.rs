@@ -3,7 +3,7 @@ use bevy::{
     prelude::*,
     window::PrimaryWindow,
 };
-use multiplayer_demo::{PlayerAttributes, PlayerLobby, Weapon};
+use multiplayer_demo::{weapons::WeaponDefinitions, PlayerAttributes, PlayerLobby, Weapon};
 use rand::Rng;
 
 use crate::resources::MyClientId;
@@ -521,12 +521,13 @@ pub fn update_player_ammo_ui(
 ) {
     // Récupère le joueur (on suppose qu'il n'y a qu'un seul joueur)
     if let Some(player) = player_query.iter().next() {
-        // Accède aux munitions de l'arme actuelle via la HashMap
-        if let Some(ammo) = player.ammo.get(&player.actual_weapon) {
-            // Mise à jour du texte UI avec les munitions actuelles
-            for mut text in text_query.iter_mut() {
-                text.sections[0].value = format!("{:.0}", ammo);
-            }
+        // Accède au chargeur et à la réserve de l'arme actuelle via les HashMap
+        let weapon = &player.actual_weapon;
+        let magazine = player.magazine.get(weapon).copied().unwrap_or(0.);
+        let reserve = player.ammo.get(weapon).copied().unwrap_or(0.);
+        // Mise à jour du texte UI avec les munitions actuelles (chargeur / réserve)
+        for mut text in text_query.iter_mut() {
+            text.sections[0].value = format!("{:.0} / {:.0}", magazine, reserve);
         }
     }
 }
@@ -565,6 +566,7 @@ pub fn update_player_weapon_ui(
         With<BorderColorEdit>,
     >,
     asset_server: Res<AssetServer>,
+    weapons: Res<WeaponDefinitions>,
 ) {
     if let Some(player) = player_query.iter().next() {
         let actual_weapon = player.actual_weapon;
@@ -573,7 +575,7 @@ pub fn update_player_weapon_ui(
         update_weapon_border_color(actual_weapon, &mut iu_weapon_query);
 
         // Mise à jour de la couleur de fond
-        update_weapon_color_image(player, &mut iu_weapon_query, asset_server);
+        update_weapon_color_image(player, &mut iu_weapon_query, asset_server, &weapons);
 
         // Mise à jour de la couleur de fond
         update_weapon_background_color(player, &mut iu_weapon_query);
@@ -617,13 +619,14 @@ fn update_weapon_color_image(
         With<BorderColorEdit>,
     >,
     asset_server: Res<AssetServer>,
+    weapons: &WeaponDefinitions,
 ) {
     for (weapon_ui, _, _, mut image) in iu_weapon_query.iter_mut() {
         // Mise à jour de la couleur de fond
         if *player.owned_weapon.get(&weapon_ui.weapon).unwrap_or(&false) {
-            // Si l'arme est possédée, pas de fond
-            let weapon_name = format!("{}", weapon_ui.weapon);
-            *image = UiImage::new(asset_server.load(format!("hud_weapon/{}.png", weapon_name)));
+            // Si l'arme est possédée, icône lue dans les définitions des armes
+            let icon = &weapons.get(weapon_ui.weapon).icon;
+            *image = UiImage::new(asset_server.load(icon.clone()));
         } else {
             // Si l'arme n'est pas possédée, fond noir
             *image = UiImage::solid_color(Color::srgb(0., 0., 0.));
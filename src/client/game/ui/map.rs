@@ -1,16 +1,24 @@
 use bevy::prelude::*;
 use bevy::ui::FocusPolicy;
-use multiplayer_demo::PlayerLobby;
+use multiplayer_demo::{
+    map::{MapDefinition, FLOOR_CELL, PICKUP_CELL, SPAWN_CELL},
+    PlayerLobby,
+};
 use renet::ClientId;
 
 use crate::resources::MyClientId;
 
-/// Ressource représentant la grille de la map 2D
+/// Ressource représentant la carte jouée (niveau 3D et mini-carte)
 #[derive(Resource)]
 pub struct MazeMap {
-    pub grid: Vec<Vec<i32>>,
-    pub cell_size: f32,
-    pub maze_size: usize,
+    pub map: MapDefinition, // Carte chargée depuis son fichier
+}
+
+impl MazeMap {
+    /// Nombre de cellules du plus grand côté de la carte (la mini-carte est carrée)
+    pub fn maze_size(&self) -> usize {
+        self.map.width().max(self.map.height())
+    }
 }
 
 /// Marqueur du container de la mini-carte UI
@@ -61,25 +69,26 @@ pub fn setup_maze_grid(
         return;
     };
 
-    let cell_pixel_size = 300.0 / maze_map.maze_size as f32;
+    let cell_pixel_size = 300.0 / maze_map.maze_size() as f32;
+    let height = maze_map.map.height() as f32;
 
-    for (y, row) in maze_map.grid.iter().enumerate() {
+    for (y, row) in maze_map.map.grid.iter().enumerate() {
         for (x, &cell) in row.iter().enumerate() {
-            if cell != 0 {
+            if cell != FLOOR_CELL {
                 let mut color = BackgroundColor(Color::srgba(1., 1., 1., 0.1));
 
                 // Voir les points de spawn de joueur
-                if cell == 2 {
+                if cell == SPAWN_CELL {
                     color = BackgroundColor(Color::srgba(0., 1., 0., 0.));
                 }
 
                 // Voir les points de spawn d'item
-                if cell == 3 {
+                if cell == PICKUP_CELL {
                     color = BackgroundColor(Color::srgba(1., 0., 1., 0.));
                 }
 
                 let px = x as f32 * cell_pixel_size;
-                let py = (maze_map.maze_size as f32 - y as f32 - 1.0) * cell_pixel_size;
+                let py = (height - y as f32 - 1.0) * cell_pixel_size;
 
                 commands.entity(minimap_entity).with_children(|parent| {
                     parent.spawn((
@@ -118,8 +127,11 @@ pub fn update_player_dots(
         return;
     };
 
-    let maze_center = (maze_map.maze_size as f32 / 2.0) * maze_map.cell_size;
-    let scale = 300.0 / maze_map.maze_size as f32;
+    let cell_size = maze_map.map.cell_size;
+    let center_x = (maze_map.map.width() as f32 / 2.0) * cell_size;
+    let center_z = (maze_map.map.height() as f32 / 2.0) * cell_size;
+    let height = maze_map.map.height() as f32;
+    let scale = 300.0 / maze_map.maze_size() as f32;
 
     let mut used_ids = std::collections::HashSet::new();
 
@@ -128,9 +140,8 @@ pub fn update_player_dots(
 
         let (px, _py, pz) = player_attr.position;
 
-        let x = ((px + maze_center) / maze_map.cell_size) * scale + 4.0;
-        let y =
-            (maze_map.maze_size as f32 - ((pz + maze_center) / maze_map.cell_size)) * scale - 6.0;
+        let x = ((px + center_x) / cell_size) * scale + 4.0;
+        let y = (height - ((pz + center_z) / cell_size)) * scale - 6.0;
 
         // Existe déjà ?
         if let Some((_, mut style, _)) = dots_query
@@ -1,4 +1,6 @@
 pub mod crosshair;
+pub mod hit_marker;
 pub mod ui;
 pub mod hud;
-pub mod map;
\ No newline at end of file
+pub mod map;
+pub mod vote;
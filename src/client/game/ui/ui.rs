@@ -1,55 +1,24 @@
 use bevy::{diagnostic::FrameTimeDiagnosticsPlugin, prelude::*};
 
 use super::{
-    crosshair, hud,
+    crosshair, hit_marker, hud,
     map::{setup_maze_grid, setup_minimap, update_player_dots, MazeMap},
+    vote::{map_vote_input, update_map_vote_ui},
 };
 
 pub struct UiPlugin;
 
 impl Plugin for UiPlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(MazeMap {
-            grid: vec![
-                vec![1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1],
-                vec![1,2,0,0,0,2,1,2,0,0,0,2,1,2,0,0,0,0,0,3,0,0,0,0,0,0,2,1,0,2,1],
-                vec![1,0,0,0,0,0,1,0,0,0,0,0,1,0,0,0,0,0,0,0,0,0,0,0,0,0,0,1,0,0,1],
-                vec![1,0,0,1,0,0,1,0,0,1,0,0,1,0,0,1,1,1,1,1,1,1,1,1,1,0,0,1,0,0,1],
-                vec![1,3,0,1,0,0,0,0,0,0,0,0,1,0,0,0,0,0,1,2,0,0,0,2,1,0,0,0,0,0,1],
-                vec![1,0,0,1,0,0,0,0,0,0,0,0,1,2,0,0,0,0,1,0,0,0,0,0,1,0,0,0,0,0,1],
-                vec![1,0,0,1,1,1,1,0,0,3,0,0,1,1,1,1,0,0,1,0,0,1,0,0,1,0,0,1,0,0,1],
-                vec![1,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,1,0,0,1,0,0,0,0,0,1],
-                vec![1,2,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,1,0,0,1,3,0,0,0,0,1],
-                vec![1,1,1,1,1,1,1,0,0,1,0,0,1,1,1,1,1,1,1,0,0,1,0,0,1,1,1,1,0,0,1],
-                vec![1,2,0,0,0,0,1,0,0,0,0,0,1,2,0,0,0,0,0,0,0,1,0,0,0,0,0,0,0,0,1],
-                vec![1,0,0,0,0,0,1,2,0,0,0,0,1,0,0,0,0,0,0,0,0,1,2,0,0,0,0,0,0,0,1],
-                vec![1,0,0,1,0,0,1,1,1,1,0,0,1,0,0,1,0,0,3,0,0,1,1,1,1,1,1,1,0,0,1],
-                vec![1,0,0,1,0,0,0,0,0,1,0,0,1,0,0,1,0,0,0,0,0,0,0,0,0,0,0,0,0,0,1],
-                vec![1,0,0,1,2,0,0,0,0,1,0,0,1,0,0,1,0,0,0,0,0,0,0,0,0,0,0,0,0,2,1],
-                vec![1,0,0,1,1,1,1,0,0,1,0,0,1,0,0,1,0,0,1,0,0,1,0,0,1,0,0,1,1,1,1],
-                vec![1,0,0,1,0,0,0,0,0,0,0,0,0,0,0,1,0,0,1,0,0,1,0,0,1,0,0,1,0,2,1],
-                vec![1,3,0,1,3,0,0,0,0,0,0,0,0,0,0,1,0,0,1,0,0,1,3,0,1,0,0,1,0,0,1],
-                vec![1,0,0,1,1,1,1,0,0,3,0,0,1,1,1,1,0,0,1,0,0,1,1,1,1,0,0,1,0,0,1],
-                vec![1,0,0,1,0,0,0,0,0,0,0,0,0,0,0,0,0,0,1,0,0,0,0,0,1,0,0,0,0,0,1],
-                vec![1,0,0,1,0,0,0,0,0,0,0,0,0,0,0,0,0,2,1,2,0,0,0,0,1,3,0,0,0,0,1],
-                vec![1,0,0,1,0,0,1,1,1,1,1,1,1,0,0,1,1,1,1,1,1,1,0,0,1,0,0,1,1,1,1],
-                vec![1,0,0,0,0,0,1,2,0,1,0,0,0,0,0,1,2,0,0,0,0,0,0,0,1,0,0,1,2,0,1],
-                vec![1,2,0,0,0,0,1,0,0,1,0,0,0,0,3,1,0,0,0,0,0,0,0,3,1,0,0,1,0,0,1],
-                vec![1,1,1,1,1,1,1,0,0,1,0,0,1,1,1,1,0,0,1,1,1,1,1,1,1,0,0,1,0,0,1],
-                vec![1,3,0,0,0,0,0,0,0,1,0,0,1,2,0,0,0,0,1,2,0,0,0,0,0,0,0,1,0,0,1],
-                vec![1,0,0,0,0,0,0,0,0,1,0,0,1,0,0,0,0,3,1,0,0,0,0,0,0,0,2,1,0,0,1],
-                vec![1,0,0,1,1,1,1,1,1,1,0,0,1,0,0,1,1,1,1,0,0,1,1,1,1,1,1,1,0,0,1],
-                vec![1,0,0,0,0,0,0,0,0,0,0,0,1,0,0,0,0,2,1,0,0,0,0,0,0,0,0,0,0,0,1],
-                vec![1,2,0,0,0,0,0,0,0,0,0,3,1,2,0,0,0,0,1,2,0,0,0,0,3,0,0,0,0,2,1],
-                vec![1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1,1],
-            ],
-
-            cell_size: 4.0,
-            maze_size: 31,
-        })
-        .add_systems(Startup, setup_minimap)
-        .add_systems(Update, setup_maze_grid)
-        .add_systems(Update, update_player_dots);
+        // La carte (`MazeMap`) n'existe qu'une fois reçue du serveur ou retrouvée dans le cache
+        app.add_systems(Startup, setup_minimap)
+            .add_systems(Update, setup_maze_grid.run_if(resource_exists::<MazeMap>))
+            .add_systems(Update, update_player_dots.run_if(resource_exists::<MazeMap>))
+            // Vote de la carte suivante en fin de partie
+            .add_systems(Update, (map_vote_input, update_map_vote_ui))
+            // Retour d'impact et annonce des tirs à la tête
+            .add_systems(Startup, hit_marker::spawn_hit_marker)
+            .add_systems(Update, hit_marker::update_hit_marker);
         app.add_plugins(FrameTimeDiagnosticsPlugin)
             .add_systems(
                 Startup,
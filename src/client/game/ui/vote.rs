@@ -0,0 +1,103 @@
+use bevy::prelude::*;
+use multiplayer_demo::ClientMessage;
+use renet::RenetClient;
+
+/// Touches de vote, une par carte candidate
+const VOTE_KEYS: [KeyCode; 9] = [
+    KeyCode::F1,
+    KeyCode::F2,
+    KeyCode::F3,
+    KeyCode::F4,
+    KeyCode::F5,
+    KeyCode::F6,
+    KeyCode::F7,
+    KeyCode::F8,
+    KeyCode::F9,
+];
+
+/// Ressource représentant le vote de la carte suivante en cours
+/// Présente seulement entre la fin d'une partie et le changement de carte
+#[derive(Resource)]
+pub struct MapVote {
+    pub candidates: Vec<String>, // Noms des cartes proposées
+    pub ends_at: f64,            // Fin du vote (temps local, en secondes)
+    pub choice: Option<usize>,   // Carte choisie par le joueur local
+}
+
+/// Marqueur du texte affichant le vote
+#[derive(Component)]
+pub struct MapVoteText;
+
+/// Affiche le vote en cours (cartes, touches et temps restant) et le retire une fois terminé
+pub fn update_map_vote_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    time: Res<Time>,
+    vote: Option<Res<MapVote>>,
+    mut text_query: Query<(Entity, &mut Text), With<MapVoteText>>,
+) {
+    let Some(vote) = vote else {
+        for (entity, _) in text_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    };
+
+    let remaining = (vote.ends_at - time.elapsed_seconds_f64()).max(0.0).ceil() as u32;
+    let mut content = format!("Vote pour la carte suivante ({remaining} s)\n");
+    for (index, candidate) in vote.candidates.iter().enumerate() {
+        let marker = if vote.choice == Some(index) { ">" } else { " " };
+        content.push_str(&format!("\n{marker} F{} : {candidate}", index + 1));
+    }
+
+    if let Ok((_, mut text)) = text_query.get_single_mut() {
+        text.sections[0].value = content;
+        return;
+    }
+    commands.spawn((
+        TextBundle {
+            text: Text::from_section(
+                content,
+                TextStyle {
+                    font: asset_server.load("fonts/AmazDooMLeft.ttf"),
+                    font_size: 40.0,
+                    color: Color::Srgba(Srgba::new(0.737, 0.024, 0.012, 0.9)),
+                },
+            ),
+            style: Style {
+                position_type: PositionType::Absolute,
+                left: Val::Px(10.),
+                top: Val::Percent(35.),
+                ..default()
+            },
+            background_color: BackgroundColor(Color::srgba(0., 0., 0., 0.6)),
+            ..default()
+        },
+        MapVoteText,
+    ));
+}
+
+/// Envoie au serveur la carte choisie avec les touches F1 à F9 pendant le vote
+pub fn map_vote_input(
+    keys: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+    vote: Option<ResMut<MapVote>>,
+    mut client: ResMut<RenetClient>,
+) {
+    let Some(mut vote) = vote else {
+        return;
+    };
+    if time.elapsed_seconds_f64() >= vote.ends_at {
+        return;
+    }
+
+    let pressed = VOTE_KEYS
+        .iter()
+        .take(vote.candidates.len())
+        .position(|key| keys.just_pressed(*key));
+    if let Some(choice) = pressed {
+        vote.choice = Some(choice);
+        let message = ClientMessage::VoteMap(choice as u8);
+        client.send_message(message.channel(), bincode::serialize(&message).unwrap());
+    }
+}
@@ -1,9 +1,9 @@
 use bevy::{
     prelude::*,
-    window::{PrimaryWindow, WindowMode, WindowResolution},
+    window::{PrimaryWindow, WindowResolution},
 };
 
-use crate::game::cursor::*;
+use crate::{game::cursor::*, resources::DisplaySettings};
 pub struct WindowSettingsPlugin;
 impl Plugin for WindowSettingsPlugin {
     fn build(&self, app: &mut App) {
@@ -14,12 +14,13 @@ impl Plugin for WindowSettingsPlugin {
 
 fn init_window(
     mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+    display: Res<DisplaySettings>,
 ) {
     if let Ok(mut window) = window_query.get_single_mut() {
         let width = window.width();
         let height = window.height();
         window.resolution = WindowResolution::new(height, width);
-        window.mode = WindowMode::BorderlessFullscreen;
+        window.mode = display.window_mode;
         println!("WINDOW WIDTH: {}", window.width());
     }
 }
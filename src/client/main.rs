@@ -3,7 +3,8 @@
 // Import des modules standard pour l'entrée/sortie et le réseau
 use std::{
     io::{self, Write},
-    net::{SocketAddrV4, UdpSocket},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, UdpSocket},
+    process::ExitCode,
     time::SystemTime,
 };
 
@@ -17,75 +18,178 @@ use bevy::{
 // Import des plugins renet pour la gestion réseau côté client
 use bevy_renet::{transport::NetcodeClientPlugin, RenetClientPlugin};
 // Import du plugin de jeu principal
-use game::game::GamePlugin;
+use game::{game::GamePlugin, player::player_movement::update_movement};
 // Import des structures de données partagées
-use multiplayer_demo::PlayerLobby;
+use multiplayer_demo::{
+    collision::CollisionWorld,
+    config::{ConfigError, ConfigFile},
+    protocol::{validate_username, ConnectData, PROTOCOL_ID},
+    weapons::{WeaponDefinitions, DEFAULT_WEAPONS_PATH},
+    PlayerLobby,
+};
 // Import des modules renet pour la configuration réseau
 use renet::{
     transport::{ClientAuthentication, NetcodeClientTransport},
     ClientId, ConnectionConfig, RenetClient,
 };
+// Import de la configuration du client
+use config::{resolve_server_addr, ClientSettings, USAGE};
 // Import des ressources locales
 use resources::SpawnedPlayers;
 // Import des systèmes et plugins locaux
 use systems::{update_lobby_system, SyncStatePlugin};
+// Import de la récupération des jetons de connexion (mode sécurisé)
+use token::fetch_connect_token;
 
 // Import des modules internes du client
 use crate::{
-    resources::{IsSynced, MyClientId, MyUsername},
+    resources::{
+        DisplaySettings, InterpolationSettings, IsSynced, MyClientId, MyUsername, PendingMap,
+        ReadySent, ReceivedSnapshots, ServerClock,
+    },
     systems::{
-        handle_lobby_sync_event_system, handle_player_despawn_event_system,
-        handle_player_spawn_event_system, receive_message_system, send_message_system,
+        handle_lobby_sync_event_system, handle_map_sync_event_system,
+        handle_pickup_sync_event_system, handle_player_despawn_event_system,
+        handle_player_spawn_event_system, interpolate_remote_players_system,
+        receive_message_system, send_leave_on_exit_system, send_message_system,
+        send_ready_system,
     },
 };
 
 // Déclarations des sous-modules du client
 mod components;  // Composants spécifiques au client
+mod config;      // Configuration (fichier et ligne de commande)
 mod events;      // Événements personnalisés
 pub mod game;    // Module principal du jeu (rendu, input, etc.)
+mod map_cache;   // Cache local des cartes reçues du serveur
 mod resources;   // Ressources locales du client
 mod systems;     // Systèmes de gestion réseau et logique
+mod token;       // Jetons de connexion du mode sécurisé
 
 /// Point d'entrée principal du client
 /// Initialise la connexion réseau et démarre l'application de jeu
-fn main() {
-    // --- Configuration de l'adresse IP du serveur ---
-    print!("Entrez l'IP du serveur (ex: 192.168.1.10): ");
-    io::stdout().flush().unwrap(); // Force l'affichage du prompt
-    let mut ipaddr = String::new();
-    io::stdin()
-        .read_line(&mut ipaddr)
-        .expect("Échec lecture IP");
-    let ipaddr = ipaddr.trim(); // Suppression des espaces et retours à la ligne
+fn main() -> ExitCode {
+    // --- Lecture de la configuration (fichier puis ligne de commande) ---
+    let settings = match ClientSettings::load(std::env::args().skip(1)) {
+        Ok(settings) => settings,
+        Err(ConfigError::Help) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(err @ ConfigError::Cli(_)) => {
+            eprintln!("❌ {err}\n\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+        Err(err) => {
+            eprintln!("❌ {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    // --- Adresse du serveur (IP ou nom DNS) ---
+    // Une adresse fournie en option ou dans le fichier n'est jamais redemandée
+    let server_addr = match &settings.server {
+        Some(server) => match resolve_server_addr(server) {
+            Ok(addr) => addr,
+            Err(err) => {
+                eprintln!("❌ Serveur invalide : {err}");
+                return ExitCode::FAILURE;
+            }
+        },
+        None if settings.no_prompt => {
+            eprintln!("❌ Aucun serveur indiqué (--server) et --no-prompt actif");
+            return ExitCode::FAILURE;
+        }
+        None => loop {
+            let question = "Entrez l'adresse du serveur (ex: 192.168.1.10 ou jeu.example.org:5000): ";
+            let Some(input) = prompt(question) else {
+                return ExitCode::FAILURE;
+            };
+            match resolve_server_addr(&input) {
+                Ok(addr) => break addr,
+                Err(err) => eprintln!("Serveur invalide : {err}"),
+            }
+        },
+    };
 
     // --- Configuration du nom d'utilisateur ---
-    let mut username = "".to_string();
-    while username.trim().is_empty() {
-        print!("Entrez votre nom d'utilisateur (ex: NoobMaster69): ");
-        io::stdout().flush().unwrap();
-        io::stdin()
-            .read_line(&mut username)
-            .expect("Échec lecture nom");
-        username = username.trim().to_string();
-    }
+    let username = match &settings.username {
+        // Déjà vérifié au chargement de la configuration
+        Some(username) => username.trim().to_string(),
+        None if settings.no_prompt => {
+            eprintln!("❌ Aucun nom d'utilisateur indiqué (--username) et --no-prompt actif");
+            return ExitCode::FAILURE;
+        }
+        None => loop {
+            let Some(input) = prompt("Entrez votre nom d'utilisateur (ex: NoobMaster69): ") else {
+                return ExitCode::FAILURE;
+            };
+            match validate_username(&input) {
+                Ok(username) => break username.to_string(),
+                Err(err) => eprintln!("Nom d'utilisateur invalide : {err}"),
+            }
+        },
+    };
+
+    // --- Définitions des armes locales, remplacées par celles du serveur à la connexion ---
+    let weapons = match WeaponDefinitions::load(std::path::Path::new(DEFAULT_WEAPONS_PATH)) {
+        Ok(weapons) => weapons,
+        Err(err) => {
+            eprintln!("❌ {err}");
+            return ExitCode::FAILURE;
+        }
+    };
 
     // --- Initialisation de l'application Bevy ---
     let mut app = App::new();
 
     // --- Configuration réseau du client ---
-    let client_id = rand::random::<u64>(); // Génération d'un ID client aléatoire
-    // Création et liaison du socket UDP (port 0 = port automatique)
-    let socket = UdpSocket::bind("0.0.0.0:0").expect("Échec du bind UDP client");
-
-    // Création de l'adresse du serveur avec le port 5000
-    let server_socket = SocketAddrV4::new(ipaddr.parse().expect("Adresse IP invalide"), 5000);
-
-    // Configuration de l'authentification client (non sécurisée pour le développement)
-    let authentication = ClientAuthentication::Unsecure {
-        server_addr: server_socket.into(), // Adresse du serveur
-        client_id,                         // ID unique du client
-        user_data: None,                   // Pas de données utilisateur supplémentaires
-        protocol_id: 0,                    // ID du protocole réseau
+    // Création et liaison du socket UDP de la même famille que le serveur (port 0 = port automatique)
+    let bind_ip = if server_addr.is_ipv4() {
+        IpAddr::V4(Ipv4Addr::UNSPECIFIED)
+    } else {
+        IpAddr::V6(Ipv6Addr::UNSPECIFIED)
+    };
+    let socket = match UdpSocket::bind(SocketAddr::new(bind_ip, 0)) {
+        Ok(socket) => socket,
+        Err(err) => {
+            eprintln!("❌ Échec du bind UDP client : {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    // La version du protocole et le nom d'utilisateur sont vérifiés par le serveur à la connexion
+    let connect_data = ConnectData::new(username.clone());
+
+    // Mode sécurisé optionnel : l'identifiant du client est attribué par l'émetteur de jetons
+    let (client_id, authentication) = if settings.secure {
+        let issuer_addr = SocketAddr::new(server_addr.ip(), settings.token_port);
+        let connect_token = match fetch_connect_token(issuer_addr, &connect_data) {
+            Ok(token) => token,
+            Err(err) => {
+                eprintln!("❌ Connexion sécurisée impossible : {err}");
+                return ExitCode::FAILURE;
+            }
+        };
+        (
+            connect_token.client_id,
+            ClientAuthentication::Secure { connect_token },
+        )
+    } else {
+        // Configuration de l'authentification client (non sécurisée pour le développement)
+        let client_id = rand::random::<u64>(); // Génération d'un ID client aléatoire
+        let user_data = connect_data
+            .to_user_data()
+            .expect("Nom d'utilisateur déjà validé");
+        (
+            client_id,
+            ClientAuthentication::Unsecure {
+                server_addr,                // Adresse du serveur
+                client_id,                  // ID unique du client
+                user_data: Some(user_data), // Version du protocole et nom d'utilisateur
+                protocol_id: PROTOCOL_ID,   // ID du protocole réseau
+            },
+        )
     };
 
     // Récupération du timestamp actuel pour la synchronisation
@@ -94,8 +198,13 @@ fn main() {
         .unwrap();
 
     // Création du transport réseau client
-    let transport = NetcodeClientTransport::new(current_time, authentication, socket)
-        .expect("Échec init transport client!");
+    let transport = match NetcodeClientTransport::new(current_time, authentication, socket) {
+        Ok(transport) => transport,
+        Err(err) => {
+            eprintln!("❌ Échec de l'initialisation du transport client : {err}");
+            return ExitCode::FAILURE;
+        }
+    };
 
     // --- Ajout des plugins réseau à l'application ---
     app.add_plugins(RenetClientPlugin);      // Plugin client renet
@@ -107,7 +216,20 @@ fn main() {
     app.insert_resource(PlayerLobby::default());                        // Lobby des joueurs
     app.insert_resource(MyClientId(ClientId::from_raw(client_id)));     // ID du client local
     app.insert_resource(MyUsername::new(username.clone()));             // Nom d'utilisateur
+    app.insert_resource(PendingMap::default());                         // Carte attendue du serveur
+    app.insert_resource(CollisionWorld::default());                     // Collisions vides jusqu'à la réception de la carte
+    app.insert_resource(weapons);                                       // Définitions des armes
+    app.insert_resource(DisplaySettings {
+        window_mode: settings.window_mode.window_mode(),                // Mode de la fenêtre choisi
+    });
     app.insert_resource(SpawnedPlayers::default());                     // Joueurs spawnés localement
+    app.insert_resource(ServerClock::default());                        // Horloge serveur estimée
+    app.insert_resource(InterpolationSettings {
+        delay: settings.interpolation_delay(),                          // Délai d'interpolation des joueurs distants
+        max_extrapolation: settings.max_extrapolation(),                // Extrapolation maximale
+    });
+    app.insert_resource(ReceivedSnapshots::default());                  // Instantanés reçus (références des différentiels)
+    app.insert_resource(ReadySent(false));                              // Message Ready pas encore envoyé
     app.insert_resource(IsSynced(false)); // Flag de synchronisation (bloque les messages tant que non synchronisé)
 
     // --- Ajout des plugins de jeu et de rendu ---
@@ -125,22 +247,67 @@ fn main() {
     app.add_event::<events::PlayerSpawnEvent>();    // Événement de spawn d'un joueur
     app.add_event::<events::PlayerDespawnEvent>();  // Événement de despawn d'un joueur
     app.add_event::<events::LobbySyncEvent>();      // Événement de synchronisation du lobby
+    app.add_event::<events::MovementAckEvent>();    // Événement d'acquittement du déplacement
+    app.add_event::<events::MapSyncEvent>();        // Événement de synchronisation de la carte
+    app.add_event::<events::PickupSyncEvent>();     // Événement des objets à ramasser
+    app.add_event::<events::ProjectileEvent>();     // Événement des projectiles simulés
+    app.add_event::<events::HitConfirmEvent>();     // Événement des impacts confirmés du joueur local
 
     // --- Ajout des systèmes clients principaux ---
     // Ces systèmes gèrent la logique réseau et la synchronisation
     app.add_systems(Update, receive_message_system);                    // Réception des messages serveur
+    app.add_systems(Update, handle_map_sync_event_system);              // Chargement de la carte annoncée par le serveur
+    app.add_systems(Update, handle_pickup_sync_event_system);           // Affichage des objets à ramasser
+    app.add_systems(Update, send_ready_system);                         // Entrée dans la partie une fois connecté
+    app.add_systems(Last, send_leave_on_exit_system);                   // Départ annoncé à la fermeture du jeu
     app.add_systems(Update, handle_player_spawn_event_system);          // Gestion des spawns de joueurs
     app.add_systems(Update, handle_player_despawn_event_system);        // Gestion des despawns de joueurs
     app.add_systems(Update, handle_lobby_sync_event_system);            // Gestion de la synchronisation du lobby
     app.add_systems(Update, update_lobby_system);                       // Mise à jour du lobby local
     app.add_systems(
         Update,
-        send_message_system.run_if(|synced: Res<IsSynced>| synced.0), // Envoi des messages (seulement si synchronisé)
+        interpolate_remote_players_system.after(handle_lobby_sync_event_system), // Affichage interpolé des joueurs distants
+    );
+    app.add_systems(
+        FixedUpdate,
+        send_message_system
+            .after(update_movement) // Envoi des entrées juste après leur prédiction
+            .run_if(|synced: Res<IsSynced>| synced.0), // Envoi des messages (seulement si synchronisé)
     );
 
     // --- Affichage du message de confirmation ---
-    info!("Client {} started with username '{}'", client_id, username);
+    info!(
+        "Client {} started with username '{}', server {}",
+        client_id, username, server_addr
+    );
 
     // --- Démarrage de la boucle principale de Bevy ---
-    app.run();
+    match app.run() {
+        AppExit::Success => ExitCode::SUCCESS,
+        AppExit::Error(code) => ExitCode::from(code.get()),
+    }
+}
+
+/// Pose une question sur le terminal et lit la réponse
+///
+/// # Arguments
+/// * `question` - Texte affiché avant la saisie
+///
+/// # Returns
+/// * `Option<String>` - Réponse saisie, ou None si l'entrée standard est fermée ou illisible
+fn prompt(question: &str) -> Option<String> {
+    print!("{question}");
+    io::stdout().flush().ok()?; // Force l'affichage de la question
+    let mut input = String::new();
+    match io::stdin().read_line(&mut input) {
+        Ok(0) => {
+            eprintln!("\n❌ Entrée standard fermée : utilisez --server et --username");
+            None
+        }
+        Ok(_) => Some(input.trim().to_string()),
+        Err(err) => {
+            eprintln!("❌ Lecture impossible : {err}");
+            None
+        }
+    }
 }
\ No newline at end of file
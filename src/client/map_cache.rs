@@ -0,0 +1,52 @@
+// src/client/map_cache.rs
+
+// Cache local des cartes reçues du serveur
+// Chaque carte est enregistrée sous son empreinte : un client qui retrouve l'empreinte
+// annoncée par le serveur n'a pas besoin de la télécharger à nouveau
+
+// Import des modules standard pour les fichiers
+use std::path::PathBuf;
+
+// Import du format de carte partagé
+use multiplayer_demo::map::{MapDefinition, MAPS_DIR};
+
+/// Sous-répertoire de `maps/` contenant les cartes reçues
+const CACHE_DIR: &str = "cache";
+
+/// Chemin du fichier de cache d'une carte
+///
+/// # Arguments
+/// * `hash` - Empreinte du contenu de la carte
+fn cache_path(hash: u64) -> PathBuf {
+    PathBuf::from(MAPS_DIR)
+        .join(CACHE_DIR)
+        .join(format!("{hash:016x}.bin"))
+}
+
+/// Cherche une carte dans le cache
+/// Une entrée illisible ou dont le contenu ne correspond plus à l'empreinte est ignorée
+///
+/// # Arguments
+/// * `hash` - Empreinte annoncée par le serveur
+///
+/// # Returns
+/// * `Option<MapDefinition>` - La carte si elle est en cache et intacte
+pub fn load(hash: u64) -> Option<MapDefinition> {
+    let bytes = std::fs::read(cache_path(hash)).ok()?;
+    let map: MapDefinition = bincode::deserialize(&bytes).ok()?;
+    (map.content_hash() == hash).then_some(map)
+}
+
+/// Enregistre une carte reçue dans le cache
+///
+/// # Arguments
+/// * `map` - Carte reçue du serveur
+/// * `hash` - Empreinte de son contenu
+pub fn store(map: &MapDefinition, hash: u64) -> std::io::Result<()> {
+    let path = cache_path(hash);
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let bytes = bincode::serialize(map).map_err(std::io::Error::other)?;
+    std::fs::write(path, bytes)
+}
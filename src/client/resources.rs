@@ -1,10 +1,18 @@
 // src/client/resources.rs
 
-// Import des modules standard pour la gestion des collections
-use std::collections::HashSet;
+// Import des modules standard pour la gestion des collections et du temps
+use std::{
+    collections::{HashSet, VecDeque},
+    time::Duration,
+};
 
 // Import des modules Bevy pour les ressources et les assets
-use bevy::{asset::Handle, ecs::system::Resource, render::texture::Image};
+use bevy::{asset::Handle, ecs::system::Resource, render::texture::Image, window::WindowMode};
+// Import du pas de simulation et des instantanés partagés
+use multiplayer_demo::{
+    movement::MOVEMENT_TIMESTEP,
+    snapshot::{Snapshot, SNAPSHOT_HISTORY},
+};
 // Import du type ClientId de renet
 use renet::ClientId;
 
@@ -32,12 +40,144 @@ impl MyUsername {
     }
 }
 
+/// Ressource contenant le mode d'affichage choisi pour la fenêtre du jeu
+/// Appliqué à la fenêtre principale au démarrage
+#[derive(Resource)]
+pub struct DisplaySettings {
+    pub window_mode: WindowMode, // Fenêtré, plein écran sans bordure ou plein écran exclusif
+}
+
+impl Default for DisplaySettings {
+    fn default() -> Self {
+        Self {
+            window_mode: WindowMode::BorderlessFullscreen,
+        }
+    }
+}
+
 /// Ressource indiquant si le client est synchronisé avec le serveur
 /// Utilisée pour contrôler l'envoi des messages (bloqué tant que non synchronisé)
 /// Évite l'envoi de messages avant que la connexion soit établie
 #[derive(Resource, Default)]
 pub struct IsSynced(pub bool);
 
+/// Ressource indiquant si le message Ready a déjà été envoyé au serveur
+#[derive(Resource, Default)]
+pub struct ReadySent(pub bool);
+
+/// Ressource contenant l'empreinte de la carte demandée au serveur
+/// Une carte reçue n'est acceptée que si son contenu correspond à cette empreinte
+#[derive(Resource, Default)]
+pub struct PendingMap {
+    pub hash: Option<u64>, // Empreinte attendue, None si aucune carte n'est en attente
+}
+
+/// Écart (en ticks) au-delà duquel l'horloge serveur est recalée sans lissage
+const CLOCK_SNAP_TICKS: f64 = 30.0;
+/// Facteur de lissage appliqué quand les paquets arrivent plus tard que prévu
+const CLOCK_SMOOTHING: f64 = 0.05;
+
+/// Ressource estimant le tick serveur courant à partir des ticks reçus
+/// Sert à choisir l'instant affiché pour les joueurs distants et à horodater les tirs
+#[derive(Resource)]
+pub struct ServerClock {
+    pub latest_tick: u32, // Dernier tick serveur reçu
+    pub tick_rate: f64,   // Nombre de ticks serveur par seconde
+    offset: Option<f64>,  // Écart lissé entre le tick serveur et l'horloge locale (en ticks)
+}
+
+impl Default for ServerClock {
+    fn default() -> Self {
+        Self {
+            latest_tick: 0,
+            tick_rate: 1.0 / MOVEMENT_TIMESTEP as f64,
+            offset: None,
+        }
+    }
+}
+
+impl ServerClock {
+    /// Change la cadence des ticks serveur annoncée par le serveur
+    /// L'écart mesuré avec l'ancienne cadence n'a plus de sens et est oublié
+    ///
+    /// # Arguments
+    /// * `tick_rate` - Nombre de ticks serveur par seconde
+    pub fn set_tick_rate(&mut self, tick_rate: u32) {
+        self.tick_rate = tick_rate as f64;
+        self.offset = None;
+    }
+
+    /// Prend en compte un tick reçu du serveur
+    /// Un paquet arrivé plus tôt que prévu recale immédiatement l'horloge, un paquet en retard
+    /// ne la fait reculer que progressivement pour absorber la gigue du réseau
+    ///
+    /// # Arguments
+    /// * `tick` - Tick serveur reçu
+    /// * `now` - Temps local écoulé en secondes
+    pub fn observe(&mut self, tick: u32, now: f64) {
+        self.latest_tick = self.latest_tick.max(tick);
+        let measured = tick as f64 - now * self.tick_rate;
+        self.offset = Some(match self.offset {
+            Some(offset) if (measured - offset).abs() < CLOCK_SNAP_TICKS => {
+                if measured > offset {
+                    measured
+                } else {
+                    offset + (measured - offset) * CLOCK_SMOOTHING
+                }
+            }
+            _ => measured,
+        });
+    }
+
+    /// Tick serveur à afficher, en retard du délai d'interpolation sur le tick estimé
+    ///
+    /// # Arguments
+    /// * `now` - Temps local écoulé en secondes
+    /// * `delay` - Délai d'interpolation
+    ///
+    /// # Returns
+    /// * `Option<f64>` - Tick de rendu, ou None tant qu'aucun tick n'a été reçu
+    pub fn render_tick(&self, now: f64, delay: Duration) -> Option<f64> {
+        self.offset
+            .map(|offset| now * self.tick_rate + offset - delay.as_secs_f64() * self.tick_rate)
+    }
+}
+
+/// Ressource contenant les derniers instantanés reçus du serveur
+/// Sert de référence pour décoder les instantanés différentiels suivants
+#[derive(Resource, Default)]
+pub struct ReceivedSnapshots(VecDeque<Snapshot>);
+
+impl ReceivedSnapshots {
+    /// Recherche un instantané reçu à partir de son tick
+    pub fn get(&self, tick: u32) -> Option<&Snapshot> {
+        self.0.iter().find(|snapshot| snapshot.tick == tick)
+    }
+
+    /// Tick du dernier instantané reçu, renvoyé au serveur comme acquittement
+    pub fn latest_tick(&self) -> Option<u32> {
+        self.0.back().map(|snapshot| snapshot.tick)
+    }
+
+    /// Mémorise un instantané décodé (plus récent que tous les précédents)
+    ///
+    /// # Arguments
+    /// * `snapshot` - Instantané complet reconstruit
+    pub fn push(&mut self, snapshot: Snapshot) {
+        self.0.push_back(snapshot);
+        while self.0.len() > SNAPSHOT_HISTORY {
+            self.0.pop_front();
+        }
+    }
+}
+
+/// Ressource de configuration de l'interpolation des joueurs distants (réglable dans `client.ron`)
+#[derive(Resource)]
+pub struct InterpolationSettings {
+    pub delay: Duration,             // Retard d'affichage des joueurs distants
+    pub max_extrapolation: Duration, // Durée maximale d'extrapolation quand les états sont en retard
+}
+
 /// Ressource pour la gestion de la skybox (carte cubique du ciel)
 /// Contient la texture de la skybox et un flag indiquant si elle est chargée
 #[derive(Resource)]
@@ -1,72 +1,143 @@
 // src/client/system.rs
 
+// Import des modules standard pour la gestion des collections
+use std::collections::{HashMap, HashSet};
+
 // Import des modules Bevy pour l'ECS, les événements et le rendu
 use bevy::{
-    app::{App, Plugin, Update},
-    asset::AssetServer,
+    app::{App, AppExit, Plugin, Update},
+    asset::{AssetServer, Assets},
     ecs::{
         entity::Entity,
         event::{EventReader, EventWriter},
+        query::With,
         system::{Commands, Query, Res, ResMut, Resource},
     },
-    log::info,
-    math::Vec3,
+    hierarchy::DespawnRecursiveExt,
+    log::{error, info, warn},
+    math::{Quat, Vec3},
+    pbr::StandardMaterial,
     prelude::default,
+    render::mesh::Mesh,
     scene::SceneBundle,
+    time::Time,
     transform::components::Transform,
 };
 // Import du module de physique pour les collisions
 use bevy_rapier3d::prelude::Collider;
 // Import des structures de données partagées
-use multiplayer_demo::{PlayerAttributes, PlayerEntity, PlayerLobby, PlayerStats, ServerMessage};
+use multiplayer_demo::{
+    collision::{hit_zones, CollisionWorld, HitZone},
+    movement::{MovementState, MAX_INPUTS_PER_COMMAND},
+    snapshot::Snapshot,
+    weapons::WeaponDefinitions,
+    ClientMessage, PlayerAttributes, PlayerEntity, PlayerInputCommand, PlayerLobby, PlayerStats,
+    ServerMessage,
+};
 // Import des modules renet pour la gestion réseau
-use renet::{ClientId, DefaultChannel, RenetClient};
+use renet::{transport::NetcodeClientTransport, ClientId, DefaultChannel, RenetClient};
 
 // Import des modules locaux
 use crate::{
-    events::{LobbySyncEvent, PlayerDespawnEvent, PlayerSpawnEvent},
-    game::player::player_shooting::Shootable,
-    resources::{IsSynced, MyUsername},
+    components::{PlayerStance, RemoteSample, SnapshotBuffer},
+    events::{
+        HitConfirmEvent, LobbySyncEvent, MapSyncEvent, MovementAckEvent, PickupSyncEvent,
+        PlayerDespawnEvent, PlayerSpawnEvent, ProjectileEvent,
+    },
+    game::{
+        level::{
+            level::LevelEntity,
+            pickups::{spawn_pickup_item, PickupItem},
+        },
+        player::{
+            player_movement::MovementPrediction,
+            player_shooting::{FiringState, Shootable},
+        },
+        ui::{
+            map::{MazeCell, MazeMap},
+            vote::MapVote,
+        },
+    },
+    map_cache,
+    resources::{
+        InterpolationSettings, IsSynced, PendingMap, ReadySent, ReceivedSnapshots, ServerClock,
+    },
     MyClientId,
 };
 
 /// Système d'envoi des messages au serveur
-/// Envoie les informations du joueur local (position, rotation, état) au serveur
+/// Envoie les dernières entrées du joueur local (non acquittées)
 ///
 /// # Arguments
 /// * `client` - Référence mutable au client renet
-/// * `query` - Requête pour récupérer les attributs et la transformation du joueur local
-/// * `username` - Référence au nom d'utilisateur local
+/// * `prediction` - Référence à la prédiction du déplacement local
+/// * `snapshots` - Référence aux instantanés reçus (acquittement du dernier)
 pub fn send_message_system(
     mut client: ResMut<RenetClient>,
-    query: Query<(&PlayerAttributes, &Transform)>,
-    username: Res<MyUsername>,
+    prediction: Res<MovementPrediction>,
+    snapshots: Res<ReceivedSnapshots>,
 ) {
-    // Récupération des données du joueur local
-    if let Ok((player, transform)) = query.get_single() {
-        // Construction du message de synchronisation
-        let player_sync = PlayerAttributes {
-            username: username.0.clone(), // Nom d'utilisateur local
-            rotation: player.rotation,    // Rotation du joueur
-            position: [
-                transform.translation.x,
-                transform.translation.y - 0.7, // Ajustement de la hauteur (centre du joueur)
-                transform.translation.z,
-            ]
-            .into(),
-            health: player.health,                     // Points de vie
-            armor: player.armor,                       // Points d'armure
-            velocity: player.velocity,                 // Vélocité
-            owned_weapon: player.owned_weapon.clone(), // Armes possédées
-            actual_weapon: player.actual_weapon,       // Arme actuellement équipée
-            ammo: player.ammo.clone(),                 // Munitions
-            entities: player.entities.clone(),         // Entités 3D des armes
-        };
-
-        // Sérialisation et envoi du message
-        let message = bincode::serialize(&player_sync).unwrap();
-        client.send_message(DefaultChannel::Unreliable, message);
+    // Les dernières entrées sont renvoyées à chaque tick pour compenser les pertes
+    let skip = prediction
+        .pending
+        .len()
+        .saturating_sub(MAX_INPUTS_PER_COMMAND);
+    let command = PlayerInputCommand {
+        inputs: prediction.pending.iter().skip(skip).copied().collect(),
+        snapshot_ack: snapshots.latest_tick(),
+    };
+    if command.inputs.is_empty() {
+        return;
     }
+
+    // Sérialisation et envoi du message sur son canal attitré
+    let message = ClientMessage::Input(command);
+    client.send_message(message.channel(), bincode::serialize(&message).unwrap());
+}
+
+/// Système d'entrée dans la partie
+/// Envoie une seule fois le message Ready dès que la connexion est établie
+/// et que la carte annoncée par le serveur est chargée
+///
+/// # Arguments
+/// * `client` - Référence mutable au client renet
+/// * `ready_sent` - Référence mutable au flag d'envoi du message Ready
+/// * `maze_map` - Carte jouée, absente tant qu'elle n'a pas été reçue
+pub fn send_ready_system(
+    mut client: ResMut<RenetClient>,
+    mut ready_sent: ResMut<ReadySent>,
+    maze_map: Option<Res<MazeMap>>,
+) {
+    if ready_sent.0 || !client.is_connected() || maze_map.is_none() {
+        return;
+    }
+    let message = ClientMessage::Ready;
+    client.send_message(message.channel(), bincode::serialize(&message).unwrap());
+    ready_sent.0 = true;
+}
+
+/// Système de départ volontaire à la fermeture du jeu
+/// Le message Leave et la déconnexion sont envoyés immédiatement, la boucle réseau
+/// ne tournant plus après la sortie de l'application
+///
+/// # Arguments
+/// * `exit_events` - Lecteur d'événements de sortie de l'application
+/// * `client` - Référence mutable au client renet
+/// * `transport` - Référence mutable au transport réseau
+pub fn send_leave_on_exit_system(
+    mut exit_events: EventReader<AppExit>,
+    mut client: ResMut<RenetClient>,
+    mut transport: ResMut<NetcodeClientTransport>,
+) {
+    if exit_events.read().next().is_none() || !client.is_connected() {
+        return;
+    }
+    let message = ClientMessage::Leave;
+    client.send_message(message.channel(), bincode::serialize(&message).unwrap());
+    if let Err(err) = transport.send_packets(&mut client) {
+        warn!("Échec de l'envoi du message Leave : {}", err);
+    }
+    transport.disconnect();
 }
 
 /// Système de réception des messages du serveur
@@ -80,6 +151,19 @@ pub fn send_message_system(
 /// * `sync_state` - Référence mutable à l'état de synchronisation
 /// * `my_id` - Référence à l'ID du client local
 /// * `player_query` - Requête pour modifier les attributs et la transformation du joueur local
+/// * `clock` - Référence mutable à l'horloge serveur estimée
+/// * `time` - Référence au temps local
+/// * `movement_ack_events` - Écrivain d'événements d'acquittement du déplacement
+/// * `prediction` - Référence mutable à la prédiction du déplacement local
+/// * `lobby` - Référence mutable au lobby des joueurs
+/// * `snapshots` - Référence mutable aux instantanés reçus
+/// * `exit_events` - Écrivain d'événements de sortie (connexion refusée par le serveur)
+/// * `map_sync_events`, `pickup_sync_events`, `projectile_events`, `hit_confirm_events` -
+///   Écrivains d'événements de synchronisation de la carte, des objets à ramasser, des projectiles
+///   et des impacts confirmés
+/// * `firing` - Référence mutable à l'état du tir local (rechargement en cours)
+/// * `weapons` - Référence mutable aux définitions des armes (remplacées par celles du serveur)
+#[allow(clippy::too_many_arguments)]
 pub fn receive_message_system(
     mut client: ResMut<RenetClient>,
     mut spawn_events: EventWriter<PlayerSpawnEvent>,
@@ -88,11 +172,32 @@ pub fn receive_message_system(
     mut sync_state: ResMut<SyncState>,
     my_id: Res<MyClientId>,
     mut player_query: Query<(&mut PlayerAttributes, &mut Transform)>,
+    mut clock: ResMut<ServerClock>,
+    time: Res<Time>,
+    mut movement_ack_events: EventWriter<MovementAckEvent>,
+    mut prediction: ResMut<MovementPrediction>,
+    mut lobby: ResMut<PlayerLobby>,
+    mut snapshots: ResMut<ReceivedSnapshots>,
+    mut exit_events: EventWriter<AppExit>,
+    (mut map_sync_events, mut pickup_sync_events, mut projectile_events, mut hit_confirm_events): (
+        EventWriter<MapSyncEvent>,
+        EventWriter<PickupSyncEvent>,
+        EventWriter<ProjectileEvent>,
+        EventWriter<HitConfirmEvent>,
+    ),
+    (mut firing, mut weapons): (ResMut<FiringState>, ResMut<WeaponDefinitions>),
 ) {
     // Traitement des messages fiables (canal ReliableOrdered)
     while let Some(message) = client.receive_message(DefaultChannel::ReliableOrdered) {
         if let Ok(server_message) = bincode::deserialize::<ServerMessage>(&message) {
             match server_message {
+                // Connexion refusée par le serveur : affichage de la raison puis sortie
+                ServerMessage::ConnectionRejected { reason } => {
+                    error!("Connexion refusée par le serveur : {}", reason);
+                    eprintln!("❌ Connexion refusée par le serveur : {reason}");
+                    exit_events.send(AppExit::error());
+                    return;
+                }
                 // Message de connexion d'un nouveau joueur
                 ServerMessage::PlayerJoin(client_id) => {
                     info!("Client connected: {}", client_id);
@@ -111,23 +216,49 @@ pub fn receive_message_system(
                     // Déclenchement de l'événement de despawn
                     despawn_events.send(PlayerDespawnEvent(client_id));
                 }
-                // Message de synchronisation du lobby
-                ServerMessage::LobbySync(map) => {
-                    lobby_sync_events.send(LobbySyncEvent(map));
+                // Attributs complets du joueur local à son apparition
+                // (à la connexion, ou sur une nouvelle carte si le joueur existe déjà)
+                ServerMessage::PlayerSpawn { attr } => {
+                    if let Ok((mut player, mut transform)) = player_query.get_single_mut() {
+                        let attr = attr.clone();
+                        reset_local_player(&mut player, &mut transform, &mut prediction, attr);
+                        firing.reload = None;
+                    }
+                    lobby.0.insert(my_id.0, attr);
                 }
                 // Message de dégâts reçus par le joueur local
                 ServerMessage::PlayerHit {
                     new_health,
+                    new_armor,
                     client_id,
+                    zone,
                 } => {
                     // Vérification que c'est bien le joueur local qui a été touché
                     if Some(client_id) == sync_state.client_id {
                         if let Ok((mut player, _)) = player_query.get_single_mut() {
                             player.health = new_health; // Mise à jour de la santé
-                            info!("🔥 Dégât reçu ! Nouvelle vie : {}", new_health);
+                            player.armor = new_armor; // Mise à jour de l'armure
+                            let zone =
+                                zone.map_or("explosion".to_string(), |zone| zone.to_string());
+                            info!(
+                                "🔥 Dégât reçu ({}) ! Nouvelle vie : {}, armure : {}",
+                                zone, new_health, new_armor
+                            );
+                        }
+                        // Le HUD lit la santé et l'armure dans le lobby
+                        if let Some(attr) = lobby.0.get_mut(&client_id) {
+                            attr.health = new_health;
+                            attr.armor = new_armor;
                         }
                     }
                 }
+                // Impact du joueur local confirmé par le serveur
+                ServerMessage::HitConfirm { victim, zone } => {
+                    if zone == Some(HitZone::Head) {
+                        info!("🎯 Tir à la tête sur {}", victim);
+                    }
+                    hit_confirm_events.send(HitConfirmEvent { victim, zone });
+                }
                 // Message de mort d'un joueur
                 ServerMessage::PlayerDeath {
                     dead: client_id,
@@ -136,13 +267,8 @@ pub fn receive_message_system(
                     // Si c'est le joueur local qui est mort, mise à jour de sa position
                     if my_id.0 == client_id {
                         if let Ok((mut player, mut transform)) = player_query.get_single_mut() {
-                            *player = p_attr;
-                            let over_the_ground = 3.; // Hauteur au-dessus du sol
-                            transform.translation = Vec3::new(
-                                player.position.0,
-                                player.position.1 + over_the_ground,
-                                player.position.2
-                            );
+                            reset_local_player(&mut player, &mut transform, &mut prediction, p_attr);
+                            firing.reload = None;
                         }
                     }
 
@@ -150,30 +276,381 @@ pub fn receive_message_system(
                     // Déclenchement de l'événement de despawn
                     despawn_events.send(PlayerDespawnEvent(client_id));
                 }
+                // État de déplacement autoritaire du joueur local
+                ServerMessage::MovementAck { tick, state } => {
+                    movement_ack_events.send(MovementAckEvent { tick, state });
+                }
+                // Message de discussion d'un joueur
+                ServerMessage::Chat { from, text } => {
+                    info!("💬 {} : {}", from, text);
+                }
+                // Paramètres de la partie : cadence des ticks serveur pour l'horloge estimée,
+                // définitions des armes du serveur et carte jouée, chargée depuis le cache ou
+                // demandée au serveur
+                ServerMessage::ServerInfo {
+                    tick_rate,
+                    map,
+                    map_hash,
+                    game_mode,
+                    weapons: server_weapons,
+                } => {
+                    info!("Partie {} sur {} ({} ticks/s)", game_mode, map, tick_rate);
+                    clock.set_tick_rate(tick_rate);
+                    // Des définitions invalides feraient échouer la lecture des armes : refus
+                    if let Err(err) = server_weapons.validate() {
+                        error!("Armes du serveur refusées : {}", err);
+                        eprintln!("❌ Armes du serveur refusées : {err}");
+                        exit_events.send(AppExit::error());
+                        return;
+                    }
+                    if *weapons != server_weapons {
+                        info!("Définitions des armes du serveur appliquées");
+                        *weapons = server_weapons;
+                    }
+                    map_sync_events.send(MapSyncEvent::Announced {
+                        name: map,
+                        hash: map_hash,
+                    });
+                }
+                // Carte complète envoyée à la demande du client
+                ServerMessage::MapData(map) => {
+                    map_sync_events.send(MapSyncEvent::Received(map));
+                }
+                // Fin de la partie : vote pour la carte suivante
+                ServerMessage::MapVote {
+                    candidates,
+                    duration_secs,
+                } => {
+                    map_sync_events.send(MapSyncEvent::VoteStarted {
+                        candidates,
+                        duration_secs,
+                    });
+                }
+                // Nouvelle carte : le niveau est reconstruit
+                ServerMessage::MapChange { map, map_hash } => {
+                    info!("Changement de carte : {}", map);
+                    pickup_sync_events.send(PickupSyncEvent::Cleared);
+                    projectile_events.send(ProjectileEvent::Cleared);
+                    map_sync_events.send(MapSyncEvent::Changed {
+                        name: map,
+                        hash: map_hash,
+                    });
+                }
+                // Objet à ramasser apparu sur la carte
+                ServerMessage::PickupSpawn { id, kind, position } => {
+                    pickup_sync_events.send(PickupSyncEvent::Spawned { id, kind, position });
+                }
+                // Objet ramassé par un joueur
+                ServerMessage::PickupDespawn(id) => {
+                    pickup_sync_events.send(PickupSyncEvent::Despawned(id));
+                }
+                // Objet ramassé par le joueur local : inventaire à jour envoyé par le serveur
+                ServerMessage::PickupTaken {
+                    kind,
+                    owned_weapon,
+                    magazine,
+                    ammo,
+                } => {
+                    info!("🎁 Objet ramassé : {}", kind);
+                    if let Ok((mut player, _)) = player_query.get_single_mut() {
+                        player.owned_weapon = owned_weapon.clone();
+                        player.magazine = magazine.clone();
+                        player.ammo = ammo.clone();
+                    }
+                    if let Some(attr) = lobby.0.get_mut(&my_id.0) {
+                        attr.owned_weapon = owned_weapon;
+                        attr.magazine = magazine;
+                        attr.ammo = ammo;
+                    }
+                }
+                // Projectile lancé par un joueur
+                ServerMessage::ProjectileSpawn {
+                    id,
+                    weapon,
+                    position,
+                    velocity,
+                } => {
+                    projectile_events.send(ProjectileEvent::Spawned {
+                        id,
+                        weapon,
+                        position,
+                        velocity,
+                    });
+                }
+                // Explosion d'un projectile
+                ServerMessage::ProjectileExplode {
+                    id,
+                    position,
+                    radius,
+                } => {
+                    projectile_events.send(ProjectileEvent::Exploded {
+                        id,
+                        position,
+                        radius,
+                    });
+                }
+                // Inventaire corrigé par le serveur (tir ou rechargement refusé, munitions rendues
+                // pour un frag, fin d'un rechargement)
+                ServerMessage::Inventory {
+                    owned_weapon,
+                    actual_weapon,
+                    magazine,
+                    ammo,
+                    reloading,
+                } => {
+                    if let Ok((mut player, _)) = player_query.get_single_mut() {
+                        player.owned_weapon = owned_weapon.clone();
+                        player.actual_weapon = actual_weapon;
+                        player.magazine = magazine.clone();
+                        player.ammo = ammo.clone();
+                    }
+                    if let Some(attr) = lobby.0.get_mut(&my_id.0) {
+                        attr.owned_weapon = owned_weapon;
+                        attr.actual_weapon = actual_weapon;
+                        attr.magazine = magazine;
+                        attr.ammo = ammo;
+                    }
+                    // Le rechargement local se cale sur celui du serveur
+                    let now = time.elapsed_seconds_f64();
+                    firing.reload = reloading.map(|(weapon, secs)| (weapon, now + secs as f64));
+                }
+                // Les instantanés ne transitent que sur le canal non fiable
+                ServerMessage::Snapshot(_) => {}
             }
         }
     }
 
     // Traitement des messages non fiables (canal Unreliable)
     while let Some(message) = client.receive_message(DefaultChannel::Unreliable) {
-        if let Ok(ServerMessage::LobbySync(map)) = bincode::deserialize(&message) {
-            lobby_sync_events.send(LobbySyncEvent(map));
+        match bincode::deserialize(&message) {
+            Ok(ServerMessage::Snapshot(bytes)) => {
+                let snapshot = match Snapshot::decode_delta(&bytes, |tick| snapshots.get(tick)) {
+                    Ok(snapshot) => snapshot,
+                    Err(err) => {
+                        warn!("Instantané ignoré : {}", err);
+                        continue;
+                    }
+                };
+
+                // Les paquets non fiables peuvent arriver dans le désordre
+                if snapshots
+                    .latest_tick()
+                    .is_some_and(|latest| snapshot.tick <= latest)
+                {
+                    continue;
+                }
+                clock.observe(snapshot.tick, time.elapsed_seconds_f64());
+                lobby_sync_events.send(LobbySyncEvent {
+                    tick: snapshot.tick,
+                    players: snapshot.players.clone(),
+                });
+                snapshots.push(snapshot);
+            }
+            Ok(ServerMessage::MovementAck { tick, state }) => {
+                movement_ack_events.send(MovementAckEvent { tick, state });
+            }
+            _ => {}
         }
     }
 }
 
+/// Système de synchronisation de la carte avec le serveur
+/// Une carte annoncée est cherchée dans le cache local puis demandée au serveur si absente ;
+/// une carte reçue est vérifiée avec l'empreinte annoncée, mise en cache puis installée.
+/// Au changement de carte, le niveau et la mini-carte actuels sont détruits avant le chargement.
+///
+/// # Arguments
+/// * `map_sync_events` - Lecteur d'événements de synchronisation de la carte
+/// * `commands` - Commandes Bevy pour installer la carte et détruire l'ancien niveau
+/// * `client` - Référence mutable au client renet
+/// * `pending` - Référence mutable à la carte attendue
+/// * `maze_map` - Carte actuellement installée, s'il y en a une
+/// * `level_entities` - Requête sur les entités du niveau actuel
+/// * `maze_cells` - Requête sur les cases de la mini-carte
+/// * `time` - Référence au temps local (fin du vote)
+#[allow(clippy::too_many_arguments)]
+pub fn handle_map_sync_event_system(
+    mut map_sync_events: EventReader<MapSyncEvent>,
+    mut commands: Commands,
+    mut client: ResMut<RenetClient>,
+    mut pending: ResMut<PendingMap>,
+    maze_map: Option<Res<MazeMap>>,
+    level_entities: Query<Entity, With<LevelEntity>>,
+    maze_cells: Query<Entity, With<MazeCell>>,
+    time: Res<Time>,
+) {
+    // Empreinte de la carte installée, tenue à jour au fil des événements de la frame
+    let mut current_hash = maze_map.map(|current| current.map.content_hash());
+
+    for event in map_sync_events.read() {
+        match event {
+            MapSyncEvent::Announced { name, hash } | MapSyncEvent::Changed { name, hash } => {
+                if current_hash == Some(*hash) {
+                    continue;
+                }
+                if matches!(event, MapSyncEvent::Changed { .. }) {
+                    // Destruction du niveau et de la mini-carte de l'ancienne carte
+                    for entity in level_entities.iter().chain(maze_cells.iter()) {
+                        commands.entity(entity).despawn_recursive();
+                    }
+                    commands.remove_resource::<MazeMap>();
+                    commands.remove_resource::<MapVote>();
+                    // Pas de collisions tant que la nouvelle carte n'est pas chargée
+                    commands.insert_resource(CollisionWorld::default());
+                    current_hash = None;
+                }
+
+                if let Some(map) = map_cache::load(*hash) {
+                    info!("Carte {} chargée depuis le cache", name);
+                    commands.insert_resource(MazeMap { map });
+                    current_hash = Some(*hash);
+                    pending.hash = None;
+                    continue;
+                }
+                info!("Carte {} absente du cache, demande au serveur", name);
+                pending.hash = Some(*hash);
+                let message = ClientMessage::RequestMap;
+                client.send_message(message.channel(), bincode::serialize(&message).unwrap());
+            }
+            MapSyncEvent::Received(map) => {
+                let Some(expected) = pending.hash else {
+                    continue;
+                };
+                let hash = map.content_hash();
+                if hash != expected {
+                    warn!(
+                        "Carte {} ignorée : empreinte {:016x} au lieu de {:016x}",
+                        map.name, hash, expected
+                    );
+                    continue;
+                }
+                if let Err(err) = map_cache::store(map, hash) {
+                    warn!("Mise en cache de la carte {} impossible : {}", map.name, err);
+                }
+                info!("Carte {} reçue du serveur", map.name);
+                commands.insert_resource(MazeMap { map: map.clone() });
+                current_hash = Some(hash);
+                pending.hash = None;
+            }
+            MapSyncEvent::VoteStarted {
+                candidates,
+                duration_secs,
+            } => {
+                info!("Vote pour la carte suivante : {}", candidates.join(", "));
+                commands.insert_resource(MapVote {
+                    candidates: candidates.clone(),
+                    ends_at: time.elapsed_seconds_f64() + *duration_secs as f64,
+                    choice: None,
+                });
+            }
+        }
+    }
+}
+
+/// Système d'affichage des objets à ramasser annoncés par le serveur
+/// Les événements sont appliqués dans l'ordre de réception : un objet réapparu puis aussitôt
+/// ramassé pendant le même tick serveur n'est pas affiché
+///
+/// # Arguments
+/// * `pickup_sync_events` - Lecteur d'événements des objets à ramasser
+/// * `commands` - Commandes Bevy pour créer et détruire les objets
+/// * `meshes` - Référence mutable aux maillages
+/// * `materials` - Référence mutable aux matériaux
+/// * `items` - Requête sur les objets affichés
+pub fn handle_pickup_sync_event_system(
+    mut pickup_sync_events: EventReader<PickupSyncEvent>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    items: Query<(Entity, &PickupItem)>,
+) {
+    // Objets affichés par identifiant, tenus à jour au fil des événements de la frame
+    let mut displayed: HashMap<u32, Entity> = items
+        .iter()
+        .map(|(entity, item)| (item.id, entity))
+        .collect();
+
+    for event in pickup_sync_events.read() {
+        match event {
+            PickupSyncEvent::Spawned { id, kind, position } => {
+                if let Some(entity) = displayed.remove(id) {
+                    commands.entity(entity).despawn_recursive();
+                }
+                let entity = spawn_pickup_item(
+                    &mut commands,
+                    &mut meshes,
+                    &mut materials,
+                    *id,
+                    *kind,
+                    *position,
+                );
+                displayed.insert(*id, entity);
+            }
+            PickupSyncEvent::Despawned(id) => {
+                if let Some(entity) = displayed.remove(id) {
+                    commands.entity(entity).despawn_recursive();
+                }
+            }
+            PickupSyncEvent::Cleared => {
+                for (_, entity) in displayed.drain() {
+                    commands.entity(entity).despawn_recursive();
+                }
+            }
+        }
+    }
+}
+
+/// Replace le joueur local à son point d'apparition avec les attributs envoyés par le serveur
+/// Les entités des armes sont propres au client et conservées
+///
+/// # Arguments
+/// * `player` - Attributs du joueur local
+/// * `transform` - Transformation du joueur local
+/// * `prediction` - Prédiction du déplacement local
+/// * `attr` - Attributs envoyés par le serveur
+fn reset_local_player(
+    player: &mut PlayerAttributes,
+    transform: &mut Transform,
+    prediction: &mut MovementPrediction,
+    attr: PlayerAttributes,
+) {
+    let entities = std::mem::take(&mut player.entities);
+    *player = attr;
+    player.entities = entities;
+    transform.translation = Vec3::from(player.position);
+    // La prédiction repart du point de respawn en attendant le serveur
+    prediction.state = MovementState::at(transform.translation);
+}
+
 /// Système de mise à jour du lobby local
 /// Met à jour le lobby avec les données reçues du serveur
+/// Les champs non répliqués (munitions, armes possédées) des entrées existantes sont conservés
 ///
 /// # Arguments
 /// * `lobby` - Référence mutable au lobby des joueurs
 /// * `lobby_sync_events` - Lecteur d'événements de synchronisation du lobby
+/// * `my_client_id` - Référence à l'ID du client local
 pub fn update_lobby_system(
     mut lobby: ResMut<PlayerLobby>,
     mut lobby_sync_events: EventReader<LobbySyncEvent>,
+    my_client_id: Res<MyClientId>,
 ) {
     for event in lobby_sync_events.read() {
-        lobby.0 = event.0.clone(); // Mise à jour complète du lobby
+        // Suppression des joueurs absents de l'instantané
+        lobby
+            .0
+            .retain(|client_id, _| event.players.contains_key(client_id));
+
+        for (client_id, state) in event.players.iter() {
+            match lobby.0.get_mut(client_id) {
+                Some(attr) => state.apply_to(attr),
+                // Le joueur local n'est ajouté qu'à réception de ses attributs complets
+                None if *client_id != my_client_id.0 => {
+                    lobby.0.insert(*client_id, state.to_attributes());
+                }
+                None => {}
+            }
+        }
     }
 }
 
@@ -184,30 +661,58 @@ pub fn update_lobby_system(
 /// * `commands` - Commandes Bevy pour créer des entités
 /// * `asset_server` - Référence au serveur d'assets
 /// * `spawn_events` - Lecteur d'événements de spawn
+/// * `my_client_id` - Référence à l'ID du client local
 pub fn handle_player_spawn_event_system(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     mut spawn_events: EventReader<PlayerSpawnEvent>,
+    my_client_id: Res<MyClientId>,
 ) {
     for event in spawn_events.read() {
         let client_id = event.0;
         info!("Tentative de spawn du joueur : {:?}", client_id);
 
         // Création de l'entité joueur avec le modèle 3D et les composants nécessaires
-        commands.spawn((
+        let mut entity = commands.spawn((
             SceneBundle {
                 scene: asset_server.load("models/guntest.glb#Scene0"), // Modèle 3D du joueur
                 ..default()
             },
-            Collider::cylinder(1.5, 0.5), // Collision cylindrique pour le joueur
+            player_collider(false),       // Zones du corps du joueur (debout à l'apparition)
+            PlayerStance::default(),      // Posture répliquée du joueur
             PlayerEntity(client_id),      // Composant d'identification du joueur
             Shootable,                    // Composant pour permettre les tirs
         ));
 
+        // Les joueurs distants sont affichés par interpolation entre les états reçus
+        if client_id != my_client_id.0 {
+            entity.insert(SnapshotBuffer::default());
+        }
+
         info!("✅ Joueur {:?} spawné avec succès", client_id);
     }
 }
 
+/// Construit le collider d'un joueur à partir des zones de son corps (tête, torse, jambes)
+/// Les volumes sont ceux utilisés par le serveur pour résoudre les tirs
+///
+/// # Arguments
+/// * `crouched` - Joueur accroupi
+fn player_collider(crouched: bool) -> Collider {
+    Collider::compound(
+        hit_zones(crouched)
+            .into_iter()
+            .map(|volume| {
+                (
+                    Vec3::Y * volume.offset,
+                    Quat::IDENTITY,
+                    Collider::cylinder(volume.half_height, volume.radius),
+                )
+            })
+            .collect(),
+    )
+}
+
 /// Système de gestion des événements de despawn de joueurs
 /// Supprime les entités 3D des joueurs qui se déconnectent
 ///
@@ -235,7 +740,8 @@ pub fn handle_player_despawn_event_system(
 }
 
 /// Système de gestion des événements de synchronisation du lobby
-/// Met à jour les positions et états des joueurs existants et spawn les nouveaux
+/// Enregistre les états reçus des joueurs existants et spawn les nouveaux
+/// Les zones du corps d'un joueur suivent sa posture répliquée
 ///
 /// # Arguments
 /// * `spawn_events` - Écrivain d'événements de spawn
@@ -246,29 +752,55 @@ pub fn handle_player_despawn_event_system(
 pub fn handle_lobby_sync_event_system(
     mut spawn_events: EventWriter<PlayerSpawnEvent>,
     mut sync_events: EventReader<LobbySyncEvent>,
-    mut query: Query<(&PlayerEntity, &mut Transform, Option<&mut PlayerStats>)>,
+    mut query: Query<(
+        &PlayerEntity,
+        &mut Transform,
+        Option<&mut SnapshotBuffer>,
+        Option<&mut PlayerStats>,
+        Option<(&mut PlayerStance, &mut Collider)>,
+    )>,
     my_client_id: Res<MyClientId>,
     mut is_synced: ResMut<IsSynced>,
 ) {
-    if let Some(event) = sync_events.read().last() {
+    // Joueurs dont le spawn a déjà été demandé pendant cette frame
+    let mut requested_spawns = HashSet::new();
+
+    for event in sync_events.read() {
         // Parcours de tous les joueurs dans le lobby
-        for (client_id, player_sync) in event.0.iter() {
+        for (client_id, player_state) in event.players.iter() {
             let mut found = false;
 
             // Mise à jour des joueurs existants
-            for (player_entity, mut transform, stats_opt) in query.iter_mut() {
+            for (player_entity, mut transform, buffer_opt, stats_opt, stance_opt) in
+                query.iter_mut()
+            {
                 if *client_id == player_entity.0 {
-                    // Mise à jour de la position et rotation
-                    transform.translation = player_sync.position.into();
-                    transform.rotation = player_sync.rotation.into();
+                    if let Some(mut buffer) = buffer_opt {
+                        // Joueur distant : l'état est mis en attente pour l'interpolation
+                        buffer.push(RemoteSample {
+                            tick: event.tick,
+                            position: player_state.position(),
+                            rotation: player_state.rotation(),
+                        });
+                    } else {
+                        // Mise à jour de la position et rotation
+                        transform.translation = player_state.position();
+                        transform.rotation = player_state.rotation();
+                    }
 
-                    // Mise à jour des statistiques si disponibles
+                    // Mise à jour des statistiques répliquées si disponibles
                     if let Some(mut stats) = stats_opt {
-                        stats.health = player_sync.health;
-                        stats.armor = player_sync.armor;
-                        stats.owned_weapon = player_sync.owned_weapon.clone();
-                        stats.actual_weapon = player_sync.actual_weapon;
-                        stats.ammo = player_sync.ammo.clone();
+                        stats.health = player_state.health as f32;
+                        stats.armor = player_state.armor as f32;
+                        stats.actual_weapon = player_state.weapon;
+                    }
+
+                    // Zones du corps reconstruites au changement de posture
+                    if let Some((mut stance, mut collider)) = stance_opt {
+                        if stance.crouched != player_state.crouched {
+                            stance.crouched = player_state.crouched;
+                            *collider = player_collider(player_state.crouched);
+                        }
                     }
 
                     found = true;
@@ -277,7 +809,7 @@ pub fn handle_lobby_sync_event_system(
             }
 
             // Spawn des nouveaux joueurs (sauf le joueur local)
-            if !found && *client_id != my_client_id.0 {
+            if !found && *client_id != my_client_id.0 && requested_spawns.insert(*client_id) {
                 spawn_events.send(PlayerSpawnEvent(*client_id));
             }
         }
@@ -287,6 +819,34 @@ pub fn handle_lobby_sync_event_system(
     }
 }
 
+/// Système d'affichage interpolé des joueurs distants
+/// Place chaque joueur distant à l'état correspondant au tick de rendu (tick estimé moins le délai)
+///
+/// # Arguments
+/// * `time` - Référence au temps local
+/// * `clock` - Référence à l'horloge serveur estimée
+/// * `settings` - Référence à la configuration de l'interpolation
+/// * `query` - Requête sur les joueurs distants et leurs états reçus
+pub fn interpolate_remote_players_system(
+    time: Res<Time>,
+    clock: Res<ServerClock>,
+    settings: Res<InterpolationSettings>,
+    mut query: Query<(&mut SnapshotBuffer, &mut Transform)>,
+) {
+    let Some(render_tick) = clock.render_tick(time.elapsed_seconds_f64(), settings.delay) else {
+        return;
+    };
+    let max_extrapolation = settings.max_extrapolation.as_secs_f64() * clock.tick_rate;
+
+    for (mut buffer, mut transform) in query.iter_mut() {
+        if let Some((position, rotation)) = buffer.sample(render_tick, max_extrapolation) {
+            transform.translation = position;
+            transform.rotation = rotation;
+        }
+        buffer.prune(render_tick);
+    }
+}
+
 /// Ressource pour gérer l'état de synchronisation du client
 /// Suit l'état de connexion et de synchronisation avec le serveur
 #[derive(Resource, Default, Debug)]
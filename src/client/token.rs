@@ -0,0 +1,95 @@
+// src/client/token.rs
+
+// Récupération d'un jeton de connexion auprès de l'émetteur du serveur (mode sécurisé)
+
+// Import des modules standard pour le réseau et les entrées/sorties
+use std::{
+    fmt,
+    io::{Read, Write},
+    net::{SocketAddr, TcpStream},
+    time::Duration,
+};
+
+// Import des données de connexion partagées
+use multiplayer_demo::protocol::{encode_query_value, ConnectData, TOKEN_PATH};
+// Import des jetons de connexion renet
+use renet::transport::ConnectToken;
+
+/// Délai maximal de connexion et de réponse de l'émetteur de jetons
+const TOKEN_REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Erreurs possibles lors de la récupération d'un jeton
+#[derive(Debug)]
+pub enum TokenError {
+    Io(std::io::Error),  // Émetteur injoignable ou connexion interrompue
+    InvalidResponse,     // Réponse HTTP illisible
+    Refused(String),     // Demande refusée par l'émetteur, avec sa raison
+    InvalidToken,        // Jeton reçu impossible à décoder
+}
+
+impl fmt::Display for TokenError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TokenError::Io(err) => write!(f, "émetteur de jetons injoignable : {err}"),
+            TokenError::InvalidResponse => write!(f, "réponse de l'émetteur de jetons illisible"),
+            TokenError::Refused(reason) => write!(f, "jeton refusé : {reason}"),
+            TokenError::InvalidToken => write!(f, "jeton de connexion invalide"),
+        }
+    }
+}
+
+impl From<std::io::Error> for TokenError {
+    fn from(err: std::io::Error) -> Self {
+        TokenError::Io(err)
+    }
+}
+
+/// Demande un jeton de connexion à l'émetteur du serveur
+///
+/// # Arguments
+/// * `issuer_addr` - Adresse HTTP de l'émetteur de jetons
+/// * `connect_data` - Version du protocole et nom d'utilisateur du joueur
+///
+/// # Returns
+/// * `Result<ConnectToken, TokenError>` - Le jeton à présenter au serveur de jeu
+pub fn fetch_connect_token(
+    issuer_addr: SocketAddr,
+    connect_data: &ConnectData,
+) -> Result<ConnectToken, TokenError> {
+    let mut stream = TcpStream::connect_timeout(&issuer_addr, TOKEN_REQUEST_TIMEOUT)?;
+    stream.set_read_timeout(Some(TOKEN_REQUEST_TIMEOUT))?;
+
+    write!(
+        stream,
+        "GET {TOKEN_PATH}?version={}&username={} HTTP/1.0\r\nHost: {issuer_addr}\r\n\r\n",
+        connect_data.version,
+        encode_query_value(&connect_data.username)
+    )?;
+    stream.flush()?;
+
+    let mut response = Vec::new();
+    stream.read_to_end(&mut response)?;
+
+    // Séparation de l'en-tête et du corps de la réponse
+    let header_end = response
+        .windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .ok_or(TokenError::InvalidResponse)?;
+    let header = std::str::from_utf8(&response[..header_end])
+        .map_err(|_| TokenError::InvalidResponse)?;
+    let body = &response[header_end + 4..];
+
+    let status = header
+        .lines()
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|code| code.parse::<u16>().ok())
+        .ok_or(TokenError::InvalidResponse)?;
+    if status != 200 {
+        return Err(TokenError::Refused(
+            String::from_utf8_lossy(body).into_owned(),
+        ));
+    }
+
+    ConnectToken::read(&mut &body[..]).map_err(|_| TokenError::InvalidToken)
+}
@@ -0,0 +1,419 @@
+// Monde de collision statique construit à partir de la grille du labyrinthe
+// Utilisé par le serveur pour résoudre les tirs sans faire confiance au client
+
+// Import du module d'affichage pour les noms des zones touchées
+use std::fmt;
+
+// Import des modules Bevy pour les ressources et les mathématiques
+use bevy::{ecs::system::Resource, math::Vec3};
+// Import de renet pour l'identification des joueurs touchés
+use renet::ClientId;
+// Import de serde pour l'envoi des zones touchées
+use serde::{Deserialize, Serialize};
+
+// Import du format de carte partagé
+use crate::map::{MapDefinition, WALL_CELL};
+
+/// Hauteur du dessus du sol (les dalles du client font 0.1 d'épaisseur)
+pub const FLOOR_HEIGHT: f32 = 0.1;
+/// Demi-hauteur du volume de collision d'un joueur (zones du corps comprises)
+pub const PLAYER_HULL_HALF_HEIGHT: f32 = 1.5;
+/// Rayon du volume de collision d'un joueur
+pub const PLAYER_HULL_RADIUS: f32 = 0.5;
+
+/// Zone du corps d'un joueur touchée par un tir
+/// Les zones sont ordonnées de la plus à la moins vulnérable
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HitZone {
+    Head,  // Tête
+    Torso, // Torse
+    Legs,  // Jambes
+}
+
+impl fmt::Display for HitZone {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            HitZone::Head => "tête",
+            HitZone::Torso => "torse",
+            HitZone::Legs => "jambes",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Volume d'une zone du corps : cylindre vertical placé par rapport au centre du joueur
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ZoneVolume {
+    pub zone: HitZone,    // Zone représentée
+    pub offset: f32,      // Hauteur du centre du cylindre par rapport au centre du joueur
+    pub half_height: f32, // Demi-hauteur du cylindre
+    pub radius: f32,      // Rayon du cylindre
+}
+
+/// Volumes des zones du corps d'un joueur selon sa posture
+/// Debout, le corps occupe toute la hauteur du volume de déplacement et la tête entoure la hauteur
+/// des yeux (`movement::STANDING_EYE_HEIGHT`) ; accroupi, tête et torse descendent avec la caméra
+/// (`movement::CROUCH_EYE_HEIGHT`) et rien ne dépasse plus le haut de la tête
+///
+/// # Arguments
+/// * `crouched` - Joueur accroupi
+///
+/// # Returns
+/// * `[ZoneVolume; 3]` - Volumes de la tête, du torse et des jambes
+pub fn hit_zones(crouched: bool) -> [ZoneVolume; 3] {
+    // Limites des zones (bas du corps, jambes/torse, torse/tête, haut de la tête)
+    let [bottom, hips, neck, top] = if crouched {
+        [-PLAYER_HULL_HALF_HEIGHT, -0.8, 0.0, 0.6]
+    } else {
+        [-PLAYER_HULL_HALF_HEIGHT, -0.3, 0.9, PLAYER_HULL_HALF_HEIGHT]
+    };
+    let volume = |zone, low: f32, high: f32, radius| ZoneVolume {
+        zone,
+        offset: (low + high) / 2.0,
+        half_height: (high - low) / 2.0,
+        radius,
+    };
+    [
+        volume(HitZone::Head, neck, top, 0.3),
+        volume(HitZone::Torso, hips, neck, PLAYER_HULL_RADIUS),
+        volume(HitZone::Legs, bottom, hips, 0.4),
+    ]
+}
+
+/// Boîte englobante alignée sur les axes
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Aabb {
+    pub min: Vec3, // Coin minimal de la boîte
+    pub max: Vec3, // Coin maximal de la boîte
+}
+
+impl Aabb {
+    /// Crée une boîte à partir de son centre et de ses demi-dimensions
+    ///
+    /// # Arguments
+    /// * `center` - Centre de la boîte
+    /// * `half_extents` - Demi-dimensions de la boîte sur chaque axe
+    pub fn from_center_half_extents(center: Vec3, half_extents: Vec3) -> Self {
+        Self {
+            min: center - half_extents,
+            max: center + half_extents,
+        }
+    }
+
+    /// Calcule la distance d'entrée d'un rayon dans la boîte (méthode des slabs)
+    ///
+    /// # Arguments
+    /// * `origin` - Origine du rayon
+    /// * `dir` - Direction normalisée du rayon
+    /// * `max_distance` - Distance maximale testée
+    ///
+    /// # Returns
+    /// * `Option<f32>` - Distance du point d'entrée, ou None si le rayon rate la boîte
+    pub fn ray_intersection(&self, origin: Vec3, dir: Vec3, max_distance: f32) -> Option<f32> {
+        let mut t_min = 0.0_f32;
+        let mut t_max = max_distance;
+
+        for axis in 0..3 {
+            let o = origin[axis];
+            let d = dir[axis];
+            let (min, max) = (self.min[axis], self.max[axis]);
+
+            if d.abs() < f32::EPSILON {
+                // Rayon parallèle au slab : il doit déjà se trouver entre les deux plans
+                if o < min || o > max {
+                    return None;
+                }
+            } else {
+                let inv = 1.0 / d;
+                let mut t0 = (min - o) * inv;
+                let mut t1 = (max - o) * inv;
+                if t0 > t1 {
+                    std::mem::swap(&mut t0, &mut t1);
+                }
+                t_min = t_min.max(t0);
+                t_max = t_max.min(t1);
+                if t_min > t_max {
+                    return None;
+                }
+            }
+        }
+
+        Some(t_min)
+    }
+}
+
+/// Élément du monde touché par un rayon
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HitTarget {
+    Wall,                      // Un mur du labyrinthe
+    Floor,                     // Le sol
+    Player(ClientId, HitZone), // Une zone du corps d'un joueur
+}
+
+/// Résultat d'un lancer de rayon dans le monde de collision
+#[derive(Clone, Copy, Debug)]
+pub struct RayHit {
+    pub target: HitTarget, // Élément touché en premier
+    pub distance: f32,     // Distance depuis l'origine du rayon
+    pub point: Vec3,       // Point d'impact
+}
+
+/// Volume de collision d'un joueur utilisé pour la résolution des tirs
+#[derive(Clone, Copy, Debug)]
+pub struct PlayerHull {
+    pub client_id: ClientId, // Joueur associé au volume
+    pub position: Vec3,      // Centre du volume (position répliquée du joueur)
+    pub crouched: bool,      // Joueur accroupi (zones du corps abaissées)
+}
+
+/// Ressource contenant la géométrie statique du niveau
+#[derive(Resource, Clone, Debug, Default)]
+pub struct CollisionWorld {
+    walls: Vec<Aabb>, // Boîtes des murs
+    floor_height: f32, // Hauteur du sol
+}
+
+impl CollisionWorld {
+    /// Construit le monde de collision à partir d'une carte
+    /// Reprend la convention de placement de `MapDefinition::cell_position`, comme `init_level`
+    ///
+    /// # Arguments
+    /// * `map` - Carte jouée
+    ///
+    /// # Returns
+    /// * `CollisionWorld` - Le monde de collision prêt à être interrogé
+    pub fn from_map(map: &MapDefinition) -> Self {
+        let half = Vec3::new(
+            map.cell_size / 2.0,
+            map.wall_height / 2.0,
+            map.cell_size / 2.0,
+        );
+
+        let walls = map
+            .cells_with(WALL_CELL)
+            .map(|(x, y)| {
+                let center = map.cell_position(x, y) + Vec3::Y * (map.wall_height / 2.0);
+                Aabb::from_center_half_extents(center, half)
+            })
+            .collect();
+
+        Self {
+            walls,
+            floor_height: FLOOR_HEIGHT,
+        }
+    }
+
+    /// Liste des boîtes de murs du niveau
+    pub fn walls(&self) -> &[Aabb] {
+        &self.walls
+    }
+
+    /// Lance un rayon contre les murs, le sol et les zones du corps des joueurs
+    ///
+    /// # Arguments
+    /// * `origin` - Origine du rayon
+    /// * `dir` - Direction du rayon (normalisée par la fonction)
+    /// * `max_distance` - Portée maximale
+    /// * `players` - Volumes des joueurs pouvant être touchés
+    ///
+    /// # Returns
+    /// * `Option<RayHit>` - Premier élément touché, ou None si rien dans la portée
+    pub fn cast_ray(
+        &self,
+        origin: Vec3,
+        dir: Vec3,
+        max_distance: f32,
+        players: &[PlayerHull],
+    ) -> Option<RayHit> {
+        let dir = dir.try_normalize()?;
+        let mut best: Option<(HitTarget, f32)> = None;
+        let mut consider = |target: HitTarget, distance: f32| {
+            if best.map_or(true, |(_, d)| distance < d) {
+                best = Some((target, distance));
+            }
+        };
+
+        // Murs du labyrinthe
+        for wall in &self.walls {
+            if let Some(t) = wall.ray_intersection(origin, dir, max_distance) {
+                consider(HitTarget::Wall, t);
+            }
+        }
+
+        // Sol (plan horizontal)
+        if dir.y < 0.0 && origin.y >= self.floor_height {
+            let t = (self.floor_height - origin.y) / dir.y;
+            if t <= max_distance {
+                consider(HitTarget::Floor, t);
+            }
+        }
+
+        // Zones du corps des joueurs, selon leur posture
+        for hull in players {
+            for volume in hit_zones(hull.crouched) {
+                if let Some(t) = ray_cylinder(
+                    origin,
+                    dir,
+                    hull.position + Vec3::Y * volume.offset,
+                    volume.radius,
+                    volume.half_height,
+                    max_distance,
+                ) {
+                    consider(HitTarget::Player(hull.client_id, volume.zone), t);
+                }
+            }
+        }
+
+        best.map(|(target, distance)| RayHit {
+            target,
+            distance,
+            point: origin + dir * distance,
+        })
+    }
+}
+
+/// Intersection d'un rayon avec un cylindre vertical fermé
+///
+/// # Arguments
+/// * `origin` - Origine du rayon
+/// * `dir` - Direction normalisée du rayon
+/// * `center` - Centre du cylindre
+/// * `radius` - Rayon du cylindre
+/// * `half_height` - Demi-hauteur du cylindre
+/// * `max_distance` - Portée maximale
+///
+/// # Returns
+/// * `Option<f32>` - Distance du point d'entrée, ou None si le rayon rate le cylindre
+fn ray_cylinder(
+    origin: Vec3,
+    dir: Vec3,
+    center: Vec3,
+    radius: f32,
+    half_height: f32,
+    max_distance: f32,
+) -> Option<f32> {
+    let o = origin - center;
+    let inside_radius = |p: Vec3| p.x * p.x + p.z * p.z <= radius * radius;
+
+    // Origine déjà à l'intérieur du volume
+    if inside_radius(o) && o.y.abs() <= half_height {
+        return Some(0.0);
+    }
+
+    let mut best: Option<f32> = None;
+    let mut consider = |t: f32| {
+        if (0.0..=max_distance).contains(&t) && best.map_or(true, |b| t < b) {
+            best = Some(t);
+        }
+    };
+
+    // Paroi latérale : résolution de l'équation du second degré dans le plan XZ
+    let a = dir.x * dir.x + dir.z * dir.z;
+    if a > f32::EPSILON {
+        let b = 2.0 * (o.x * dir.x + o.z * dir.z);
+        let c = o.x * o.x + o.z * o.z - radius * radius;
+        let discriminant = b * b - 4.0 * a * c;
+        if discriminant >= 0.0 {
+            let t = (-b - discriminant.sqrt()) / (2.0 * a);
+            if (o.y + dir.y * t).abs() <= half_height {
+                consider(t);
+            }
+        }
+    }
+
+    // Couvercles haut et bas
+    if dir.y.abs() > f32::EPSILON {
+        for cap in [half_height, -half_height] {
+            let t = (cap - o.y) / dir.y;
+            if inside_radius(o + dir * t) {
+                consider(t);
+            }
+        }
+    }
+
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Centre du joueur visé, assez haut pour que les rayons horizontaux ne touchent pas le sol
+    const CENTER: Vec3 = Vec3::new(0.0, 10.0, 0.0);
+
+    /// Tire horizontalement vers le joueur, à une hauteur donnée par rapport à son centre
+    ///
+    /// # Arguments
+    /// * `height` - Hauteur du rayon par rapport au centre du joueur
+    /// * `crouched` - Joueur accroupi
+    ///
+    /// # Returns
+    /// * `Option<HitTarget>` - Élément touché par le rayon
+    fn shoot_at(height: f32, crouched: bool) -> Option<HitTarget> {
+        let hull = PlayerHull {
+            client_id: ClientId::from_raw(7),
+            position: CENTER,
+            crouched,
+        };
+        let origin = CENTER + Vec3::new(0.0, height, 10.0);
+        CollisionWorld::default()
+            .cast_ray(origin, Vec3::NEG_Z, 50.0, &[hull])
+            .map(|hit| hit.target)
+    }
+
+    /// Cible attendue pour une zone du joueur visé
+    fn player(zone: HitZone) -> Option<HitTarget> {
+        Some(HitTarget::Player(ClientId::from_raw(7), zone))
+    }
+
+    #[test]
+    fn standing_zones_are_hit_at_their_height() {
+        assert_eq!(shoot_at(1.2, false), player(HitZone::Head));
+        assert_eq!(shoot_at(0.3, false), player(HitZone::Torso));
+        assert_eq!(shoot_at(-0.9, false), player(HitZone::Legs));
+    }
+
+    #[test]
+    fn crouched_zones_are_lowered() {
+        assert_eq!(shoot_at(0.3, true), player(HitZone::Head));
+        assert_eq!(shoot_at(-0.4, true), player(HitZone::Torso));
+        assert_eq!(shoot_at(-1.2, true), player(HitZone::Legs));
+        // Hauteur de la tête debout : le tir passe au-dessus du joueur accroupi
+        assert_eq!(shoot_at(1.2, true), None);
+    }
+
+    #[test]
+    fn ray_above_the_head_misses() {
+        assert_eq!(shoot_at(PLAYER_HULL_HALF_HEIGHT + 0.1, false), None);
+        assert_eq!(shoot_at(0.7, true), None);
+    }
+
+    #[test]
+    fn first_zone_along_the_ray_is_kept() {
+        // Rayon plongeant qui entre par le haut de la tête et traverse tout le corps
+        let hull = PlayerHull {
+            client_id: ClientId::from_raw(7),
+            position: CENTER,
+            crouched: false,
+        };
+        let origin = CENTER + Vec3::Y * 5.0;
+        let hit = CollisionWorld::default()
+            .cast_ray(origin, Vec3::NEG_Y, 50.0, &[hull])
+            .expect("tir vertical");
+        assert_eq!(Some(hit.target), player(HitZone::Head));
+        assert!((hit.distance - (5.0 - PLAYER_HULL_HALF_HEIGHT)).abs() < 1e-5);
+    }
+
+    #[test]
+    fn zones_stack_without_gaps() {
+        for crouched in [false, true] {
+            let [head, torso, legs] = hit_zones(crouched);
+            let top = |volume: ZoneVolume| volume.offset + volume.half_height;
+            let bottom = |volume: ZoneVolume| volume.offset - volume.half_height;
+            assert!((bottom(head) - top(torso)).abs() < 1e-6);
+            assert!((bottom(torso) - top(legs)).abs() < 1e-6);
+            assert!((bottom(legs) + PLAYER_HULL_HALF_HEIGHT).abs() < 1e-6);
+            assert!(top(head) <= PLAYER_HULL_HALF_HEIGHT);
+        }
+    }
+}
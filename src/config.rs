@@ -0,0 +1,147 @@
+// Chargement des configurations du serveur et du client
+// Chaque programme lit un fichier RON optionnel puis applique ses options de ligne de commande ;
+// les valeurs sont validées au démarrage et toutes les erreurs sont signalées ensemble
+
+// Import des modules standard pour l'affichage et les fichiers
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+// Import de serde pour la lecture du fichier de configuration
+use serde::de::DeserializeOwned;
+
+/// Erreurs de configuration signalées au démarrage
+#[derive(Debug)]
+pub enum ConfigError {
+    /// Aide demandée (pas une erreur, mais interrompt le démarrage)
+    Help,
+    /// Fichier de configuration illisible
+    Io {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    /// Fichier de configuration mal formé
+    Parse {
+        path: PathBuf,
+        error: ron::error::SpannedError,
+    },
+    /// Option de ligne de commande invalide (l'aide du programme est affichée ensuite)
+    Cli(String),
+    /// Valeurs incohérentes (toutes les erreurs trouvées)
+    Invalid(Vec<String>),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::Help => write!(f, "aide demandée"),
+            ConfigError::Io { path, error } => {
+                write!(f, "lecture de {} impossible : {error}", path.display())
+            }
+            ConfigError::Parse { path, error } => {
+                write!(f, "{} mal formé : {error}", path.display())
+            }
+            ConfigError::Cli(message) => write!(f, "{message}"),
+            ConfigError::Invalid(errors) => {
+                write!(f, "configuration invalide :")?;
+                for error in errors {
+                    write!(f, "\n  - {error}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Configuration d'un programme : valeurs par défaut, puis fichier RON, puis ligne de commande
+/// Chaque programme ne fournit que son fichier par défaut, ses options et ses vérifications
+pub trait ConfigFile: DeserializeOwned + Default {
+    /// Fichier de configuration lu par défaut s'il existe dans le répertoire courant
+    const DEFAULT_PATH: &'static str;
+
+    /// Applique les options de la ligne de commande par-dessus la configuration
+    /// `--config` et sa valeur sont déjà traitées et peuvent être ignorées
+    ///
+    /// # Arguments
+    /// * `args` - Arguments de la ligne de commande
+    fn apply_args(&mut self, args: &[String]) -> Result<(), ConfigError>;
+
+    /// Vérifie la cohérence de la configuration et rassemble toutes les erreurs
+    fn validate(&self) -> Result<(), ConfigError>;
+
+    /// Charge la configuration : valeurs par défaut, puis fichier, puis ligne de commande
+    ///
+    /// # Arguments
+    /// * `args` - Arguments de la ligne de commande (sans le nom du programme)
+    ///
+    /// # Returns
+    /// * `Result<Self, ConfigError>` - Configuration validée
+    fn load(args: impl IntoIterator<Item = String>) -> Result<Self, ConfigError> {
+        let args: Vec<String> = args.into_iter().collect();
+        if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+            return Err(ConfigError::Help);
+        }
+
+        // Fichier explicite (obligatoire) ou fichier par défaut (facultatif)
+        let explicit = option_value(&args, "--config")?;
+        let mut settings = match explicit {
+            Some(path) => Self::from_file(Path::new(&path))?,
+            None if Path::new(Self::DEFAULT_PATH).exists() => {
+                Self::from_file(Path::new(Self::DEFAULT_PATH))?
+            }
+            None => Self::default(),
+        };
+
+        settings.apply_args(&args)?;
+        settings.validate()?;
+        Ok(settings)
+    }
+
+    /// Lit un fichier de configuration RON
+    ///
+    /// # Arguments
+    /// * `path` - Chemin du fichier
+    fn from_file(path: &Path) -> Result<Self, ConfigError> {
+        let text = std::fs::read_to_string(path).map_err(|error| ConfigError::Io {
+            path: path.to_path_buf(),
+            error,
+        })?;
+        ron::from_str(&text).map_err(|error| ConfigError::Parse {
+            path: path.to_path_buf(),
+            error,
+        })
+    }
+}
+
+/// Recherche la valeur d'une option dans les arguments
+///
+/// # Arguments
+/// * `args` - Arguments de la ligne de commande
+/// * `name` - Nom de l'option
+///
+/// # Returns
+/// * `Result<Option<String>, ConfigError>` - Valeur de l'option si elle est présente
+pub fn option_value(args: &[String], name: &str) -> Result<Option<String>, ConfigError> {
+    match args.iter().position(|arg| arg == name) {
+        Some(index) => args
+            .get(index + 1)
+            .cloned()
+            .map(Some)
+            .ok_or_else(|| ConfigError::Cli(format!("valeur manquante pour {name}"))),
+        None => Ok(None),
+    }
+}
+
+/// Convertit la valeur d'une option
+///
+/// # Arguments
+/// * `name` - Nom de l'option (pour le message d'erreur)
+/// * `value` - Valeur à convertir
+pub fn parse_value<T: std::str::FromStr>(name: &str, value: &str) -> Result<T, ConfigError> {
+    value
+        .parse()
+        .map_err(|_| ConfigError::Cli(format!("valeur invalide pour {name} : {value}")))
+}
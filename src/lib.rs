@@ -6,33 +6,215 @@ use bevy::{
     ecs::{component::Component, entity::Entity, system::Resource},
     math::{Quat, Vec3},
 };
-// Import de renet pour la gestion des identifiants clients
-use renet::ClientId;
+// Import de renet pour la gestion des identifiants clients et des canaux
+use renet::{ClientId, DefaultChannel};
 // Import de serde pour la sérialisation/désérialisation des données
 use serde::{Deserialize, Serialize};
-// Import pour la gestion du temps
-use std::time::Instant;
+
+// Import des types de simulation du déplacement
+use movement::{MovementInput, MovementState};
+// Import du format de carte transmis aux clients
+use map::MapDefinition;
+// Import des objets à ramasser
+use pickup::PickupKind;
+// Import des définitions d'armes transmises aux clients
+use weapons::WeaponDefinitions;
+// Import des zones du corps touchées par les tirs
+use collision::HitZone;
+
+// Modules partagés entre le client et le serveur
+pub mod collision; // Monde de collision statique (murs, sol, volumes des joueurs)
+pub mod config;    // Chargement des fichiers de configuration et des options
+pub mod map;       // Format des fichiers de carte
+pub mod mapgen;    // Générateur procédural de labyrinthes
+pub mod movement;  // Simulation déterministe du déplacement des joueurs
+pub mod pickup;    // Objets à ramasser posés sur la carte
+pub mod protocol;  // Version du protocole et données de connexion
+pub mod snapshot;  // Instantanés de l'état des joueurs encodés en différentiel
+pub mod weapons;   // Définitions des armes (fichier RON partagé)
 
 /// Énumération des messages envoyés par le serveur aux clients
 /// Ces messages permettent la synchronisation entre le serveur et les clients
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum ServerMessage {
+    /// Refus de la connexion avec une raison lisible, suivi de la déconnexion du client
+    /// Doit rester le premier variant pour être décodable quelle que soit la version du client
+    ConnectionRejected {
+        reason: String, // Raison du refus
+    },
     /// Message envoyé quand un nouveau joueur rejoint le serveur
     PlayerJoin(ClientId),
     /// Message envoyé quand un joueur quitte le serveur
     PlayerLeave(ClientId),
-    /// Synchronisation de l'état du lobby avec tous les joueurs connectés
-    LobbySync(HashMap<ClientId, PlayerAttributes>),
+    /// Instantané de l'état des joueurs, encodé par `snapshot::Snapshot::encode_delta`
+    /// par rapport au dernier instantané acquitté par le client destinataire
+    Snapshot(Vec<u8>),
+    /// Attributs complets du joueur destinataire à son apparition
+    /// (armes possédées et munitions ne sont pas répliquées dans les instantanés)
+    PlayerSpawn {
+        attr: PlayerAttributes, // Attributs du joueur local
+    },
     /// Message envoyé quand un joueur est touché par un tir
     PlayerHit {
-        client_id: ClientId,  // ID du joueur touché
-        new_health: f32,      // Nouvelle santé du joueur
+        client_id: ClientId,   // ID du joueur touché
+        new_health: f32,       // Nouvelle santé du joueur
+        new_armor: f32,        // Nouvelle armure du joueur (après absorption)
+        zone: Option<HitZone>, // Zone du corps touchée (None pour une explosion)
+    },
+    /// Confirmation envoyée au tireur quand il blesse un autre joueur (retour visuel du HUD)
+    HitConfirm {
+        victim: ClientId,      // ID du joueur touché
+        zone: Option<HitZone>, // Zone du corps touchée (None pour une explosion)
     },
     /// Message envoyé quand un joueur meurt
     PlayerDeath {
         dead: ClientId,                    // ID du joueur mort
         attr:PlayerAttributes,     // Nouvelle position de respawn
     },
+    /// Message de discussion relayé à tous les joueurs
+    Chat {
+        from: ClientId, // Auteur du message
+        text: String,   // Texte du message
+    },
+    /// État de déplacement autoritaire du joueur destinataire
+    /// Le client rejoue à partir de cet état les entrées qui n'ont pas encore été traitées
+    MovementAck {
+        tick: u32,            // Numéro de la dernière entrée traitée par le serveur
+        state: MovementState, // État du joueur après cette entrée
+    },
+    /// Paramètres de la partie envoyés au client dès que sa connexion est acceptée
+    /// Le client compare `map_hash` à ses cartes en cache et demande la carte s'il ne l'a pas ;
+    /// il remplace ses définitions d'armes locales par celles du serveur
+    ServerInfo {
+        tick_rate: u32,             // Ticks serveur par seconde (cadence des instantanés)
+        map: String,                // Nom de la carte jouée
+        map_hash: u64,              // Empreinte du contenu de la carte (`MapDefinition::content_hash`)
+        game_mode: GameMode,        // Mode de jeu
+        weapons: WeaponDefinitions, // Définitions des armes utilisées par le serveur
+    },
+    /// Définition complète de la carte jouée, envoyée à la demande du client
+    MapData(MapDefinition),
+    /// Fin de la partie : vote pour la carte suivante parmi quelques candidates
+    MapVote {
+        candidates: Vec<String>, // Noms des cartes proposées, dans l'ordre des choix
+        duration_secs: u32,      // Durée du vote
+    },
+    /// Changement de carte : le client reconstruit le niveau, depuis son cache ou après `RequestMap`
+    /// Les joueurs réapparaissent ensuite sur les points de spawn de la nouvelle carte
+    MapChange {
+        map: String,   // Nom de la nouvelle carte
+        map_hash: u64, // Empreinte de son contenu
+    },
+    /// Apparition d'un objet à ramasser (envoyée à l'entrée dans la partie, au changement de
+    /// carte et à la réapparition d'un objet ramassé)
+    PickupSpawn {
+        id: u32,          // Identifiant de l'objet (indice de sa cellule sur la carte)
+        kind: PickupKind, // Nature de l'objet
+        position: Vec3,   // Position du centre de l'objet
+    },
+    /// Disparition d'un objet ramassé, jusqu'à sa réapparition
+    PickupDespawn(u32),
+    /// Objet ramassé par le joueur destinataire, avec son inventaire à jour
+    /// (armes possédées et munitions ne sont pas répliquées dans les instantanés)
+    PickupTaken {
+        kind: PickupKind,                    // Nature de l'objet ramassé
+        owned_weapon: HashMap<Weapon, bool>, // Armes possédées
+        magazine: HashMap<Weapon, f32>,      // Munitions dans le chargeur de chaque arme
+        ammo: HashMap<Weapon, f32>,          // Munitions en réserve par arme
+    },
+    /// Projectile simulé par le serveur (roquette, boule d'énergie) lancé par un joueur
+    /// Les clients le font avancer en ligne droite jusqu'à son explosion
+    ProjectileSpawn {
+        id: u32,        // Identifiant du projectile
+        weapon: Weapon, // Arme ayant tiré le projectile (modèle affiché)
+        position: Vec3, // Position de départ
+        velocity: Vec3, // Vitesse du projectile
+    },
+    /// Explosion d'un projectile, qui disparaît
+    ProjectileExplode {
+        id: u32,        // Identifiant du projectile
+        position: Vec3, // Centre de l'explosion
+        radius: f32,    // Rayon de l'explosion
+    },
+    /// Inventaire autoritaire du joueur destinataire, envoyé quand celui du client diverge
+    /// (tir ou rechargement refusé, munitions rendues pour un frag) et à la fin d'un rechargement
+    Inventory {
+        owned_weapon: HashMap<Weapon, bool>, // Armes possédées
+        actual_weapon: Weapon,               // Arme équipée
+        magazine: HashMap<Weapon, f32>,      // Munitions dans le chargeur de chaque arme
+        ammo: HashMap<Weapon, f32>,          // Munitions en réserve par arme
+        reloading: Option<(Weapon, f32)>,    // Arme en cours de rechargement et secondes restantes
+    },
+}
+
+/// Longueur maximale (en caractères) d'un message de discussion
+pub const MAX_CHAT_LENGTH: usize = 200;
+
+/// Énumération des messages envoyés par les clients au serveur
+/// Chaque message a un canal attitré, vérifié par le serveur à la réception
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum ClientMessage {
+    /// Commande d'entrées du pas de simulation
+    Input(PlayerInputCommand),
+    /// Tir effectué par le joueur
+    Shoot(PlayerShoot),
+    /// Changement de l'arme équipée
+    SwitchWeapon(Weapon),
+    /// Message de discussion destiné à tous les joueurs
+    Chat(String),
+    /// Le client n'a pas la carte annoncée dans `ServerInfo` et demande son envoi
+    RequestMap,
+    /// Le client a chargé le niveau et demande à apparaître dans la partie
+    Ready,
+    /// Le client quitte volontairement la partie
+    Leave,
+    /// Choix du joueur pendant le vote de la carte suivante (indice dans `MapVote::candidates`)
+    VoteMap(u8),
+    /// Pression ou relâchement de la gâchette d'une arme à préparation (rotation, charge)
+    Trigger {
+        weapon: Weapon, // Arme concernée
+        pressed: bool,  // True à la pression, False au relâchement
+    },
+    /// Rechargement de l'arme équipée demandé par le joueur
+    Reload(Weapon),
+}
+
+impl ClientMessage {
+    /// Canal sur lequel le message doit être envoyé
+    /// Les entrées, renvoyées à chaque tick, tolèrent les pertes ; les autres messages non
+    ///
+    /// # Returns
+    /// * `DefaultChannel` - Canal attitré du message
+    pub fn channel(&self) -> DefaultChannel {
+        match self {
+            ClientMessage::Input(_) => DefaultChannel::Unreliable,
+            ClientMessage::Shoot(_)
+            | ClientMessage::SwitchWeapon(_)
+            | ClientMessage::Chat(_)
+            | ClientMessage::RequestMap
+            | ClientMessage::Ready
+            | ClientMessage::Leave
+            | ClientMessage::VoteMap(_)
+            | ClientMessage::Trigger { .. }
+            | ClientMessage::Reload(_) => DefaultChannel::ReliableOrdered,
+        }
+    }
+
+    /// Nom du message pour les journaux
+    pub fn name(&self) -> &'static str {
+        match self {
+            ClientMessage::Input(_) => "Input",
+            ClientMessage::Shoot(_) => "Shoot",
+            ClientMessage::SwitchWeapon(_) => "SwitchWeapon",
+            ClientMessage::Chat(_) => "Chat",
+            ClientMessage::RequestMap => "RequestMap",
+            ClientMessage::Ready => "Ready",
+            ClientMessage::Leave => "Leave",
+            ClientMessage::VoteMap(_) => "VoteMap",
+            ClientMessage::Trigger { .. } => "Trigger",
+            ClientMessage::Reload(_) => "Reload",
+        }
+    }
 }
 
 /// Énumération des armes disponibles dans le jeu
@@ -46,6 +228,17 @@ pub enum Weapon {
     Bfg,            // BFG (Big Fucking Gun) - arme puissante
 }
 
+impl Weapon {
+    /// Toutes les armes, dans l'ordre du HUD
+    pub const ALL: [Weapon; 5] = [
+        Weapon::Gun,
+        Weapon::Shotgun,
+        Weapon::Gatling,
+        Weapon::RocketLauncher,
+        Weapon::Bfg,
+    ];
+}
+
 /// Implémentation de l'affichage pour l'énumération Weapon
 /// Permet de convertir une arme en chaîne de caractères pour l'affichage
 impl std::fmt::Display for Weapon {
@@ -60,13 +253,37 @@ impl std::fmt::Display for Weapon {
     }
 }
 
+/// Modes de jeu proposés par le serveur
+#[derive(Eq, Hash, PartialEq, Copy, Clone, Debug, Serialize, Deserialize)]
+pub enum GameMode {
+    Deathmatch, // Chacun pour soi
+}
+
+impl std::fmt::Display for GameMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            GameMode::Deathmatch => write!(f, "Deathmatch"),
+        }
+    }
+}
+
+/// Commande d'entrées envoyée par le client à chaque pas de simulation
+/// Contient les dernières entrées non acquittées pour résister aux pertes de paquets
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PlayerInputCommand {
+    pub inputs: Vec<MovementInput>, // Entrées dans l'ordre croissant de leur numéro
+    pub snapshot_ack: Option<u32>,  // Tick du dernier instantané reçu par le client
+}
+
 /// Structure représentant un tir effectué par un joueur
 /// Contient les informations nécessaires pour traiter le tir côté serveur
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct PlayerShoot {
     pub weapon: Weapon,  // Arme utilisée pour le tir
     pub from: Vec3,      // Position de départ du tir
     pub to: Vec3,        // Position de destination du tir
+    pub tick: u32,       // Tick serveur de l'état affiché par le tireur au moment du tir
+    pub seed: u32,       // Graine de la dispersion des plombs (`WeaponDefinition::pellet_directions`)
 }
 
 /// Composant Bevy représentant les statistiques d'un joueur
@@ -133,8 +350,11 @@ pub struct PlayerAttributes {
     pub velocity: Vec3,                             // Vecteur de vélocité
     pub owned_weapon: HashMap<Weapon, bool>,        // Armes possédées
     pub actual_weapon: Weapon,                      // Arme actuellement équipée
-    pub ammo: HashMap<Weapon, f32>,                 // Munitions par arme
-    pub entities: HashMap<Weapon, Entity>,          // Entités 3D des armes
+    pub magazine: HashMap<Weapon, f32>,             // Munitions dans le chargeur de chaque arme
+    pub ammo: HashMap<Weapon, f32>,                 // Munitions en réserve par arme
+    pub crouched: bool,                             // Joueur accroupi
+    #[serde(skip)]
+    pub entities: HashMap<Weapon, Entity>,          // Entités 3D des armes (propres à chaque client, jamais envoyées)
 }
 
 /// Implémentation des méthodes pour PlayerAttributes
@@ -158,8 +378,3 @@ impl PlayerAttributes {
         *self.owned_weapon.get(weapon).unwrap_or(&false)
     }
 }
-
-/// Ressource Bevy pour gérer les joueurs récemment respawnés
-/// Utilisée pour éviter les problèmes de collision ou de tir immédiat après respawn
-#[derive(Default, Resource)]
-pub struct RecentlyRespawned(pub HashMap<ClientId, Instant>);
@@ -0,0 +1,726 @@
+// Format de carte partagé entre le serveur et le client
+// Un fichier RON décrit une carte : métadonnées, légende des caractères et grille
+// Le serveur en tire ses points de spawn et son monde de collision, le client son niveau et sa mini-carte
+
+// Import des modules standard pour les collections, l'affichage et les fichiers
+use std::{
+    collections::{BTreeMap, VecDeque},
+    fmt,
+    path::{Path, PathBuf},
+};
+
+// Import des mathématiques Bevy pour le placement des cellules
+use bevy::math::Vec3;
+// Import de serde pour la lecture des fichiers et l'envoi des cartes
+use serde::{Deserialize, Serialize};
+
+/// Code de cellule représentant un sol libre
+pub const FLOOR_CELL: i32 = 0;
+/// Code de cellule représentant un mur
+pub const WALL_CELL: i32 = 1;
+/// Code de cellule représentant un point de spawn de joueur
+pub const SPAWN_CELL: i32 = 2;
+/// Code de cellule représentant un emplacement d'objet à ramasser
+pub const PICKUP_CELL: i32 = 3;
+
+/// Répertoire des cartes livrées avec le jeu
+pub const MAPS_DIR: &str = "maps";
+/// Carte jouée par défaut
+pub const DEFAULT_MAP: &str = "maze";
+/// Hauteur d'apparition des joueurs au-dessus du sol
+pub const SPAWN_HEIGHT: f32 = 2.0;
+
+/// Distance minimale (en cellules) entre deux points de spawn acceptée par la validation
+pub const MIN_SPAWN_SPACING: f32 = 2.0;
+
+/// Taille de cellule utilisée quand le fichier ne la précise pas
+const DEFAULT_CELL_SIZE: f32 = 4.0;
+
+/// Légende utilisée quand le fichier n'en fournit pas
+/// `#` = mur, `.` = sol, `S` = spawn de joueur, `P` = objet à ramasser
+fn default_legend() -> BTreeMap<char, i32> {
+    BTreeMap::from([
+        ('.', FLOOR_CELL),
+        ('#', WALL_CELL),
+        ('S', SPAWN_CELL),
+        ('P', PICKUP_CELL),
+    ])
+}
+
+/// Taille de cellule par défaut (pour serde)
+fn default_cell_size() -> f32 {
+    DEFAULT_CELL_SIZE
+}
+
+/// Contenu d'un fichier de carte tel qu'il est écrit sur le disque
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct MapFile {
+    name: String, // Nom affiché de la carte
+    #[serde(default = "default_cell_size")]
+    cell_size: f32, // Taille d'une cellule en unités du monde
+    #[serde(default)]
+    wall_height: Option<f32>, // Hauteur des murs (par défaut : taille d'une cellule)
+    #[serde(default = "default_legend")]
+    legend: BTreeMap<char, i32>, // Correspondance entre caractères et codes de cellule
+    grid: Vec<String>, // Lignes de la grille, une chaîne par rangée
+}
+
+/// Erreurs possibles lors de la lecture d'une carte
+#[derive(Debug)]
+pub enum MapError {
+    /// Fichier de carte illisible
+    Io {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    /// Fichier de carte mal formé
+    Parse(ron::error::SpannedError),
+    /// Caractère absent de la légende
+    UnknownSymbol {
+        row: usize,
+        column: usize,
+        symbol: char,
+    },
+    /// Grille sans aucune cellule
+    EmptyGrid,
+    /// Code de cellule sans caractère dans la légende par défaut (écriture d'une carte)
+    UnwritableCode(i32),
+    /// Taille de cellule nulle, négative ou invalide
+    InvalidCellSize(f32),
+    /// Hauteur de mur nulle, négative ou invalide
+    InvalidWallHeight(f32),
+}
+
+impl fmt::Display for MapError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MapError::Io { path, error } => {
+                write!(f, "lecture de la carte {} impossible : {error}", path.display())
+            }
+            MapError::Parse(error) => write!(f, "carte mal formée : {error}"),
+            MapError::UnknownSymbol {
+                row,
+                column,
+                symbol,
+            } => write!(
+                f,
+                "caractère '{symbol}' absent de la légende (ligne {row}, colonne {column})"
+            ),
+            MapError::EmptyGrid => write!(f, "la grille de la carte est vide"),
+            MapError::UnwritableCode(code) => {
+                write!(f, "code de cellule {code} absent de la légende par défaut")
+            }
+            MapError::InvalidCellSize(size) => {
+                write!(f, "taille de cellule invalide : {size}")
+            }
+            MapError::InvalidWallHeight(height) => {
+                write!(f, "hauteur de mur invalide : {height}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for MapError {}
+
+/// Problème détecté par la validation d'une carte
+/// Les positions sont numérotées à partir de 1 (ligne = rangée, colonne), comme dans les erreurs de lecture
+#[derive(Debug, Clone, PartialEq)]
+pub enum MapIssue {
+    /// Rangée de longueur différente de la première
+    RaggedRow {
+        row: usize,
+        length: usize,
+        expected: usize,
+    },
+    /// Cellule du bord qui n'est pas un mur : les joueurs peuvent sortir du niveau
+    OpenBorder { row: usize, column: usize },
+    /// Code de cellule inconnu du jeu
+    UnknownCode {
+        row: usize,
+        column: usize,
+        code: i32,
+    },
+    /// Zone praticable sans passage vers la zone principale
+    UnreachableRegion {
+        row: usize,
+        column: usize,
+        cells: usize,
+    },
+    /// Carte sans aucun point de spawn
+    NoSpawn,
+    /// Point de spawn hors de la zone principale
+    IsolatedSpawn { row: usize, column: usize },
+    /// Points de spawn trop proches l'un de l'autre
+    SpawnsTooClose {
+        first: (usize, usize),
+        second: (usize, usize),
+        distance: f32,
+    },
+}
+
+impl fmt::Display for MapIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MapIssue::RaggedRow {
+                row,
+                length,
+                expected,
+            } => write!(
+                f,
+                "ligne {row} : {length} cellules au lieu de {expected} (grille non rectangulaire)"
+            ),
+            MapIssue::OpenBorder { row, column } => {
+                write!(f, "ligne {row}, colonne {column} : bord ouvert (mur attendu)")
+            }
+            MapIssue::UnknownCode { row, column, code } => {
+                write!(f, "ligne {row}, colonne {column} : code de cellule inconnu {code}")
+            }
+            MapIssue::UnreachableRegion { row, column, cells } => write!(
+                f,
+                "ligne {row}, colonne {column} : zone inaccessible de {cells} cellule(s)"
+            ),
+            MapIssue::NoSpawn => write!(f, "aucun point de spawn"),
+            MapIssue::IsolatedSpawn { row, column } => write!(
+                f,
+                "ligne {row}, colonne {column} : point de spawn isolé de la zone principale"
+            ),
+            MapIssue::SpawnsTooClose {
+                first,
+                second,
+                distance,
+            } => write!(
+                f,
+                "points de spawn trop proches ({distance:.1} cellules) : ligne {}, colonne {} et ligne {}, colonne {}",
+                first.0, first.1, second.0, second.1
+            ),
+        }
+    }
+}
+
+/// Zone praticable d'une carte (cellules non murées reliées entre elles)
+struct Region {
+    start: (usize, usize), // Première cellule rencontrée (colonne, rangée)
+    cells: usize,          // Nombre de cellules de la zone
+}
+
+/// Carte chargée, prête à être utilisée par le serveur et le client
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct MapDefinition {
+    pub name: String,         // Nom affiché de la carte
+    pub cell_size: f32,       // Taille d'une cellule en unités du monde
+    pub wall_height: f32,     // Hauteur des murs
+    pub grid: Vec<Vec<i32>>,  // Codes des cellules, rangée par rangée
+}
+
+impl MapDefinition {
+    /// Lit une carte depuis le texte d'un fichier RON
+    ///
+    /// # Arguments
+    /// * `text` - Contenu du fichier
+    ///
+    /// # Returns
+    /// * `Result<MapDefinition, MapError>` - La carte, ou la raison de l'échec
+    pub fn parse(text: &str) -> Result<Self, MapError> {
+        let file: MapFile = ron::from_str(text).map_err(MapError::Parse)?;
+
+        if !(file.cell_size > 0.0 && file.cell_size.is_finite()) {
+            return Err(MapError::InvalidCellSize(file.cell_size));
+        }
+        let wall_height = file.wall_height.unwrap_or(file.cell_size);
+        if !(wall_height > 0.0 && wall_height.is_finite()) {
+            return Err(MapError::InvalidWallHeight(wall_height));
+        }
+
+        // Conversion des caractères en codes de cellule (lignes et colonnes numérotées à partir de 1)
+        let mut grid = Vec::with_capacity(file.grid.len());
+        for (row, line) in file.grid.iter().enumerate() {
+            let mut cells = Vec::with_capacity(line.len());
+            for (column, symbol) in line.chars().enumerate() {
+                let code = file.legend.get(&symbol).ok_or(MapError::UnknownSymbol {
+                    row: row + 1,
+                    column: column + 1,
+                    symbol,
+                })?;
+                cells.push(*code);
+            }
+            grid.push(cells);
+        }
+        if grid.iter().all(|row| row.is_empty()) {
+            return Err(MapError::EmptyGrid);
+        }
+
+        Ok(Self {
+            name: file.name,
+            cell_size: file.cell_size,
+            wall_height,
+            grid,
+        })
+    }
+
+    /// Charge une carte depuis un fichier
+    ///
+    /// # Arguments
+    /// * `path` - Chemin du fichier de carte
+    pub fn load(path: &Path) -> Result<Self, MapError> {
+        let text = std::fs::read_to_string(path).map_err(|error| MapError::Io {
+            path: path.to_path_buf(),
+            error,
+        })?;
+        Self::parse(&text)
+    }
+
+    /// Écrit la carte au format des fichiers de carte, avec la légende par défaut
+    ///
+    /// # Returns
+    /// * `Result<String, MapError>` - Texte RON, ou le premier code sans caractère dans la légende
+    pub fn to_ron(&self) -> Result<String, MapError> {
+        let legend = default_legend();
+        let symbols: BTreeMap<i32, char> =
+            legend.iter().map(|(&symbol, &code)| (code, symbol)).collect();
+        let grid = self
+            .grid
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|code| symbols.get(code).copied().ok_or(MapError::UnwritableCode(*code)))
+                    .collect::<Result<String, MapError>>()
+            })
+            .collect::<Result<Vec<String>, MapError>>()?;
+
+        let file = MapFile {
+            name: self.name.clone(),
+            cell_size: self.cell_size,
+            wall_height: Some(self.wall_height),
+            legend,
+            grid,
+        };
+        Ok(ron::ser::to_string_pretty(&file, ron::ser::PrettyConfig::default())
+            .expect("carte toujours sérialisable"))
+    }
+
+    /// Chemin du fichier d'une carte désignée par son nom ou son chemin
+    /// Un nom simple (`maze`) désigne `maps/maze.ron`, un chemin est utilisé tel quel
+    ///
+    /// # Arguments
+    /// * `map` - Nom ou chemin de la carte
+    pub fn resolve_path(map: &str) -> PathBuf {
+        let path = Path::new(map);
+        if path.extension().is_some() || path.components().count() > 1 {
+            path.to_path_buf()
+        } else {
+            Path::new(MAPS_DIR).join(format!("{map}.ron"))
+        }
+    }
+
+    /// Nombre de colonnes de la grille (rangée la plus longue)
+    pub fn width(&self) -> usize {
+        self.grid.iter().map(|row| row.len()).max().unwrap_or(0)
+    }
+
+    /// Nombre de rangées de la grille
+    pub fn height(&self) -> usize {
+        self.grid.len()
+    }
+
+    /// Code de la cellule à une position de la grille
+    ///
+    /// # Arguments
+    /// * `x` - Colonne
+    /// * `y` - Rangée
+    pub fn cell(&self, x: usize, y: usize) -> Option<i32> {
+        self.grid.get(y).and_then(|row| row.get(x)).copied()
+    }
+
+    /// Positions dans la grille de toutes les cellules d'un code donné
+    ///
+    /// # Arguments
+    /// * `code` - Code de cellule recherché
+    pub fn cells_with(&self, code: i32) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.grid.iter().enumerate().flat_map(move |(y, row)| {
+            row.iter()
+                .enumerate()
+                .filter(move |(_, &cell)| cell == code)
+                .map(move |(x, _)| (x, y))
+        })
+    }
+
+    /// Position dans le monde du centre d'une cellule, au niveau du sol
+    /// La grille est centrée sur l'origine : la colonne donne X, la rangée donne Z
+    ///
+    /// # Arguments
+    /// * `x` - Colonne
+    /// * `y` - Rangée
+    pub fn cell_position(&self, x: usize, y: usize) -> Vec3 {
+        Vec3::new(
+            x as f32 * self.cell_size - (self.width() as f32 * self.cell_size / 2.0),
+            0.0,
+            y as f32 * self.cell_size - (self.height() as f32 * self.cell_size / 2.0),
+        )
+    }
+
+    /// Empreinte du contenu de la carte (FNV-1a 64 bits de sa forme sérialisée)
+    /// Identique sur toutes les plateformes : sert de nom de cache et de vérification
+    /// de la carte reçue du serveur
+    pub fn content_hash(&self) -> u64 {
+        const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+        const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+        let bytes = bincode::serialize(self).expect("carte toujours sérialisable");
+        bytes.iter().fold(FNV_OFFSET, |hash, &byte| {
+            (hash ^ byte as u64).wrapping_mul(FNV_PRIME)
+        })
+    }
+
+    /// Points d'apparition des joueurs dans le monde
+    pub fn spawn_points(&self) -> Vec<Vec3> {
+        self.cells_with(SPAWN_CELL)
+            .map(|(x, y)| self.cell_position(x, y) + Vec3::Y * SPAWN_HEIGHT)
+            .collect()
+    }
+
+    /// Vérifie qu'une carte est jouable : grille rectangulaire fermée par des murs, codes connus,
+    /// une seule zone praticable et des points de spawn accessibles et espacés
+    ///
+    /// # Arguments
+    /// * `min_spawn_spacing` - Distance minimale entre deux spawns, en cellules
+    ///
+    /// # Returns
+    /// * `Vec<MapIssue>` - Problèmes détectés (vide si la carte est valide)
+    pub fn validate(&self, min_spawn_spacing: f32) -> Vec<MapIssue> {
+        let mut issues = Vec::new();
+
+        // Grille rectangulaire : toutes les rangées ont la longueur de la première
+        let expected = self.grid.first().map_or(0, |row| row.len());
+        for (y, row) in self.grid.iter().enumerate() {
+            if row.len() != expected {
+                issues.push(MapIssue::RaggedRow {
+                    row: y + 1,
+                    length: row.len(),
+                    expected,
+                });
+            }
+        }
+
+        // Codes connus et bords fermés (dernière cellule de chaque rangée pour une grille irrégulière)
+        let last_row = self.height().saturating_sub(1);
+        for (y, row) in self.grid.iter().enumerate() {
+            for (x, &cell) in row.iter().enumerate() {
+                if !matches!(cell, FLOOR_CELL | WALL_CELL | SPAWN_CELL | PICKUP_CELL) {
+                    issues.push(MapIssue::UnknownCode {
+                        row: y + 1,
+                        column: x + 1,
+                        code: cell,
+                    });
+                }
+                let on_border = y == 0 || y == last_row || x == 0 || x + 1 == row.len();
+                if on_border && cell != WALL_CELL {
+                    issues.push(MapIssue::OpenBorder {
+                        row: y + 1,
+                        column: x + 1,
+                    });
+                }
+            }
+        }
+
+        // Zones praticables : la plus grande est la zone principale, les autres sont inaccessibles
+        let (regions, region_of) = self.walkable_regions();
+        let main_region = (0..regions.len()).max_by_key(|&region| regions[region].cells);
+        for (region, found) in regions.iter().enumerate() {
+            if Some(region) != main_region {
+                let (x, y) = found.start;
+                issues.push(MapIssue::UnreachableRegion {
+                    row: y + 1,
+                    column: x + 1,
+                    cells: found.cells,
+                });
+            }
+        }
+
+        // Points de spawn : présents, dans la zone principale et suffisamment espacés
+        let spawns: Vec<(usize, usize)> = self.cells_with(SPAWN_CELL).collect();
+        if spawns.is_empty() {
+            issues.push(MapIssue::NoSpawn);
+        }
+        for &(x, y) in &spawns {
+            if region_of[y][x] != main_region {
+                issues.push(MapIssue::IsolatedSpawn {
+                    row: y + 1,
+                    column: x + 1,
+                });
+            }
+        }
+        for (index, &(x1, y1)) in spawns.iter().enumerate() {
+            for &(x2, y2) in &spawns[index + 1..] {
+                let distance = (x1 as f32 - x2 as f32).hypot(y1 as f32 - y2 as f32);
+                if distance < min_spawn_spacing {
+                    issues.push(MapIssue::SpawnsTooClose {
+                        first: (y1 + 1, x1 + 1),
+                        second: (y2 + 1, x2 + 1),
+                        distance,
+                    });
+                }
+            }
+        }
+
+        issues
+    }
+
+    /// Découpe la grille en zones praticables reliées par les côtés des cellules
+    ///
+    /// # Returns
+    /// * Les zones trouvées, dans l'ordre de lecture de la grille
+    /// * Pour chaque cellule : l'indice de sa zone (None pour un mur)
+    fn walkable_regions(&self) -> (Vec<Region>, Vec<Vec<Option<usize>>>) {
+        let mut region_of: Vec<Vec<Option<usize>>> =
+            self.grid.iter().map(|row| vec![None; row.len()]).collect();
+        let mut regions = Vec::new();
+
+        for (y, row) in self.grid.iter().enumerate() {
+            for (x, &cell) in row.iter().enumerate() {
+                if cell == WALL_CELL || region_of[y][x].is_some() {
+                    continue;
+                }
+
+                // Parcours en largeur depuis la première cellule non visitée
+                let region = regions.len();
+                let mut cells = 0;
+                let mut queue = VecDeque::from([(x, y)]);
+                region_of[y][x] = Some(region);
+                while let Some((cx, cy)) = queue.pop_front() {
+                    cells += 1;
+                    let neighbours = [
+                        (cx.wrapping_sub(1), cy),
+                        (cx + 1, cy),
+                        (cx, cy.wrapping_sub(1)),
+                        (cx, cy + 1),
+                    ];
+                    for (nx, ny) in neighbours {
+                        let walkable = self.cell(nx, ny).is_some_and(|cell| cell != WALL_CELL);
+                        if walkable && region_of[ny][nx].is_none() {
+                            region_of[ny][nx] = Some(region);
+                            queue.push_back((nx, ny));
+                        }
+                    }
+                }
+                regions.push(Region {
+                    start: (x, y),
+                    cells,
+                });
+            }
+        }
+
+        (regions, region_of)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Texte d'une carte de test utilisant la légende par défaut
+    fn map_text(rows: &[&str]) -> String {
+        let grid: Vec<String> = rows.iter().map(|row| format!("{row:?}")).collect();
+        format!("(name: \"Test\", grid: [{}])", grid.join(", "))
+    }
+
+    /// Construit une carte à partir de rangées écrites avec la légende par défaut
+    /// `?` représente un code inconnu du jeu
+    fn map(rows: &[&str]) -> MapDefinition {
+        let legend = default_legend();
+        MapDefinition {
+            name: "Test".to_string(),
+            cell_size: DEFAULT_CELL_SIZE,
+            wall_height: DEFAULT_CELL_SIZE,
+            grid: rows
+                .iter()
+                .map(|row| {
+                    row.chars()
+                        .map(|symbol| legend.get(&symbol).copied().unwrap_or(42))
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn unknown_symbol_is_rejected() {
+        let text = map_text(&["####", "#S.#", "#.X#", "####"]);
+        match MapDefinition::parse(&text) {
+            Err(MapError::UnknownSymbol {
+                row,
+                column,
+                symbol,
+            }) => assert_eq!((row, column, symbol), (3, 3, 'X')),
+            other => panic!("caractère inconnu accepté : {other:?}"),
+        }
+    }
+
+    #[test]
+    fn custom_legend_replaces_default() {
+        let text = "(name: \"Test\", legend: {'x': 1, 'o': 0}, grid: [\"xxx\", \"xox\", \"xxx\"])";
+        let map = MapDefinition::parse(text).expect("carte valide");
+        assert_eq!(map.cell(0, 0), Some(WALL_CELL));
+        assert_eq!(map.cell(1, 1), Some(FLOOR_CELL));
+
+        // Les caractères de la légende par défaut ne sont plus reconnus
+        let text = "(name: \"Test\", legend: {'x': 1}, grid: [\"x#x\"])";
+        assert!(matches!(
+            MapDefinition::parse(text),
+            Err(MapError::UnknownSymbol { symbol: '#', .. })
+        ));
+    }
+
+    #[test]
+    fn ragged_rows_are_kept_as_written() {
+        let map = MapDefinition::parse(&map_text(&["#####", "#S.#", "#.....#", "#####"]))
+            .expect("carte valide");
+        assert_eq!(map.height(), 4);
+        assert_eq!(map.width(), 7);
+        assert_eq!(
+            map.grid.iter().map(Vec::len).collect::<Vec<_>>(),
+            vec![5, 4, 7, 5]
+        );
+        // Les cellules manquantes d'une rangée courte n'existent pas
+        assert_eq!(map.cell(5, 1), None);
+        assert_eq!(map.cell(6, 2), Some(FLOOR_CELL));
+    }
+
+    #[test]
+    fn empty_grid_and_invalid_sizes_are_rejected() {
+        assert!(matches!(
+            MapDefinition::parse(&map_text(&["", ""])),
+            Err(MapError::EmptyGrid)
+        ));
+        assert!(matches!(
+            MapDefinition::parse("(name: \"Test\", cell_size: 0.0, grid: [\"#\"])"),
+            Err(MapError::InvalidCellSize(_))
+        ));
+        assert!(matches!(
+            MapDefinition::parse("(name: \"Test\", wall_height: Some(-1.0), grid: [\"#\"])"),
+            Err(MapError::InvalidWallHeight(_))
+        ));
+        assert!(matches!(
+            MapDefinition::parse("(name: \"Test\", grid: [\"#\"], size: 3)"),
+            Err(MapError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn shipped_maze_round_trips() {
+        let path =
+            Path::new(env!("CARGO_MANIFEST_DIR")).join(MapDefinition::resolve_path(DEFAULT_MAP));
+        let text = std::fs::read_to_string(&path).expect("maps/maze.ron lisible");
+        let file: MapFile = ron::from_str(&text).expect("maps/maze.ron bien formé");
+        let map = MapDefinition::parse(&text).expect("maps/maze.ron valide");
+
+        assert_eq!(map.name, "Maze");
+        assert_eq!((map.width(), map.height()), (31, 31));
+
+        // Chaque code se retraduit en le caractère écrit dans le fichier
+        let symbols: BTreeMap<i32, char> =
+            file.legend.iter().map(|(&c, &code)| (code, c)).collect();
+        let rows: Vec<String> = map
+            .grid
+            .iter()
+            .map(|row| row.iter().map(|code| symbols[code]).collect())
+            .collect();
+        assert_eq!(rows, file.grid);
+
+        // La carte envoyée sur le réseau se relit à l'identique
+        let encoded = ron::to_string(&map).expect("carte sérialisable");
+        assert_eq!(ron::from_str::<MapDefinition>(&encoded).unwrap(), map);
+    }
+
+    #[test]
+    fn valid_map_has_no_issue() {
+        let map = map(&["######", "#S..S#", "#.P..#", "######"]);
+        assert_eq!(map.validate(MIN_SPAWN_SPACING), vec![]);
+    }
+
+    #[test]
+    fn ragged_row_is_reported() {
+        let map = map(&["######", "#S..S#", "#....", "######"]);
+        assert!(map
+            .validate(MIN_SPAWN_SPACING)
+            .contains(&MapIssue::RaggedRow {
+                row: 3,
+                length: 5,
+                expected: 6,
+            }));
+    }
+
+    #[test]
+    fn open_border_is_reported() {
+        let map = map(&["######", "#S..S.", "#....#", "######"]);
+        assert_eq!(
+            map.validate(MIN_SPAWN_SPACING),
+            vec![MapIssue::OpenBorder { row: 2, column: 6 }]
+        );
+    }
+
+    #[test]
+    fn unknown_code_is_reported() {
+        let map = map(&["######", "#S..S#", "#.?..#", "######"]);
+        assert_eq!(
+            map.validate(MIN_SPAWN_SPACING),
+            vec![MapIssue::UnknownCode {
+                row: 3,
+                column: 3,
+                code: 42,
+            }]
+        );
+    }
+
+    #[test]
+    fn second_region_is_reported() {
+        let map = map(&["#######", "#S..#.#", "#..S#.#", "#######"]);
+        let (regions, _) = map.walkable_regions();
+        assert_eq!(regions.len(), 2);
+        assert_eq!(
+            map.validate(MIN_SPAWN_SPACING),
+            vec![MapIssue::UnreachableRegion {
+                row: 2,
+                column: 6,
+                cells: 2,
+            }]
+        );
+    }
+
+    #[test]
+    fn isolated_spawn_is_reported() {
+        let map = map(&["########", "#S...#S#", "#...S#.#", "########"]);
+        assert_eq!(
+            map.validate(MIN_SPAWN_SPACING),
+            vec![
+                MapIssue::UnreachableRegion {
+                    row: 2,
+                    column: 7,
+                    cells: 2,
+                },
+                MapIssue::IsolatedSpawn { row: 2, column: 7 },
+            ]
+        );
+    }
+
+    #[test]
+    fn close_spawns_are_reported() {
+        let map = map(&["######", "#SS..#", "#....#", "######"]);
+        assert_eq!(
+            map.validate(MIN_SPAWN_SPACING),
+            vec![MapIssue::SpawnsTooClose {
+                first: (2, 2),
+                second: (2, 3),
+                distance: 1.0,
+            }]
+        );
+    }
+
+    #[test]
+    fn shipped_maze_is_valid() {
+        let path =
+            Path::new(env!("CARGO_MANIFEST_DIR")).join(MapDefinition::resolve_path(DEFAULT_MAP));
+        let map = MapDefinition::load(&path).expect("maps/maze.ron lisible");
+        assert_eq!(map.validate(MIN_SPAWN_SPACING), vec![]);
+    }
+}
@@ -0,0 +1,387 @@
+// Générateur procédural de labyrinthes jouables
+// Un labyrinthe parfait (un seul chemin entre deux points) est creusé à partir d'une graine,
+// puis ouvert par des salles et des boucles ; les spawns et les objets sont répartis au plus loin
+// les uns des autres. Toutes les cellules praticables restent reliées entre elles.
+
+// Import des générateurs aléatoires reproductibles
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+// Import de serde pour la lecture des réglages dans la configuration du serveur
+use serde::{Deserialize, Serialize};
+
+// Import du format de carte produit
+use crate::map::{MapDefinition, FLOOR_CELL, PICKUP_CELL, SPAWN_CELL, WALL_CELL};
+
+/// Largeur et hauteur maximales d'une carte générée, en cellules
+/// Bornent la mémoire et le temps de génération (la carte est aussi envoyée aux clients)
+pub const MAX_GENERATED_SIZE: usize = 255;
+
+/// Réglages du générateur de labyrinthes
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct GeneratorSettings {
+    pub seed: Option<u64>,     // Graine du générateur (None = tirée au hasard)
+    pub name: Option<String>,  // Nom de la carte (par défaut : déduit de la graine)
+    pub width: usize,          // Largeur visée en cellules (arrondie à la taille compatible)
+    pub height: usize,         // Hauteur visée en cellules (arrondie à la taille compatible)
+    pub corridor_width: usize, // Largeur des couloirs en cellules
+    pub rooms: usize,          // Nombre de salles ouvertes dans le labyrinthe
+    pub loop_density: f32,     // Proportion des murs intérieurs restants percés (0 à 1)
+    pub spawns: usize,         // Nombre de points de spawn
+    pub pickups: usize,        // Nombre d'emplacements d'objets
+    pub cell_size: f32,        // Taille d'une cellule en unités du monde
+    pub wall_height: f32,      // Hauteur des murs
+}
+
+impl Default for GeneratorSettings {
+    fn default() -> Self {
+        Self {
+            seed: None,
+            name: None,
+            width: 31,
+            height: 31,
+            corridor_width: 2,
+            rooms: 4,
+            loop_density: 0.15,
+            spawns: 16,
+            pickups: 8,
+            cell_size: 4.0,
+            wall_height: 4.0,
+        }
+    }
+}
+
+impl GeneratorSettings {
+    /// Vérifie la cohérence des réglages
+    ///
+    /// # Returns
+    /// * `Vec<String>` - Description de chaque réglage invalide (vide si tout est correct)
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        if self.corridor_width == 0 {
+            errors.push("corridor_width doit être au moins 1".to_string());
+        }
+        // Au moins deux couloirs dans chaque direction, séparés et entourés de murs
+        let minimum = 2 * (self.corridor_width + 1) + 1;
+        if self.width < minimum || self.height < minimum {
+            errors.push(format!(
+                "width et height doivent être au moins {minimum} avec des couloirs de {}",
+                self.corridor_width
+            ));
+        }
+        if self.width > MAX_GENERATED_SIZE || self.height > MAX_GENERATED_SIZE {
+            errors.push(format!(
+                "width et height doivent être au plus {MAX_GENERATED_SIZE} (reçu {} x {})",
+                self.width, self.height
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.loop_density) {
+            errors.push(format!(
+                "loop_density doit être comprise entre 0 et 1 (reçu {})",
+                self.loop_density
+            ));
+        }
+        if self.spawns == 0 {
+            errors.push("spawns doit être au moins 1".to_string());
+        }
+        if !(self.cell_size > 0.0 && self.cell_size.is_finite()) {
+            errors.push(format!("cell_size invalide : {}", self.cell_size));
+        }
+        if !(self.wall_height > 0.0 && self.wall_height.is_finite()) {
+            errors.push(format!("wall_height invalide : {}", self.wall_height));
+        }
+        errors
+    }
+
+    /// Graine effectivement utilisée : celle des réglages, ou une graine tirée au hasard
+    pub fn resolve_seed(&self) -> u64 {
+        self.seed.unwrap_or_else(rand::random)
+    }
+}
+
+/// Labyrinthe en cours de creusement
+/// Les nœuds sont des carrés de `corridor` cellules séparés par des murs d'une cellule
+struct Maze {
+    grid: Vec<Vec<i32>>, // Codes des cellules
+    columns: usize,      // Nombre de nœuds par rangée
+    rows: usize,         // Nombre de rangées de nœuds
+    corridor: usize,     // Largeur des couloirs
+}
+
+impl Maze {
+    /// Crée une grille entièrement murée
+    fn new(columns: usize, rows: usize, corridor: usize) -> Self {
+        let width = columns * (corridor + 1) + 1;
+        let height = rows * (corridor + 1) + 1;
+        Self {
+            grid: vec![vec![WALL_CELL; width]; height],
+            columns,
+            rows,
+            corridor,
+        }
+    }
+
+    /// Première cellule (colonne ou rangée) d'un nœud
+    fn origin(&self, node: usize) -> usize {
+        1 + node * (self.corridor + 1)
+    }
+
+    /// Remplace par du sol un rectangle de cellules (bornes incluses)
+    fn carve(&mut self, (x0, y0): (usize, usize), (x1, y1): (usize, usize)) {
+        for row in &mut self.grid[y0..=y1] {
+            row[x0..=x1].fill(FLOOR_CELL);
+        }
+    }
+
+    /// Creuse un nœud
+    fn carve_node(&mut self, (cx, cy): (usize, usize)) {
+        let (x, y) = (self.origin(cx), self.origin(cy));
+        self.carve((x, y), (x + self.corridor - 1, y + self.corridor - 1));
+    }
+
+    /// Mur séparant deux nœuds voisins, sous forme de rectangle de cellules
+    fn wall_between(
+        &self,
+        (cx, cy): (usize, usize),
+        (nx, ny): (usize, usize),
+    ) -> [(usize, usize); 2] {
+        let (x, y) = (self.origin(cx.min(nx)), self.origin(cy.min(ny)));
+        if cx != nx {
+            let wall_x = x + self.corridor;
+            [(wall_x, y), (wall_x, y + self.corridor - 1)]
+        } else {
+            let wall_y = y + self.corridor;
+            [(x, wall_y), (x + self.corridor - 1, wall_y)]
+        }
+    }
+
+    /// Indique si le mur entre deux nœuds voisins est encore debout
+    fn is_wall_standing(&self, a: (usize, usize), b: (usize, usize)) -> bool {
+        let [(x, y), _] = self.wall_between(a, b);
+        self.grid[y][x] == WALL_CELL
+    }
+
+    /// Perce le mur entre deux nœuds voisins
+    fn open_wall(&mut self, a: (usize, usize), b: (usize, usize)) {
+        let [start, end] = self.wall_between(a, b);
+        self.carve(start, end);
+    }
+
+    /// Nœuds voisins d'un nœud, dans l'ordre gauche, droite, haut, bas
+    fn neighbours(&self, (cx, cy): (usize, usize)) -> Vec<(usize, usize)> {
+        let mut neighbours = Vec::with_capacity(4);
+        if cx > 0 {
+            neighbours.push((cx - 1, cy));
+        }
+        if cx + 1 < self.columns {
+            neighbours.push((cx + 1, cy));
+        }
+        if cy > 0 {
+            neighbours.push((cx, cy - 1));
+        }
+        if cy + 1 < self.rows {
+            neighbours.push((cx, cy + 1));
+        }
+        neighbours
+    }
+
+    /// Creuse un labyrinthe parfait par exploration en profondeur aléatoire
+    /// Chaque nœud est relié à tous les autres par un unique chemin
+    fn carve_perfect(&mut self, rng: &mut StdRng) {
+        let mut visited = vec![vec![false; self.columns]; self.rows];
+        let start = (rng.gen_range(0..self.columns), rng.gen_range(0..self.rows));
+        visited[start.1][start.0] = true;
+        self.carve_node(start);
+
+        let mut stack = vec![start];
+        while let Some(&current) = stack.last() {
+            let unvisited: Vec<_> = self
+                .neighbours(current)
+                .into_iter()
+                .filter(|&(nx, ny)| !visited[ny][nx])
+                .collect();
+            match unvisited.choose(rng) {
+                Some(&next) => {
+                    visited[next.1][next.0] = true;
+                    self.carve_node(next);
+                    self.open_wall(current, next);
+                    stack.push(next);
+                }
+                None => {
+                    stack.pop();
+                }
+            }
+        }
+    }
+
+    /// Ouvre des salles : rectangles de 2 à 3 nœuds de côté entièrement creusés
+    fn carve_rooms(&mut self, count: usize, rng: &mut StdRng) {
+        for _ in 0..count {
+            let room_width = rng.gen_range(2..=3).min(self.columns);
+            let room_height = rng.gen_range(2..=3).min(self.rows);
+            let cx = rng.gen_range(0..=self.columns - room_width);
+            let cy = rng.gen_range(0..=self.rows - room_height);
+            let start = (self.origin(cx), self.origin(cy));
+            let end = (
+                self.origin(cx + room_width - 1) + self.corridor - 1,
+                self.origin(cy + room_height - 1) + self.corridor - 1,
+            );
+            self.carve(start, end);
+        }
+    }
+
+    /// Perce une partie des murs intérieurs restants pour créer des boucles
+    fn carve_loops(&mut self, density: f32, rng: &mut StdRng) {
+        for cy in 0..self.rows {
+            for cx in 0..self.columns {
+                for next in [(cx + 1, cy), (cx, cy + 1)] {
+                    if next.0 >= self.columns || next.1 >= self.rows {
+                        continue;
+                    }
+                    if self.is_wall_standing((cx, cy), next) && rng.gen::<f32>() < density {
+                        self.open_wall((cx, cy), next);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Place des marqueurs (spawns ou objets) sur le sol, chacun le plus loin possible
+    /// des marqueurs déjà placés
+    fn scatter(&mut self, code: i32, count: usize, rng: &mut StdRng) {
+        let mut candidates: Vec<(usize, usize)> = Vec::new();
+        let mut placed: Vec<(usize, usize)> = Vec::new();
+        for (y, row) in self.grid.iter().enumerate() {
+            for (x, &cell) in row.iter().enumerate() {
+                match cell {
+                    FLOOR_CELL => candidates.push((x, y)),
+                    SPAWN_CELL | PICKUP_CELL => placed.push((x, y)),
+                    _ => {}
+                }
+            }
+        }
+        // Le mélange départage les cellules à égale distance
+        candidates.shuffle(rng);
+
+        for _ in 0..count {
+            let farthest = candidates
+                .iter()
+                .enumerate()
+                .max_by_key(|&(_, &(x, y))| {
+                    placed
+                        .iter()
+                        .map(|&(px, py)| x.abs_diff(px).pow(2) + y.abs_diff(py).pow(2))
+                        .min()
+                        .unwrap_or(usize::MAX)
+                })
+                .map(|(index, _)| index);
+            let Some(index) = farthest else {
+                break;
+            };
+            let (x, y) = candidates.swap_remove(index);
+            self.grid[y][x] = code;
+            placed.push((x, y));
+        }
+    }
+}
+
+/// Génère une carte jouable à partir des réglages
+///
+/// # Arguments
+/// * `settings` - Réglages du générateur (supposés valides, voir `GeneratorSettings::validate`)
+/// * `seed` - Graine du générateur : une même graine donne toujours la même carte
+///
+/// # Returns
+/// * `MapDefinition` - Carte fermée par des murs dont toutes les cellules praticables sont reliées
+pub fn generate(settings: &GeneratorSettings, seed: u64) -> MapDefinition {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let corridor = settings.corridor_width.max(1);
+    let columns = ((settings.width.saturating_sub(1)) / (corridor + 1)).max(2);
+    let rows = ((settings.height.saturating_sub(1)) / (corridor + 1)).max(2);
+
+    let mut maze = Maze::new(columns, rows, corridor);
+    maze.carve_perfect(&mut rng);
+    // Salles et boucles ne font que retirer des murs intérieurs : la connexité est conservée
+    maze.carve_rooms(settings.rooms, &mut rng);
+    maze.carve_loops(settings.loop_density, &mut rng);
+    maze.scatter(SPAWN_CELL, settings.spawns, &mut rng);
+    maze.scatter(PICKUP_CELL, settings.pickups, &mut rng);
+
+    MapDefinition {
+        name: settings
+            .name
+            .clone()
+            .unwrap_or_else(|| format!("Labyrinthe {seed}")),
+        cell_size: settings.cell_size,
+        wall_height: settings.wall_height,
+        grid: maze.grid,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map::{MapIssue, MIN_SPAWN_SPACING};
+
+    /// Problèmes qu'une carte générée ne doit jamais présenter
+    fn connectivity_issues(map: &MapDefinition) -> Vec<MapIssue> {
+        map.validate(MIN_SPAWN_SPACING)
+            .into_iter()
+            .filter(|issue| {
+                matches!(
+                    issue,
+                    MapIssue::UnreachableRegion { .. }
+                        | MapIssue::OpenBorder { .. }
+                        | MapIssue::IsolatedSpawn { .. }
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn generated_maps_are_connected_and_closed() {
+        for corridor_width in [1, 2, 3] {
+            for loop_density in [0.0, 0.15, 1.0] {
+                for (width, height) in [(2 * (corridor_width + 1) + 1, 31), (31, 31), (57, 23)] {
+                    let settings = GeneratorSettings {
+                        width,
+                        height,
+                        corridor_width,
+                        loop_density,
+                        ..GeneratorSettings::default()
+                    };
+                    assert!(settings.validate().is_empty(), "{settings:?}");
+                    for seed in 0..20 {
+                        let map = generate(&settings, seed);
+                        let issues = connectivity_issues(&map);
+                        assert!(
+                            issues.is_empty(),
+                            "graine {seed}, {settings:?} : {issues:?}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn generation_is_reproducible() {
+        let settings = GeneratorSettings::default();
+        assert_eq!(generate(&settings, 42).grid, generate(&settings, 42).grid);
+    }
+
+    #[test]
+    fn oversized_maps_are_rejected() {
+        let settings = GeneratorSettings {
+            width: 1_000_000,
+            ..GeneratorSettings::default()
+        };
+        assert!(!settings.validate().is_empty());
+
+        let settings = GeneratorSettings {
+            width: MAX_GENERATED_SIZE,
+            height: MAX_GENERATED_SIZE,
+            ..GeneratorSettings::default()
+        };
+        assert!(settings.validate().is_empty());
+    }
+}
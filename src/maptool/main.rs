@@ -0,0 +1,232 @@
+// src/maptool/main
+
+// Outil en ligne de commande pour les fichiers de carte
+// `maptool check` vérifie qu'une ou plusieurs cartes sont jouables, avec un code de sortie
+// non nul en cas de problème pour être utilisé en intégration continue
+// `maptool generate` écrit un labyrinthe généré à partir d'une graine
+
+// Import des modules standard pour les arguments, les fichiers et le code de sortie
+use std::{path::Path, process::ExitCode, str::FromStr};
+
+// Import du format de carte et du générateur partagés
+use multiplayer_demo::{
+    map::{MapDefinition, MIN_SPAWN_SPACING},
+    mapgen::{generate, GeneratorSettings},
+};
+
+/// Texte d'aide de l'outil
+const USAGE: &str = "\
+Usage : maptool <COMMANDE> [OPTIONS]
+
+Commandes :
+  check <NOM|FICHIER>...      Vérifie des cartes (nom d'une carte de maps/ ou chemin d'un fichier)
+  generate [OPTIONS]          Génère un labyrinthe (sur la sortie standard sans --output)
+
+Options de check :
+  --min-spawn-spacing <N>     Distance minimale entre deux spawns, en cellules (défaut : 2)
+
+Options de generate :
+  --seed <GRAINE>             Graine du générateur (défaut : tirée au hasard)
+  --name <NOM>                Nom de la carte (défaut : Labyrinthe <GRAINE>)
+  --width <N>                 Largeur visée en cellules (défaut : 31, au plus 255)
+  --height <N>                Hauteur visée en cellules (défaut : 31, au plus 255)
+  --corridor-width <N>        Largeur des couloirs en cellules (défaut : 2)
+  --rooms <N>                 Nombre de salles (défaut : 4)
+  --loop-density <0..1>       Proportion des murs intérieurs percés (défaut : 0.15)
+  --spawns <N>                Nombre de points de spawn (défaut : 16)
+  --pickups <N>               Nombre d'emplacements d'objets (défaut : 8)
+  --cell-size <N>             Taille d'une cellule en unités du monde (défaut : 4)
+  --wall-height <N>           Hauteur des murs (défaut : 4)
+  --output <FICHIER>          Fichier de carte écrit (ex: maps/arene.ron)
+
+  --help                      Affiche cette aide";
+
+/// Point d'entrée de l'outil
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "--help") {
+        println!("{USAGE}");
+        return ExitCode::SUCCESS;
+    }
+
+    match args.split_first() {
+        Some((command, rest)) if command == "check" => check(rest),
+        Some((command, rest)) if command == "generate" => generate_map(rest),
+        Some((command, _)) => usage_error(&format!("commande inconnue : {command}")),
+        None => usage_error("commande manquante"),
+    }
+}
+
+/// Vérifie les cartes indiquées et affiche leurs problèmes
+///
+/// # Arguments
+/// * `args` - Arguments de la commande (cartes et options)
+///
+/// # Returns
+/// * `ExitCode` - Succès si toutes les cartes sont lisibles et valides
+fn check(args: &[String]) -> ExitCode {
+    let mut min_spawn_spacing = MIN_SPAWN_SPACING;
+    let mut maps = Vec::new();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--min-spawn-spacing" => {
+                let Some(value) = args.next() else {
+                    return usage_error("valeur manquante pour --min-spawn-spacing");
+                };
+                match value.parse::<f32>() {
+                    Ok(spacing) if spacing >= 0.0 => min_spawn_spacing = spacing,
+                    _ => {
+                        return usage_error(&format!(
+                            "valeur invalide pour --min-spawn-spacing : {value}"
+                        ))
+                    }
+                }
+            }
+            option if option.starts_with("--") => {
+                return usage_error(&format!("option inconnue : {option}"))
+            }
+            map => maps.push(map),
+        }
+    }
+    if maps.is_empty() {
+        return usage_error("aucune carte à vérifier");
+    }
+
+    let mut failed = false;
+    for map in maps {
+        let path = MapDefinition::resolve_path(map);
+        let definition = match MapDefinition::load(&path) {
+            Ok(definition) => definition,
+            Err(err) => {
+                eprintln!("❌ {} : {err}", path.display());
+                failed = true;
+                continue;
+            }
+        };
+
+        let issues = definition.validate(min_spawn_spacing);
+        if issues.is_empty() {
+            println!("✅ {} ({})", path.display(), definition.name);
+        } else {
+            eprintln!(
+                "❌ {} ({}) : {} problème(s)",
+                path.display(),
+                definition.name,
+                issues.len()
+            );
+            for issue in issues {
+                eprintln!("  - {issue}");
+            }
+            failed = true;
+        }
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Génère un labyrinthe et l'écrit dans un fichier ou sur la sortie standard
+///
+/// # Arguments
+/// * `args` - Options de la commande
+///
+/// # Returns
+/// * `ExitCode` - Succès si la carte a été écrite
+fn generate_map(args: &[String]) -> ExitCode {
+    let mut settings = GeneratorSettings::default();
+    let mut output = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let Some(value) = args.next() else {
+            return usage_error(&format!("valeur manquante pour {arg}"));
+        };
+        let applied = match arg.as_str() {
+            "--seed" => parse(arg, value).map(|seed| settings.seed = Some(seed)),
+            "--name" => {
+                settings.name = Some(value.clone());
+                Ok(())
+            }
+            "--width" => parse(arg, value).map(|width| settings.width = width),
+            "--height" => parse(arg, value).map(|height| settings.height = height),
+            "--corridor-width" => parse(arg, value).map(|width| settings.corridor_width = width),
+            "--rooms" => parse(arg, value).map(|rooms| settings.rooms = rooms),
+            "--loop-density" => parse(arg, value).map(|density| settings.loop_density = density),
+            "--spawns" => parse(arg, value).map(|spawns| settings.spawns = spawns),
+            "--pickups" => parse(arg, value).map(|pickups| settings.pickups = pickups),
+            "--cell-size" => parse(arg, value).map(|size| settings.cell_size = size),
+            "--wall-height" => parse(arg, value).map(|height| settings.wall_height = height),
+            "--output" => {
+                output = Some(value.clone());
+                Ok(())
+            }
+            option => Err(format!("option inconnue : {option}")),
+        };
+        if let Err(message) = applied {
+            return usage_error(&message);
+        }
+    }
+
+    let errors = settings.validate();
+    if !errors.is_empty() {
+        for error in errors {
+            eprintln!("❌ {error}");
+        }
+        return ExitCode::from(2);
+    }
+
+    let seed = settings.resolve_seed();
+    let map = generate(&settings, seed);
+    // Le générateur garantit une carte jouable : un problème ici serait un bug du générateur
+    for issue in map.validate(MIN_SPAWN_SPACING) {
+        eprintln!("⚠️ {issue}");
+    }
+    let text = match map.to_ron() {
+        Ok(text) => format!(
+            "// Labyrinthe généré par `maptool generate` (graine {seed}, {} x {})\n{text}\n",
+            map.width(),
+            map.height()
+        ),
+        Err(err) => {
+            eprintln!("❌ {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match output {
+        Some(path) => {
+            if let Err(err) = std::fs::write(Path::new(&path), text) {
+                eprintln!("❌ Écriture de {path} impossible : {err}");
+                return ExitCode::FAILURE;
+            }
+            println!("✅ {path} : {} (graine {seed})", map.name);
+        }
+        None => print!("{text}"),
+    }
+    ExitCode::SUCCESS
+}
+
+/// Convertit la valeur d'une option
+///
+/// # Arguments
+/// * `name` - Nom de l'option (pour le message d'erreur)
+/// * `value` - Valeur à convertir
+fn parse<T: FromStr>(name: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("valeur invalide pour {name} : {value}"))
+}
+
+/// Affiche une erreur d'utilisation suivie de l'aide
+///
+/// # Arguments
+/// * `message` - Description de l'erreur
+fn usage_error(message: &str) -> ExitCode {
+    eprintln!("❌ {message}\n\n{USAGE}");
+    ExitCode::from(2)
+}
@@ -0,0 +1,199 @@
+// Simulation déterministe du déplacement d'un joueur
+// Exécutée à l'identique par le client (prédiction) et par le serveur (autorité)
+
+// Import des modules Bevy pour les mathématiques
+use bevy::math::{Vec2, Vec3};
+// Import de serde pour la sérialisation/désérialisation des entrées et des états
+use serde::{Deserialize, Serialize};
+
+// Import du monde de collision partagé
+use crate::collision::{
+    Aabb, CollisionWorld, FLOOR_HEIGHT, PLAYER_HULL_HALF_HEIGHT, PLAYER_HULL_RADIUS,
+};
+
+/// Durée d'un pas de simulation (60 pas par seconde, identique au tick serveur)
+pub const MOVEMENT_TIMESTEP: f32 = 1.0 / 60.0;
+/// Vitesse de marche horizontale
+pub const WALK_SPEED: f32 = 8.0;
+/// Multiplicateur de vitesse en course
+pub const RUN_MULTIPLIER: f32 = 2.0;
+/// Multiplicateur de vitesse accroupi
+pub const CROUCH_MULTIPLIER: f32 = 0.6;
+/// Vitesse verticale donnée par un saut
+pub const JUMP_VELOCITY: f32 = 12.0;
+/// Accélération de la gravité
+pub const GRAVITY: f32 = 40.0;
+/// Hauteur des yeux debout par rapport au centre du joueur
+pub const STANDING_EYE_HEIGHT: f32 = 1.3;
+/// Hauteur des yeux accroupi par rapport au centre du joueur
+pub const CROUCH_EYE_HEIGHT: f32 = 0.3;
+/// Amortissement de la poussée horizontale des explosions (par seconde, décroissance exponentielle)
+pub const KNOCKBACK_DAMPING: f32 = 4.0;
+/// Nombre maximal d'entrées transportées par une commande (redondance contre les pertes)
+pub const MAX_INPUTS_PER_COMMAND: usize = 8;
+/// Marge laissée entre le joueur et un obstacle après une collision
+const SKIN: f32 = 0.001;
+
+/// Entrées du joueur pour un pas de simulation
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+pub struct MovementInput {
+    pub tick: u32,       // Numéro de séquence de l'entrée côté client
+    pub movement: Vec2,  // Intention de déplacement (x = avant/arrière, y = gauche/droite)
+    pub jump: bool,      // Saut demandé
+    pub crouch: bool,    // Joueur accroupi
+    pub run: bool,       // Course demandée
+    pub yaw: f32,        // Orientation horizontale de la vue (radians)
+    pub pitch: f32,      // Orientation verticale de la vue (radians)
+}
+
+/// État physique d'un joueur à l'issue d'un pas de simulation
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+pub struct MovementState {
+    pub position: Vec3,  // Centre du volume du joueur
+    pub velocity: Vec3,  // Vélocité courante
+    pub grounded: bool,  // Joueur au contact du sol
+    pub knockback: Vec3, // Poussée horizontale des explosions, ajoutée au déplacement et amortie
+    pub crouched: bool,  // Joueur accroupi (caméra et zones du corps abaissées)
+}
+
+impl MovementState {
+    /// Crée un état immobile à la position donnée
+    ///
+    /// # Arguments
+    /// * `position` - Position initiale du joueur
+    pub fn at(position: Vec3) -> Self {
+        Self {
+            position,
+            velocity: Vec3::ZERO,
+            grounded: false,
+            knockback: Vec3::ZERO,
+            crouched: false,
+        }
+    }
+
+    /// Applique une impulsion (souffle d'une explosion)
+    /// La composante verticale s'ajoute à la vélocité, la composante horizontale à la poussée
+    ///
+    /// # Arguments
+    /// * `impulse` - Changement de vitesse appliqué au joueur
+    pub fn apply_impulse(&mut self, impulse: Vec3) {
+        self.velocity.y += impulse.y;
+        if impulse.y > 0.0 {
+            // Le joueur décolle : le saut du pas suivant ne remet pas sa vitesse verticale à zéro
+            self.grounded = false;
+        }
+        self.knockback += impulse.with_y(0.0);
+    }
+}
+
+/// Demi-dimensions de la boîte utilisée pour les collisions de déplacement
+pub fn player_half_extents() -> Vec3 {
+    Vec3::new(PLAYER_HULL_RADIUS, PLAYER_HULL_HALF_HEIGHT, PLAYER_HULL_RADIUS)
+}
+
+/// Avance la simulation d'un joueur d'un pas
+///
+/// # Arguments
+/// * `state` - État du joueur avant le pas
+/// * `input` - Entrées appliquées pendant le pas
+/// * `world` - Monde de collision du niveau
+///
+/// # Returns
+/// * `MovementState` - État du joueur après le pas
+pub fn step(state: &MovementState, input: &MovementInput, world: &CollisionWorld) -> MovementState {
+    let dt = MOVEMENT_TIMESTEP;
+    let mut velocity = state.velocity;
+
+    // Gestion du saut si le joueur est au sol
+    if state.grounded {
+        velocity.y = 0.0;
+        if input.jump {
+            velocity.y = JUMP_VELOCITY;
+        }
+    }
+
+    // Calcule les vecteurs avant/droite en fonction du yaw
+    let forward = Vec2::new(input.yaw.sin(), input.yaw.cos());
+    let right = Vec2::new(forward.y, -forward.x);
+
+    // Mouvement horizontal : sans entrée le joueur s'arrête net (pas de glisse)
+    let mut speed = WALK_SPEED;
+    if input.run {
+        speed *= RUN_MULTIPLIER;
+    }
+    if input.crouch {
+        speed *= CROUCH_MULTIPLIER;
+    }
+    if let Some(dir) = (forward * input.movement.x + right * input.movement.y).try_normalize() {
+        velocity.x = dir.x * speed;
+        velocity.z = dir.y * speed;
+    } else {
+        velocity.x = 0.0;
+        velocity.z = 0.0;
+    }
+
+    // Gravité (accélération vers le bas)
+    velocity.y -= GRAVITY * dt;
+
+    // Déplacement axe par axe avec résolution des collisions (poussée des explosions comprise)
+    let half = player_half_extents();
+    let mut position = state.position;
+    let mut grounded = false;
+    let mut knockback = state.knockback;
+    let delta = (velocity + knockback) * dt;
+
+    for axis in [0, 2, 1] {
+        if delta[axis] == 0.0 {
+            continue;
+        }
+        position[axis] += delta[axis];
+
+        for wall in world.walls() {
+            let body = Aabb::from_center_half_extents(position, half);
+            if !overlaps(&body, wall) {
+                continue;
+            }
+            if delta[axis] > 0.0 {
+                position[axis] = wall.min[axis] - half[axis] - SKIN;
+            } else {
+                position[axis] = wall.max[axis] + half[axis] + SKIN;
+                if axis == 1 {
+                    grounded = true;
+                }
+            }
+            velocity[axis] = 0.0;
+            knockback[axis] = 0.0;
+        }
+    }
+
+    // Amortissement de la poussée
+    knockback *= (-KNOCKBACK_DAMPING * dt).exp();
+    if knockback.length_squared() < 1e-4 {
+        knockback = Vec3::ZERO;
+    }
+
+    // Le sol arrête la chute
+    if position.y - half.y < FLOOR_HEIGHT {
+        position.y = FLOOR_HEIGHT + half.y;
+        velocity.y = 0.0;
+        grounded = true;
+    }
+
+    MovementState {
+        position,
+        velocity,
+        grounded,
+        knockback,
+        crouched: input.crouch,
+    }
+}
+
+/// Vérifie si deux boîtes se chevauchent strictement (un simple contact ne compte pas)
+fn overlaps(a: &Aabb, b: &Aabb) -> bool {
+    a.min.x < b.max.x
+        && a.max.x > b.min.x
+        && a.min.y < b.max.y
+        && a.max.y > b.min.y
+        && a.min.z < b.max.z
+        && a.max.z > b.min.z
+}
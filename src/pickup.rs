@@ -0,0 +1,64 @@
+// Objets à ramasser posés sur les cellules d'objets de la carte
+// Le serveur décide seul de leur apparition et de leur ramassage ; les clients ne font que les afficher
+
+// Import de l'affichage
+use std::fmt;
+
+// Import des modules Bevy pour les mathématiques
+use bevy::math::Vec3;
+// Import de serde pour la configuration du serveur et les messages réseau
+use serde::{Deserialize, Serialize};
+
+// Import des structures de données partagées
+use crate::{
+    map::{MapDefinition, PICKUP_CELL},
+    Weapon,
+};
+
+/// Hauteur du centre d'un objet au-dessus du sol
+pub const PICKUP_HEIGHT: f32 = 1.0;
+/// Distance horizontale en deçà de laquelle un joueur ramasse un objet
+pub const PICKUP_RADIUS: f32 = 1.2;
+/// Écart vertical maximal entre le centre d'un joueur et celui d'un objet ramassé
+pub const PICKUP_REACH_HEIGHT: f32 = 2.5;
+
+/// Nature d'un objet à ramasser
+#[derive(Eq, Hash, PartialEq, Copy, Clone, Debug, Serialize, Deserialize)]
+pub enum PickupKind {
+    Health,         // Points de vie
+    Armor,          // Points d'armure
+    Ammo(Weapon),   // Munitions d'une arme
+    Weapon(Weapon), // Arme (avec ses munitions)
+}
+
+impl fmt::Display for PickupKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PickupKind::Health => write!(f, "Health"),
+            PickupKind::Armor => write!(f, "Armor"),
+            PickupKind::Ammo(weapon) => write!(f, "Ammo({weapon})"),
+            PickupKind::Weapon(weapon) => write!(f, "Weapon({weapon})"),
+        }
+    }
+}
+
+/// Positions dans le monde des objets d'une carte, dans l'ordre de lecture de la grille
+/// L'indice d'une position sert d'identifiant à l'objet qui y est posé
+///
+/// # Arguments
+/// * `map` - Carte jouée
+pub fn pickup_points(map: &MapDefinition) -> Vec<Vec3> {
+    map.cells_with(PICKUP_CELL)
+        .map(|(x, y)| map.cell_position(x, y) + Vec3::Y * PICKUP_HEIGHT)
+        .collect()
+}
+
+/// Indique si un joueur est assez proche d'un objet pour le ramasser
+///
+/// # Arguments
+/// * `player` - Position du joueur
+/// * `pickup` - Position du centre de l'objet
+pub fn is_within_reach(player: Vec3, pickup: Vec3) -> bool {
+    let horizontal = (player - pickup).with_y(0.0).length();
+    horizontal <= PICKUP_RADIUS && (player.y - pickup.y).abs() <= PICKUP_REACH_HEIGHT
+}
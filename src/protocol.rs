@@ -0,0 +1,176 @@
+// Identification du protocole réseau et données transmises à la connexion
+// Permet au serveur de refuser avec une raison lisible un client d'une autre version
+
+// Import de l'affichage des erreurs
+use std::fmt;
+
+// Import de la taille du bloc de données utilisateur de netcode
+use renet::transport::NETCODE_USER_DATA_BYTES;
+
+/// Identifiant du protocole netcode
+/// Volontairement identique d'une version à l'autre : un identifiant différent ferait échouer
+/// la connexion sans explication, alors que la version transmise dans `ConnectData`
+/// permet au serveur d'expliquer son refus
+pub const PROTOCOL_ID: u64 = 0;
+
+/// Version du protocole de jeu, à incrémenter à chaque modification des messages échangés
+pub const PROTOCOL_VERSION: u32 = 13;
+
+/// Longueur maximale (en octets UTF-8) d'un nom d'utilisateur
+pub const MAX_USERNAME_BYTES: usize = 32;
+
+/// Erreurs possibles lors de la lecture des données de connexion
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConnectDataError {
+    UsernameEmpty,            // Nom d'utilisateur vide
+    UsernameTooLong(usize),   // Nom d'utilisateur trop long (longueur en octets)
+    UsernameNotUtf8,          // Nom d'utilisateur illisible
+}
+
+impl fmt::Display for ConnectDataError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConnectDataError::UsernameEmpty => write!(f, "le nom d'utilisateur est vide"),
+            ConnectDataError::UsernameTooLong(len) => write!(
+                f,
+                "le nom d'utilisateur fait {len} octets (maximum {MAX_USERNAME_BYTES})"
+            ),
+            ConnectDataError::UsernameNotUtf8 => {
+                write!(f, "le nom d'utilisateur n'est pas de l'UTF-8 valide")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConnectDataError {}
+
+/// Données envoyées par le client dans le bloc `user_data` de netcode à la connexion
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConnectData {
+    pub version: u32,     // Version du protocole du client
+    pub username: String, // Nom d'utilisateur choisi
+}
+
+impl ConnectData {
+    /// Crée les données de connexion de la version courante
+    ///
+    /// # Arguments
+    /// * `username` - Nom d'utilisateur du joueur
+    pub fn new(username: String) -> Self {
+        Self {
+            version: PROTOCOL_VERSION,
+            username,
+        }
+    }
+
+    /// Encode les données dans le bloc `user_data`
+    /// Format : version u32, longueur du nom u8, octets UTF-8 du nom, zéros ensuite
+    ///
+    /// # Returns
+    /// * `Result<[u8; NETCODE_USER_DATA_BYTES], ConnectDataError>` - Le bloc prêt à être envoyé
+    pub fn to_user_data(&self) -> Result<[u8; NETCODE_USER_DATA_BYTES], ConnectDataError> {
+        let username = validate_username(&self.username)?;
+
+        let mut user_data = [0u8; NETCODE_USER_DATA_BYTES];
+        user_data[0..4].copy_from_slice(&self.version.to_le_bytes());
+        user_data[4] = username.len() as u8;
+        user_data[5..5 + username.len()].copy_from_slice(username.as_bytes());
+        Ok(user_data)
+    }
+
+    /// Décode les données d'un bloc `user_data`
+    /// La version est toujours lue en premier pour pouvoir refuser un client d'une autre version
+    /// même si la suite du bloc a changé de format
+    ///
+    /// # Arguments
+    /// * `user_data` - Bloc reçu à la connexion
+    ///
+    /// # Returns
+    /// * `(u32, Result<String, ConnectDataError>)` - Version annoncée et nom d'utilisateur
+    pub fn from_user_data(
+        user_data: &[u8; NETCODE_USER_DATA_BYTES],
+    ) -> (u32, Result<String, ConnectDataError>) {
+        let version = u32::from_le_bytes([user_data[0], user_data[1], user_data[2], user_data[3]]);
+        let len = user_data[4] as usize;
+        let username = if len > MAX_USERNAME_BYTES {
+            Err(ConnectDataError::UsernameTooLong(len))
+        } else {
+            std::str::from_utf8(&user_data[5..5 + len])
+                .map_err(|_| ConnectDataError::UsernameNotUtf8)
+                .and_then(validate_username)
+                .map(str::to_string)
+        };
+        (version, username)
+    }
+}
+
+/// Vérifie qu'un nom d'utilisateur est utilisable
+///
+/// # Arguments
+/// * `username` - Nom à vérifier
+///
+/// # Returns
+/// * `Result<&str, ConnectDataError>` - Le nom sans espaces superflus
+pub fn validate_username(username: &str) -> Result<&str, ConnectDataError> {
+    let username = username.trim();
+    if username.is_empty() {
+        return Err(ConnectDataError::UsernameEmpty);
+    }
+    if username.len() > MAX_USERNAME_BYTES {
+        return Err(ConnectDataError::UsernameTooLong(username.len()));
+    }
+    Ok(username)
+}
+
+/// Port UDP par défaut du serveur de jeu
+pub const DEFAULT_SERVER_PORT: u16 = 5000;
+
+/// Port par défaut de l'émetteur de jetons de connexion (mode sécurisé)
+pub const DEFAULT_TOKEN_PORT: u16 = 5001;
+
+/// Chemin HTTP de la demande de jeton de connexion
+pub const TOKEN_PATH: &str = "/token";
+
+/// Encode une valeur pour une chaîne de requête HTTP (encodage pourcent)
+///
+/// # Arguments
+/// * `value` - Valeur à encoder
+///
+/// # Returns
+/// * `String` - Valeur utilisable dans une URL
+pub fn encode_query_value(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+/// Décode une valeur d'une chaîne de requête HTTP
+///
+/// # Arguments
+/// * `value` - Valeur encodée
+///
+/// # Returns
+/// * `Option<String>` - Valeur décodée, ou None si l'encodage est invalide
+pub fn decode_query_value(value: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut input = value.bytes();
+    while let Some(byte) = input.next() {
+        match byte {
+            b'%' => {
+                let high = (input.next()? as char).to_digit(16)?;
+                let low = (input.next()? as char).to_digit(16)?;
+                bytes.push((high * 16 + low) as u8);
+            }
+            b'+' => bytes.push(b' '),
+            _ => bytes.push(byte),
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
@@ -0,0 +1,521 @@
+// src/server/config.rs
+
+// Configuration du serveur : fichier RON optionnel puis options de ligne de commande
+// Le chargement est commun avec le client (multiplayer_demo::config), seules les options sont propres au serveur
+
+// Import des modules standard pour les collections, le réseau et les durées
+use std::{
+    collections::HashMap,
+    net::{IpAddr, Ipv4Addr},
+    time::Duration,
+};
+
+// Import des modules Bevy pour les ressources
+use bevy::ecs::system::Resource;
+// Import des structures de données partagées
+use multiplayer_demo::{
+    collision::HitZone,
+    config::{parse_value, ConfigError, ConfigFile},
+    map::DEFAULT_MAP,
+    mapgen::GeneratorSettings,
+    pickup::PickupKind,
+    protocol::{DEFAULT_SERVER_PORT, DEFAULT_TOKEN_PORT},
+    weapons::DEFAULT_WEAPONS_PATH,
+    GameMode, Weapon,
+};
+// Import de serde pour la lecture du fichier de configuration
+use serde::Deserialize;
+
+/// Fichier de configuration lu par défaut s'il existe dans le répertoire courant
+pub const DEFAULT_CONFIG_PATH: &str = "server.ron";
+
+/// Nombre maximal de cartes proposées au vote (une touche F1 à F9 par carte côté client)
+const MAX_VOTE_CANDIDATES: usize = 9;
+
+/// Aide affichée par `--help`
+pub const USAGE: &str = "\
+Usage : server [OPTIONS]
+
+Options :
+  --config <FICHIER>          Fichier de configuration RON (défaut : server.ron s'il existe)
+  --bind <IP>                 Adresse d'écoute
+  --port <PORT>               Port UDP du jeu
+  --public-addr <IP>          Adresse publique annoncée aux clients (mode sécurisé)
+  --max-clients <N>           Nombre maximum de clients
+  --tick-rate <HZ>            Fréquence de simulation et d'envoi des instantanés
+  --map <NOM|FICHIER>         Carte jouée (nom d'une carte de maps/ ou chemin d'un fichier)
+  --generate <GRAINE|random>  Joue un labyrinthe généré au lieu de la carte (réglages : generator)
+  --map-rotation <A,B,...>    Cartes jouées à tour de rôle après la première
+  --match-duration <SECONDES> Durée d'une partie (0 = partie sans fin)
+  --vote-duration <SECONDES>  Durée du vote de la carte suivante (0 = pas de vote)
+  --game-mode <MODE>          Mode de jeu (Deathmatch)
+  --secure                    Active le mode sécurisé (jetons de connexion)
+  --token-port <PORT>         Port HTTP de l'émetteur de jetons
+  --max-health <PV>           Points de vie à l'apparition
+  --starting-armor <PA>       Points d'armure à l'apparition
+  --armor-absorption <0..1>   Part des dégâts absorbée par l'armure
+  --weapons <FICHIER>         Définitions des armes (défaut : assets/weapons.ron)
+  --help                      Affiche cette aide";
+
+/// Réglages de gameplay appliqués par le serveur
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct GameplayTuning {
+    pub max_health: f32,                       // Points de vie à l'apparition
+    pub starting_armor: f32,                   // Points d'armure à l'apparition
+    pub armor_absorption: f32,                 // Part des dégâts absorbée par l'armure (0 à 1)
+    pub starting_weapons: Vec<Weapon>,         // Armes possédées à l'apparition (la première est équipée)
+    pub pickups: PickupTuning,                 // Objets à ramasser
+    pub hit_zones: HitZoneTuning,              // Multiplicateurs de dégâts par zone du corps
+}
+
+impl Default for GameplayTuning {
+    fn default() -> Self {
+        Self {
+            max_health: 100.,
+            starting_armor: 0.,
+            armor_absorption: 0.5,
+            starting_weapons: vec![Weapon::Gun, Weapon::Shotgun],
+            pickups: PickupTuning::default(),
+            hit_zones: HitZoneTuning::default(),
+        }
+    }
+}
+
+/// Multiplicateurs appliqués aux dégâts d'un tir selon la zone du corps touchée
+/// Les explosions et l'aura du BFG ne visent pas de zone et gardent leurs dégâts de base
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct HitZoneTuning {
+    pub head: f32,  // Tir à la tête
+    pub torso: f32, // Tir dans le torse
+    pub legs: f32,  // Tir dans les jambes
+}
+
+impl Default for HitZoneTuning {
+    fn default() -> Self {
+        Self {
+            head: 2.,
+            torso: 1.,
+            legs: 0.75,
+        }
+    }
+}
+
+impl HitZoneTuning {
+    /// Multiplicateur de dégâts d'une zone du corps
+    pub fn multiplier(&self, zone: HitZone) -> f32 {
+        match zone {
+            HitZone::Head => self.head,
+            HitZone::Torso => self.torso,
+            HitZone::Legs => self.legs,
+        }
+    }
+}
+
+/// Réglages des objets à ramasser posés sur les cellules d'objets de la carte
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct PickupTuning {
+    pub items: Vec<PickupKind>,      // Objets attribués tour à tour aux cellules d'objets (ordre de lecture)
+    pub health: f32,                 // Points de vie rendus (sans dépasser `max_health`)
+    pub armor: f32,                  // Points d'armure donnés (sans dépasser `max_armor`)
+    pub max_armor: f32,              // Points d'armure maximum
+    pub ammo: HashMap<Weapon, f32>,  // Munitions données par une boîte ou avec l'arme
+    pub respawn_secs: f32,           // Délai de réapparition des objets
+    pub weapon_respawn_secs: f32,    // Délai de réapparition des armes
+}
+
+impl Default for PickupTuning {
+    fn default() -> Self {
+        Self {
+            items: vec![
+                PickupKind::Health,
+                PickupKind::Ammo(Weapon::Gun),
+                PickupKind::Armor,
+                PickupKind::Ammo(Weapon::Shotgun),
+                PickupKind::Weapon(Weapon::Gatling),
+                PickupKind::Health,
+                PickupKind::Ammo(Weapon::Gatling),
+                PickupKind::Weapon(Weapon::RocketLauncher),
+                PickupKind::Armor,
+                PickupKind::Ammo(Weapon::RocketLauncher),
+                PickupKind::Health,
+                PickupKind::Weapon(Weapon::Bfg),
+                PickupKind::Ammo(Weapon::Bfg),
+            ],
+            health: 25.,
+            armor: 50.,
+            max_armor: 100.,
+            ammo: HashMap::from([
+                (Weapon::Gun, 15.),
+                (Weapon::Shotgun, 8.),
+                (Weapon::Gatling, 50.),
+                (Weapon::RocketLauncher, 3.),
+                (Weapon::Bfg, 1.),
+            ]),
+            respawn_secs: 20.,
+            weapon_respawn_secs: 45.,
+        }
+    }
+}
+
+impl PickupTuning {
+    /// Munitions données avec une boîte ou une arme
+    pub fn ammo(&self, weapon: Weapon) -> f32 {
+        self.ammo.get(&weapon).copied().unwrap_or(0.)
+    }
+
+    /// Délai de réapparition d'un objet ramassé
+    pub fn respawn_delay(&self, kind: PickupKind) -> Duration {
+        let secs = match kind {
+            PickupKind::Weapon(_) => self.weapon_respawn_secs,
+            _ => self.respawn_secs,
+        };
+        Duration::from_secs_f32(secs)
+    }
+}
+
+/// Ressource contenant la configuration complète du serveur
+#[derive(Resource, Deserialize, Debug, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerSettings {
+    pub bind_address: IpAddr,           // Adresse d'écoute
+    pub port: u16,                      // Port UDP du jeu
+    pub public_address: Option<IpAddr>, // Adresse publique (par défaut : IP locale)
+    pub max_clients: usize,             // Nombre maximum de clients connectés simultanément
+    pub tick_rate: u32,                 // Ticks serveur par seconde
+    pub lag_compensation_ms: u64,       // Retour en arrière maximal accordé aux tirs
+    pub map: String,                    // Carte jouée (nom dans `maps/` ou chemin)
+    pub weapons: String,                // Fichier des définitions d'armes
+    pub generator: Option<GeneratorSettings>, // Labyrinthe généré joué à la place de `map`
+    pub map_rotation: Vec<String>,      // Cartes jouées à tour de rôle après `map`
+    pub match_duration_secs: u64,       // Durée d'une partie (0 = partie sans fin)
+    pub vote_duration_secs: u64,        // Durée du vote de la carte suivante (0 = pas de vote)
+    pub vote_candidates: usize,         // Nombre de cartes proposées au vote
+    pub game_mode: GameMode,            // Mode de jeu
+    pub secure: bool,                   // Mode sécurisé (jetons de connexion)
+    pub token_port: u16,                // Port HTTP de l'émetteur de jetons
+    pub gameplay: GameplayTuning,       // Réglages de gameplay
+}
+
+impl Default for ServerSettings {
+    fn default() -> Self {
+        Self {
+            bind_address: IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+            port: DEFAULT_SERVER_PORT,
+            public_address: None,
+            max_clients: 32,
+            tick_rate: 60,
+            lag_compensation_ms: 250,
+            map: DEFAULT_MAP.to_string(),
+            weapons: DEFAULT_WEAPONS_PATH.to_string(),
+            generator: None,
+            map_rotation: Vec::new(),
+            match_duration_secs: 600,
+            vote_duration_secs: 20,
+            vote_candidates: 3,
+            game_mode: GameMode::Deathmatch,
+            secure: false,
+            token_port: DEFAULT_TOKEN_PORT,
+            gameplay: GameplayTuning::default(),
+        }
+    }
+}
+
+impl ConfigFile for ServerSettings {
+    const DEFAULT_PATH: &'static str = DEFAULT_CONFIG_PATH;
+
+    fn apply_args(&mut self, args: &[String]) -> Result<(), ConfigError> {
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .cloned()
+                    .ok_or_else(|| ConfigError::Cli(format!("valeur manquante pour {name}")))
+            };
+            match arg.as_str() {
+                "--config" => {
+                    value("--config")?;
+                }
+                "--bind" => self.bind_address = parse_value("--bind", &value("--bind")?)?,
+                "--port" => self.port = parse_value("--port", &value("--port")?)?,
+                "--public-addr" => {
+                    self.public_address =
+                        Some(parse_value("--public-addr", &value("--public-addr")?)?)
+                }
+                "--max-clients" => {
+                    self.max_clients = parse_value("--max-clients", &value("--max-clients")?)?
+                }
+                "--tick-rate" => {
+                    self.tick_rate = parse_value("--tick-rate", &value("--tick-rate")?)?
+                }
+                "--map" => self.map = value("--map")?,
+                "--weapons" => self.weapons = value("--weapons")?,
+                "--map-rotation" => {
+                    self.map_rotation = value("--map-rotation")?
+                        .split(',')
+                        .map(|map| map.trim().to_string())
+                        .collect()
+                }
+                "--match-duration" => {
+                    self.match_duration_secs =
+                        parse_value("--match-duration", &value("--match-duration")?)?
+                }
+                "--vote-duration" => {
+                    self.vote_duration_secs =
+                        parse_value("--vote-duration", &value("--vote-duration")?)?
+                }
+                "--generate" => {
+                    let seed = value("--generate")?;
+                    let generator = self.generator.get_or_insert_with(Default::default);
+                    generator.seed = match seed.as_str() {
+                        "random" => None,
+                        seed => Some(parse_value("--generate", seed)?),
+                    };
+                }
+                "--game-mode" => {
+                    let mode = value("--game-mode")?;
+                    self.game_mode = ron::from_str(&mode).map_err(|_| {
+                        ConfigError::Cli(format!("mode de jeu inconnu pour --game-mode : {mode}"))
+                    })?
+                }
+                "--secure" => self.secure = true,
+                "--token-port" => {
+                    self.token_port = parse_value("--token-port", &value("--token-port")?)?
+                }
+                "--max-health" => {
+                    self.gameplay.max_health = parse_value("--max-health", &value("--max-health")?)?
+                }
+                "--starting-armor" => {
+                    self.gameplay.starting_armor =
+                        parse_value("--starting-armor", &value("--starting-armor")?)?
+                }
+                "--armor-absorption" => {
+                    self.gameplay.armor_absorption =
+                        parse_value("--armor-absorption", &value("--armor-absorption")?)?
+                }
+                other => return Err(ConfigError::Cli(format!("option inconnue : {other}"))),
+            }
+        }
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), ConfigError> {
+        let mut errors = Vec::new();
+
+        if self.port == 0 {
+            errors.push("port : doit être compris entre 1 et 65535".to_string());
+        }
+        if self.secure && self.token_port == self.port {
+            errors.push(format!(
+                "token_port : {} est déjà utilisé par le port du jeu",
+                self.token_port
+            ));
+        }
+        if self.secure && self.public_address.is_some_and(|ip| ip.is_unspecified()) {
+            errors.push("public_address : ne peut pas être une adresse non spécifiée".to_string());
+        }
+        if !(1..=1024).contains(&self.max_clients) {
+            errors.push(format!(
+                "max_clients : {} hors de l'intervalle 1..=1024",
+                self.max_clients
+            ));
+        }
+        if !(10..=240).contains(&self.tick_rate) {
+            errors.push(format!(
+                "tick_rate : {} Hz hors de l'intervalle 10..=240",
+                self.tick_rate
+            ));
+        }
+        if self.lag_compensation_ms > 1000 {
+            errors.push(format!(
+                "lag_compensation_ms : {} ms dépasse le maximum de 1000 ms",
+                self.lag_compensation_ms
+            ));
+        }
+        if self.map.trim().is_empty() {
+            errors.push("map : aucune carte indiquée".to_string());
+        }
+        if self.weapons.trim().is_empty() {
+            errors.push("weapons : aucun fichier de définitions d'armes indiqué".to_string());
+        }
+        if self.map_rotation.iter().any(|map| map.trim().is_empty()) {
+            errors.push("map_rotation : nom de carte vide".to_string());
+        }
+        if !(1..=MAX_VOTE_CANDIDATES).contains(&self.vote_candidates) {
+            errors.push(format!(
+                "vote_candidates : {} hors de l'intervalle 1..={MAX_VOTE_CANDIDATES}",
+                self.vote_candidates
+            ));
+        }
+        if self.vote_duration_secs > 300 {
+            errors.push(format!(
+                "vote_duration_secs : {} s dépasse le maximum de 300 s",
+                self.vote_duration_secs
+            ));
+        }
+        if let Some(generator) = &self.generator {
+            errors.extend(
+                generator
+                    .validate()
+                    .into_iter()
+                    .map(|error| format!("generator.{error}")),
+            );
+        }
+
+        let gameplay = &self.gameplay;
+        if gameplay.max_health <= 0. {
+            errors.push(format!(
+                "gameplay.max_health : {} doit être strictement positif",
+                gameplay.max_health
+            ));
+        }
+        if gameplay.starting_armor < 0. {
+            errors.push(format!(
+                "gameplay.starting_armor : {} ne peut pas être négatif",
+                gameplay.starting_armor
+            ));
+        }
+        if gameplay.starting_armor > gameplay.pickups.max_armor {
+            errors.push(format!(
+                "gameplay.starting_armor : {} dépasse gameplay.pickups.max_armor ({})",
+                gameplay.starting_armor, gameplay.pickups.max_armor
+            ));
+        }
+        if !(0.0..=1.0).contains(&gameplay.armor_absorption) {
+            errors.push(format!(
+                "gameplay.armor_absorption : {} hors de l'intervalle 0..=1",
+                gameplay.armor_absorption
+            ));
+        }
+        if gameplay.starting_weapons.is_empty() {
+            errors.push("gameplay.starting_weapons : au moins une arme est nécessaire".to_string());
+        }
+        let pickups = &gameplay.pickups;
+        for (field, value) in [
+            ("health", pickups.health),
+            ("armor", pickups.armor),
+            ("max_armor", pickups.max_armor),
+        ] {
+            if value < 0. {
+                errors.push(format!(
+                    "gameplay.pickups.{field} : {value} ne peut pas être négatif"
+                ));
+            }
+        }
+        for (field, value) in [
+            ("respawn_secs", pickups.respawn_secs),
+            ("weapon_respawn_secs", pickups.weapon_respawn_secs),
+        ] {
+            if !(0.0..=3600.0).contains(&value) {
+                errors.push(format!(
+                    "gameplay.pickups.{field} : {value} s hors de l'intervalle 0..=3600"
+                ));
+            }
+        }
+        for weapon in Weapon::ALL {
+            match pickups.ammo.get(&weapon) {
+                None => errors.push(format!(
+                    "gameplay.pickups.ammo : valeur manquante pour {weapon}"
+                )),
+                Some(value) if *value < 0. => errors.push(format!(
+                    "gameplay.pickups.ammo : {value} pour {weapon} ne peut pas être négatif"
+                )),
+                Some(_) => {}
+            }
+        }
+        let hit_zones = &gameplay.hit_zones;
+        for (field, value) in [
+            ("head", hit_zones.head),
+            ("torso", hit_zones.torso),
+            ("legs", hit_zones.legs),
+        ] {
+            if !value.is_finite() || value < 0. {
+                errors.push(format!(
+                    "gameplay.hit_zones.{field} : {value} doit être un multiplicateur positif ou nul"
+                ));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError::Invalid(errors))
+        }
+    }
+}
+
+impl ServerSettings {
+    /// Durée d'un tick serveur
+    pub fn tick_interval(&self) -> Duration {
+        Duration::from_secs_f64(1.0 / self.tick_rate as f64)
+    }
+
+    /// Durée d'une partie (zéro pour une partie sans fin)
+    pub fn match_duration(&self) -> Duration {
+        Duration::from_secs(self.match_duration_secs)
+    }
+
+    /// Durée du vote de la carte suivante
+    pub fn vote_duration(&self) -> Duration {
+        Duration::from_secs(self.vote_duration_secs)
+    }
+
+    /// Retour en arrière maximal accordé aux tirs, en ticks serveur
+    pub fn max_rewind_ticks(&self) -> u32 {
+        (self.lag_compensation_ms as f64 * self.tick_rate as f64 / 1000.0).ceil() as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::math::Vec3;
+    use multiplayer_demo::collision::{CollisionWorld, HitTarget, PlayerHull};
+    use renet::ClientId;
+
+    #[test]
+    fn default_multipliers_favor_the_head() {
+        let tuning = HitZoneTuning::default();
+        assert_eq!(tuning.multiplier(HitZone::Head), 2.0);
+        assert_eq!(tuning.multiplier(HitZone::Torso), 1.0);
+        assert_eq!(tuning.multiplier(HitZone::Legs), 0.75);
+    }
+
+    #[test]
+    fn missing_multipliers_keep_their_default() {
+        let tuning: HitZoneTuning = ron::from_str("(head: 3.0)").expect("réglages partiels");
+        assert_eq!(tuning.multiplier(HitZone::Head), 3.0);
+        assert_eq!(tuning.multiplier(HitZone::Torso), 1.0);
+        assert_eq!(tuning.multiplier(HitZone::Legs), 0.75);
+        assert!(ron::from_str::<HitZoneTuning>("(neck: 1.5)").is_err());
+    }
+
+    #[test]
+    fn damage_follows_the_zone_hit() {
+        // Même calcul que `receive_shoot_system` : dégâts de base multipliés selon la zone touchée
+        let tuning = HitZoneTuning {
+            head: 2.5,
+            torso: 1.0,
+            legs: 0.5,
+        };
+        let hull = PlayerHull {
+            client_id: ClientId::from_raw(3),
+            position: Vec3::new(0.0, 10.0, 0.0),
+            crouched: false,
+        };
+        let world = CollisionWorld::default();
+        let damage_at = |height: f32| {
+            let origin = hull.position + Vec3::new(0.0, height, 10.0);
+            match world.cast_ray(origin, Vec3::NEG_Z, 50.0, &[hull])?.target {
+                HitTarget::Player(_, zone) => Some(20.0 * tuning.multiplier(zone)),
+                _ => None,
+            }
+        };
+        assert_eq!(damage_at(1.2), Some(50.0));
+        assert_eq!(damage_at(0.3), Some(20.0));
+        assert_eq!(damage_at(-0.9), Some(10.0));
+        assert_eq!(damage_at(2.0), None);
+    }
+}
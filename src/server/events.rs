@@ -0,0 +1,82 @@
+// src/server/events.rs
+
+// Import des modules nécessaires pour les événements et les types de données
+use bevy::ecs::event::Event;
+use multiplayer_demo::{collision::HitZone, PlayerInputCommand, PlayerShoot, Weapon};
+use renet::ClientId;
+
+/// Événement déclenché à la réception d'une commande d'entrées d'un joueur
+#[derive(Event)]
+pub struct PlayerInputEvent {
+    pub client_id: ClientId,          // Joueur ayant envoyé les entrées
+    pub command: PlayerInputCommand,  // Entrées reçues
+}
+
+/// Événement déclenché à la réception d'un tir d'un joueur
+#[derive(Event)]
+pub struct PlayerShootEvent {
+    pub client_id: ClientId, // Joueur ayant tiré
+    pub shoot: PlayerShoot,  // Description du tir
+}
+
+/// Événement déclenché quand un joueur demande à changer d'arme
+#[derive(Event)]
+pub struct WeaponSwitchEvent {
+    pub client_id: ClientId, // Joueur concerné
+    pub weapon: Weapon,      // Arme demandée
+}
+
+/// Événement déclenché quand un joueur presse ou relâche la gâchette d'une arme à préparation
+#[derive(Event)]
+pub struct TriggerEvent {
+    pub client_id: ClientId, // Joueur concerné
+    pub weapon: Weapon,      // Arme concernée
+    pub pressed: bool,       // True à la pression, False au relâchement
+}
+
+/// Événement déclenché quand un joueur demande à recharger son arme
+#[derive(Event)]
+pub struct ReloadEvent {
+    pub client_id: ClientId, // Joueur concerné
+    pub weapon: Weapon,      // Arme à recharger
+}
+
+/// Événement déclenché quand un joueur subit des dégâts (tir, explosion)
+#[derive(Event)]
+pub struct DamageEvent {
+    pub attacker: ClientId,    // Joueur à l'origine des dégâts
+    pub weapon: Weapon,        // Arme utilisée
+    pub victim: ClientId,      // Joueur touché (éventuellement l'attaquant lui-même)
+    pub damage: f32,           // Dégâts avant absorption par l'armure (zone du corps comprise)
+    pub zone: Option<HitZone>, // Zone du corps touchée (None pour une explosion ou une aura)
+}
+
+/// Événement déclenché à la réception d'un message de discussion
+#[derive(Event)]
+pub struct ChatEvent {
+    pub client_id: ClientId, // Auteur du message
+    pub text: String,        // Texte du message
+}
+
+/// Événement déclenché quand un client est prêt à entrer dans la partie
+#[derive(Event)]
+pub struct PlayerReadyEvent(pub ClientId);
+
+/// Événement déclenché quand un client annonce qu'il quitte la partie
+#[derive(Event)]
+pub struct PlayerLeaveEvent(pub ClientId);
+
+/// Événement déclenché quand un client demande la carte jouée
+#[derive(Event)]
+pub struct MapRequestEvent(pub ClientId);
+
+/// Événement déclenché quand un joueur vote pour la carte suivante
+#[derive(Event)]
+pub struct MapVoteEvent {
+    pub client_id: ClientId, // Joueur ayant voté
+    pub choice: usize,       // Indice de la carte choisie parmi les candidates
+}
+
+/// Événement déclenché à la fin d'une partie pour passer à la carte choisie
+#[derive(Event)]
+pub struct MapChangeEvent(pub usize); // Indice de la carte dans la rotation
@@ -1,7 +1,8 @@
 // Import des modules standard pour la gestion des collections et du réseau
 use std::collections::HashMap;
-use std::net::UdpSocket;
-use std::time::{Duration, SystemTime};
+use std::net::{SocketAddr, UdpSocket};
+use std::process::ExitCode;
+use std::time::SystemTime;
 
 // Import des modules Bevy pour l'ECS et la gestion des systèmes
 use bevy::prelude::IntoSystemConfigs;
@@ -17,26 +18,42 @@ use bevy::{
 // Import des modules renet pour la gestion du réseau multijoueur
 use bevy_renet::{transport::NetcodeServerPlugin, RenetServerPlugin};
 // Import des structures de données partagées entre client et serveur
-use multiplayer_demo::{PlayerLobby, RecentlyRespawned};
+use multiplayer_demo::{
+    collision::CollisionWorld,
+    config::{ConfigError, ConfigFile},
+    map::{MapDefinition, MIN_SPAWN_SPACING},
+    mapgen::generate,
+    protocol::PROTOCOL_ID,
+    weapons::WeaponDefinitions,
+    PlayerLobby,
+};
 use renet::{
-    transport::{NetcodeServerTransport, ServerAuthentication, ServerConfig},
+    transport::{NetcodeServerTransport, ServerAuthentication, ServerConfig, NETCODE_KEY_BYTES},
     ConnectionConfig, RenetServer,
 };
 
 // Import des modules locaux du serveur
-use resources::SpawnSpots;
+use config::{ServerSettings, USAGE};
+use resources::{
+    ActiveMap, FireCooldowns, HeldTriggers, LagCompensation, MapRotation, MatchState,
+    PendingRejections, Pickups, PlayerMovements, PlayerNames, PositionHistory, Projectiles, Reloads,
+    SentMaps, ServerTick, SnapshotBaselines, SpawnSpots,
+};
 use systems::{
-    handle_events_system, receive_message_system, receive_shoot_system, send_message_system,
-    setup_system,
+    apply_input_system, change_map_system, chat_system, damage_system, disconnect_rejected_system,
+    handle_events_system, handle_leave_system, handle_ready_system, map_vote_system,
+    match_timer_system, pickup_system, projectile_system, receive_message_system,
+    receive_shoot_system, record_history_system, reload_system, send_map_system,
+    send_message_system, setup_system, switch_weapon_system, trigger_system,
 };
-
-/// Adresse et port sur lesquels le serveur écoute les connexions
-/// "0.0.0.0" signifie que le serveur écoute sur toutes les interfaces réseau
-const SERVER_ADDR: &str = "0.0.0.0:5000";
+use token_issuer::{spawn_token_server, TokenIssuer};
 
 // Déclaration des modules locaux
+mod config;
+mod events;
 mod resources;
 mod systems;
+mod token_issuer;
 
 /// Énumération définissant les ensembles de systèmes du serveur
 /// Permet d'organiser l'exécution des systèmes dans un ordre spécifique
@@ -44,12 +61,115 @@ mod systems;
 pub enum ServerSystemSet {
     Events,  // Systèmes de gestion des événements réseau
     Receive, // Systèmes de réception des messages clients
+    Apply,   // Systèmes d'application des messages clients reçus
     Send,    // Systèmes d'envoi des messages aux clients
 }
 
+/// Charge une carte désignée par son nom ou son chemin et signale l'échec
+///
+/// # Arguments
+/// * `name` - Nom d'une carte de `maps/` ou chemin d'un fichier
+///
+/// # Returns
+/// * `Option<MapDefinition>` - La carte, ou None si elle est illisible
+fn load_map(name: &str) -> Option<MapDefinition> {
+    let path = MapDefinition::resolve_path(name);
+    match MapDefinition::load(&path) {
+        Ok(map) => {
+            println!("🗺️ Carte {} : {}", map.name, path.display());
+            report_map_issues(&map);
+            Some(map)
+        }
+        Err(err) => {
+            eprintln!("❌ Carte {} : {err}", path.display());
+            None
+        }
+    }
+}
+
+/// Signale les problèmes d'une carte
+/// Une carte douteuse reste jouable : ses problèmes n'empêchent pas le démarrage
+///
+/// # Arguments
+/// * `map` - Carte à vérifier
+fn report_map_issues(map: &MapDefinition) {
+    for issue in map.validate(MIN_SPAWN_SPACING) {
+        eprintln!("⚠️ Carte {} : {issue}", map.name);
+    }
+}
+
 /// Point d'entrée principal du serveur
 /// Initialise et démarre le serveur de jeu multijoueur
-fn main() {
+fn main() -> ExitCode {
+    // Lecture de la configuration (fichier puis ligne de commande), avant toute initialisation
+    let settings = match ServerSettings::load(std::env::args().skip(1)) {
+        Ok(settings) => settings,
+        Err(ConfigError::Help) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(err @ ConfigError::Cli(_)) => {
+            eprintln!("❌ {err}\n\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+        Err(err) => {
+            eprintln!("❌ {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    // Définitions des armes (dégâts, cadence, munitions), partagées avec les clients
+    let weapons_path = std::path::Path::new(&settings.weapons);
+    let weapons = match WeaponDefinitions::load(weapons_path) {
+        Ok(weapons) => {
+            println!("🔫 Armes : {}", weapons_path.display());
+            weapons
+        }
+        Err(err) => {
+            eprintln!("❌ {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    // Carte jouée : labyrinthe généré, ou fichier (nom d'une carte de `maps/` ou chemin)
+    // suivi des cartes de la rotation, toutes chargées et vérifiées dès le démarrage
+    let (map, rotation) = match &settings.generator {
+        Some(generator) => {
+            let seed = generator.resolve_seed();
+            println!("🗺️ Labyrinthe généré avec la graine {seed}");
+            let map = generate(generator, seed);
+            report_map_issues(&map);
+            let rotation = MapRotation {
+                maps: vec![map.clone()],
+                next: 0,
+                generator: Some((generator.clone(), seed)),
+            };
+            (map, rotation)
+        }
+        None => {
+            let Some(map) = load_map(&settings.map) else {
+                return ExitCode::FAILURE;
+            };
+            // Sans rotation, la partie suivante rejoue la même carte
+            let mut maps = Vec::new();
+            for name in &settings.map_rotation {
+                let Some(map) = load_map(name) else {
+                    return ExitCode::FAILURE;
+                };
+                maps.push(map);
+            }
+            if maps.is_empty() {
+                maps.push(map.clone());
+            }
+            let rotation = MapRotation {
+                maps,
+                next: 0,
+                generator: None,
+            };
+            (map, rotation)
+        }
+    };
+
     // Création d'une nouvelle application Bevy
     let mut app = App::new();
 
@@ -68,24 +188,94 @@ fn main() {
 
     // Insertion des ressources globales du serveur
     app.insert_resource(PlayerLobby(HashMap::default())); // Lobby des joueurs connectés
-    app.insert_resource(SpawnSpots::new());               // Points de spawn du niveau
-    app.insert_resource(RecentlyRespawned::default());    // Gestion des respawns récents
+    app.insert_resource(SpawnSpots::from_map(&map));      // Points de spawn du niveau
+    app.insert_resource(PlayerMovements::default());      // États de déplacement autoritaires
+    app.insert_resource(CollisionWorld::from_map(&map));  // Géométrie du niveau pour les tirs
+    app.insert_resource(Pickups::from_map(&map, &settings.gameplay.pickups.items)); // Objets à ramasser
+    app.insert_resource(ActiveMap::new(map));             // Carte jouée, envoyée aux clients
+    app.insert_resource(weapons);                         // Définitions des armes
+    app.insert_resource(rotation);                        // Cartes jouées à tour de rôle
+    app.insert_resource(MatchState::playing(settings.match_duration())); // Partie en cours
+    app.insert_resource(ServerTick::default());           // Tick serveur courant
+    app.insert_resource(FireCooldowns::default());        // Cadences de tir des joueurs
+    app.insert_resource(HeldTriggers::default());         // Gâchettes maintenues (armes à préparation)
+    app.insert_resource(Reloads::default());              // Rechargements en cours
+    app.insert_resource(Projectiles::default());          // Projectiles en vol
+    app.insert_resource(PositionHistory::default());      // Historique des positions pour la compensation
+    app.insert_resource(SnapshotBaselines::default());    // Références de l'encodage différentiel par client
+    app.insert_resource(PlayerNames::default());          // Noms des clients acceptés
+    app.insert_resource(PendingRejections::default());    // Clients refusés en attente de déconnexion
+    app.insert_resource(SentMaps::default());             // Cartes déjà envoyées à chaque client
+
+    // Événements produits par l'aiguillage des messages clients
+    app.add_event::<events::PlayerInputEvent>();  // Commandes d'entrées
+    app.add_event::<events::PlayerShootEvent>();  // Tirs
+    app.add_event::<events::WeaponSwitchEvent>(); // Changements d'arme
+    app.add_event::<events::TriggerEvent>();      // Gâchettes des armes à préparation
+    app.add_event::<events::ReloadEvent>();       // Demandes de rechargement
+    app.add_event::<events::ChatEvent>();         // Messages de discussion
+    app.add_event::<events::PlayerReadyEvent>();  // Entrées dans la partie
+    app.add_event::<events::PlayerLeaveEvent>();  // Départs volontaires
+    app.add_event::<events::MapRequestEvent>();   // Demandes de la carte jouée
+    app.add_event::<events::MapVoteEvent>();      // Votes pour la carte suivante
+    app.add_event::<events::MapChangeEvent>();    // Changements de carte en fin de partie
+    app.add_event::<events::DamageEvent>();       // Dégâts des tirs et des explosions
 
     // Configuration de l'adresse réseau du serveur
-    let server_addr = SERVER_ADDR.parse().unwrap();
-    
+    let server_addr = SocketAddr::new(settings.bind_address, settings.port);
+
     // Création et liaison du socket UDP pour la communication réseau
-    let socket = UdpSocket::bind(server_addr).expect("Échec du bind de l'UDP socket");
+    let socket = match UdpSocket::bind(server_addr) {
+        Ok(socket) => socket,
+        Err(err) => {
+            eprintln!("❌ Impossible d'écouter sur {server_addr} : {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    // Mode sécurisé optionnel : les clients doivent présenter un jeton délivré par l'émetteur
+    let (public_addr, authentication) = if settings.secure {
+        // En mode sécurisé, l'adresse publique doit être celle à laquelle les clients se connectent
+        let ip = match settings.public_address {
+            Some(ip) => ip,
+            None => match local_ip_address::local_ip() {
+                Ok(ip) => ip,
+                Err(err) => {
+                    eprintln!("❌ IP locale introuvable, indiquez --public-addr : {err}");
+                    return ExitCode::FAILURE;
+                }
+            },
+        };
+        let public_addr = SocketAddr::new(ip, settings.port);
+
+        // Clé privée partagée entre l'émetteur de jetons et le transport
+        let private_key: [u8; NETCODE_KEY_BYTES] = rand::random();
+        let issuer = TokenIssuer::new(private_key, PROTOCOL_ID, vec![public_addr]);
+        let token_addr = SocketAddr::new(settings.bind_address, settings.token_port);
+        if let Err(err) = spawn_token_server(token_addr, issuer) {
+            eprintln!("❌ Impossible de démarrer l'émetteur de jetons sur {token_addr} : {err}");
+            return ExitCode::FAILURE;
+        }
+
+        (public_addr, ServerAuthentication::Secure { private_key })
+    } else {
+        // Pas d'authentification (développement)
+        let public_addr = SocketAddr::new(
+            settings.public_address.unwrap_or(settings.bind_address),
+            settings.port,
+        );
+        (public_addr, ServerAuthentication::Unsecure)
+    };
 
     // Configuration du serveur réseau avec les paramètres de connexion
     let server_config = ServerConfig {
         current_time: SystemTime::now()
             .duration_since(SystemTime::UNIX_EPOCH)
             .unwrap(), // Timestamp actuel pour la synchronisation
-        max_clients: 10, // Nombre maximum de clients connectés simultanément
-        protocol_id: 0,  // ID du protocole réseau
-        public_addresses: vec![server_addr], // Adresses publiques du serveur
-        authentication: ServerAuthentication::Unsecure, // Pas d'authentification (développement)
+        max_clients: settings.max_clients, // Nombre maximum de clients connectés simultanément
+        protocol_id: PROTOCOL_ID, // ID du protocole réseau (la version est vérifiée à la connexion)
+        public_addresses: vec![public_addr], // Adresses publiques du serveur
+        authentication,
     };
 
     // Création du transport réseau avec la configuration définie
@@ -102,8 +292,15 @@ fn main() {
     // Ajout du système de configuration initiale (exécuté au démarrage)
     app.add_systems(Startup, setup_system);
 
-    // Définition de l'intervalle de mise à jour fixe (60 FPS)
-    let fixed_interval = Duration::from_secs_f32(1.0 / 60.0);
+    // Définition de l'intervalle de mise à jour fixe (cadence des ticks serveur)
+    // Les entrées des clients sont toujours simulées au pas de déplacement partagé,
+    // un tick serveur traite simplement toutes les entrées reçues depuis le précédent
+    let fixed_interval = settings.tick_interval();
+
+    // Fenêtre de compensation de latence exprimée en ticks serveur
+    app.insert_resource(LagCompensation {
+        max_rewind_ticks: settings.max_rewind_ticks(),
+    });
 
     // Configuration de l'ordre d'exécution des ensembles de systèmes
     app.configure_sets(
@@ -111,7 +308,8 @@ fn main() {
         (
             ServerSystemSet::Events,                    // D'abord les événements
             ServerSystemSet::Receive.after(ServerSystemSet::Events), // Puis la réception
-            ServerSystemSet::Send.after(ServerSystemSet::Receive),   // Enfin l'envoi
+            ServerSystemSet::Apply.after(ServerSystemSet::Receive),  // Puis leur application
+            ServerSystemSet::Send.after(ServerSystemSet::Apply),     // Enfin l'envoi
         ),
     );
 
@@ -121,17 +319,73 @@ fn main() {
         (
             // Système de gestion des événements réseau (connexions/déconnexions)
             handle_events_system.in_set(ServerSystemSet::Events),
-            // Système de réception des messages des clients
+            // Système de déconnexion des clients refusés
+            disconnect_rejected_system
+                .in_set(ServerSystemSet::Events)
+                .after(handle_events_system),
+            // Système de réception et d'aiguillage des messages des clients
             receive_message_system.in_set(ServerSystemSet::Receive),
+            // Systèmes d'application des messages reçus
+            handle_ready_system.in_set(ServerSystemSet::Apply),
+            handle_leave_system.in_set(ServerSystemSet::Apply),
+            send_map_system.in_set(ServerSystemSet::Apply),
+            apply_input_system
+                .in_set(ServerSystemSet::Apply)
+                .after(handle_ready_system),
+            switch_weapon_system.in_set(ServerSystemSet::Apply),
+            chat_system.in_set(ServerSystemSet::Apply),
+            // Système des objets à ramasser, aux positions calculées pour ce tick
+            pickup_system
+                .in_set(ServerSystemSet::Apply)
+                .after(apply_input_system),
             // Système de traitement des tirs des clients
-            receive_shoot_system.in_set(ServerSystemSet::Receive),
+            receive_shoot_system
+                .in_set(ServerSystemSet::Apply)
+                .after(apply_input_system)
+                .after(switch_weapon_system),
+            // Système des projectiles simulés, aux positions calculées pour ce tick
+            projectile_system
+                .in_set(ServerSystemSet::Apply)
+                .after(apply_input_system)
+                .after(receive_shoot_system),
+            // Système d'application des dégâts des tirs et des explosions
+            damage_system
+                .in_set(ServerSystemSet::Apply)
+                .after(receive_shoot_system)
+                .after(projectile_system),
+            // Système des rechargements, après les tirs qui vident un chargeur
+            reload_system
+                .in_set(ServerSystemSet::Apply)
+                .after(receive_shoot_system),
+            // Système de suivi des gâchettes, après les tirs du même tick
+            trigger_system
+                .in_set(ServerSystemSet::Apply)
+                .after(receive_shoot_system),
+            // Systèmes de fin de partie : vote puis changement de carte
+            map_vote_system.in_set(ServerSystemSet::Apply),
+            match_timer_system
+                .in_set(ServerSystemSet::Apply)
+                .after(map_vote_system),
+            change_map_system
+                .in_set(ServerSystemSet::Apply)
+                .after(match_timer_system)
+                .after(damage_system)
+                .after(pickup_system),
+            // Système d'avancement du tick et d'historique des positions
+            record_history_system.in_set(ServerSystemSet::Send),
             // Système d'envoi des messages aux clients
-            send_message_system.in_set(ServerSystemSet::Send),
+            send_message_system
+                .in_set(ServerSystemSet::Send)
+                .after(record_history_system),
         )
             .into_configs() // Conversion en configurations de systèmes
             .run_if(on_timer(fixed_interval)), // Exécution à intervalle fixe
     );
 
+    // Configuration accessible aux systèmes (réglages de gameplay, informations de partie)
+    app.insert_resource(settings);
+
     // Démarrage de la boucle principale du serveur
     app.run();
+    ExitCode::SUCCESS
 }
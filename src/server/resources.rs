@@ -1,5 +1,25 @@
-// Import du module Bevy pour la définition des ressources
-use bevy::ecs::system::Resource;
+// Import des modules standard pour les collections et le temps
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+// Import des modules Bevy pour la définition des ressources
+use bevy::{
+    ecs::system::Resource,
+    math::{Quat, Vec3},
+};
+// Import de l'état de déplacement et des instantanés partagés
+use multiplayer_demo::{
+    map::MapDefinition,
+    mapgen::{generate, GeneratorSettings},
+    movement::{MovementState, MAX_INPUTS_PER_COMMAND},
+    pickup::{pickup_points, PickupKind},
+    snapshot::{Snapshot, SNAPSHOT_HISTORY},
+    Weapon,
+};
+// Import du type ClientId de renet
+use renet::ClientId;
 
 /// Ressource représentant les points de spawn disponibles dans le niveau
 /// Contient une liste de coordonnées 3D où les joueurs peuvent apparaître
@@ -8,171 +28,493 @@ pub struct SpawnSpots(pub Vec<(f32, f32, f32)>);
 
 /// Implémentation des méthodes pour SpawnSpots
 impl SpawnSpots {
-    /// Crée une nouvelle instance de SpawnSpots avec la liste des points de spawn
+    /// Crée la liste des points de spawn à partir des cellules de spawn de la carte
+    ///
+    /// # Arguments
+    /// * `map` - Carte jouée
+    ///
+    /// # Returns
+    /// * `SpawnSpots` - Une nouvelle instance avec les points de spawn de la carte
+    pub fn from_map(map: &MapDefinition) -> Self {
+        Self(map.spawn_points().into_iter().map(Into::into).collect())
+    }
+}
+
+/// Objet à ramasser posé sur une cellule d'objet de la carte
+pub struct PickupSpawner {
+    pub kind: PickupKind,              // Nature de l'objet
+    pub position: Vec3,                // Position du centre de l'objet
+    pub respawn_at: Option<Instant>,   // Réapparition de l'objet ramassé (None = disponible)
+}
+
+/// Ressource contenant les objets à ramasser de la carte jouée
+/// L'indice d'un objet dans la liste est son identifiant dans les messages réseau
+#[derive(Resource, Default)]
+pub struct Pickups(pub Vec<PickupSpawner>);
+
+impl Pickups {
+    /// Pose les objets sur les cellules d'objets de la carte, tous disponibles
+    ///
+    /// # Arguments
+    /// * `map` - Carte jouée
+    /// * `items` - Objets attribués tour à tour aux cellules (aucun objet si vide)
+    pub fn from_map(map: &MapDefinition, items: &[PickupKind]) -> Self {
+        Self(
+            pickup_points(map)
+                .into_iter()
+                .zip(items.iter().cycle())
+                .map(|(position, &kind)| PickupSpawner {
+                    kind,
+                    position,
+                    respawn_at: None,
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Ressource contenant la carte jouée par le serveur et son empreinte
+/// L'empreinte est annoncée aux clients, qui ne demandent la carte que s'ils ne l'ont pas en cache
+#[derive(Resource)]
+pub struct ActiveMap {
+    pub map: MapDefinition, // Carte jouée
+    pub hash: u64,          // Empreinte de son contenu
+}
+
+impl ActiveMap {
+    /// Crée la ressource en calculant l'empreinte de la carte
+    ///
+    /// # Arguments
+    /// * `map` - Carte jouée
+    pub fn new(map: MapDefinition) -> Self {
+        let hash = map.content_hash();
+        Self { map, hash }
+    }
+}
+
+/// Ressource contenant les cartes jouées à tour de rôle
+/// En mode généré, de nouveaux labyrinthes sont produits à chaque fin de partie
+#[derive(Resource)]
+pub struct MapRotation {
+    pub maps: Vec<MapDefinition>, // Cartes de la rotation, chargées au démarrage
+    pub next: usize,              // Indice de la prochaine carte de la rotation
+    pub generator: Option<(GeneratorSettings, u64)>, // Réglages du générateur et dernière graine utilisée
+}
+
+impl MapRotation {
+    /// Cartes proposées pour la partie suivante : la prochaine carte de la rotation puis les suivantes
+    ///
+    /// # Arguments
+    /// * `count` - Nombre de cartes souhaitées
+    ///
+    /// # Returns
+    /// * `Vec<usize>` - Indices des cartes dans la rotation (au moins une)
+    pub fn candidates(&mut self, count: usize) -> Vec<usize> {
+        if let Some((settings, seed)) = &mut self.generator {
+            self.maps = (0..count.max(1))
+                .map(|_| {
+                    *seed = seed.wrapping_add(1);
+                    generate(settings, *seed)
+                })
+                .collect();
+            self.next = 0;
+        }
+        let count = count.clamp(1, self.maps.len());
+        (0..count)
+            .map(|offset| (self.next + offset) % self.maps.len())
+            .collect()
+    }
+
+    /// Retient la carte choisie : la rotation reprend à la carte qui la suit
+    ///
+    /// # Arguments
+    /// * `index` - Indice de la carte dans la rotation
+    ///
+    /// # Returns
+    /// * `MapDefinition` - La carte choisie
+    pub fn select(&mut self, index: usize) -> MapDefinition {
+        self.next = (index + 1) % self.maps.len();
+        self.maps[index].clone()
+    }
+}
+
+/// Étape de la partie en cours
+pub enum MatchPhase {
+    /// Partie en cours, jusqu'à l'instant indiqué (None = partie sans fin)
+    Playing { ends_at: Option<Instant> },
+    /// Vote de la carte suivante
+    Voting {
+        ends_at: Instant,                // Fin du vote
+        candidates: Vec<usize>,          // Indices des cartes proposées dans la rotation
+        votes: HashMap<ClientId, usize>, // Choix de chaque joueur (indice parmi les candidates)
+    },
+}
+
+/// Ressource contenant l'étape de la partie en cours
+#[derive(Resource)]
+pub struct MatchState {
+    pub phase: MatchPhase,
+}
+
+impl MatchState {
+    /// Démarre une nouvelle partie
+    ///
+    /// # Arguments
+    /// * `duration` - Durée de la partie (zéro = partie sans fin)
+    pub fn playing(duration: Duration) -> Self {
+        let ends_at = (!duration.is_zero()).then(|| Instant::now() + duration);
+        Self {
+            phase: MatchPhase::Playing { ends_at },
+        }
+    }
+}
+
+/// Avance tolérée sur la cadence de tir, les tirs d'un client pouvant arriver groupés
+pub const FIRE_RATE_TOLERANCE: Duration = Duration::from_millis(100);
+
+/// Ressource contenant, pour chaque joueur et chaque arme, l'instant à partir duquel un tir est accepté
+#[derive(Resource, Default)]
+pub struct FireCooldowns(pub HashMap<ClientId, HashMap<Weapon, Instant>>);
+
+impl FireCooldowns {
+    /// Vérifie la cadence de tir d'une arme et réserve le tir suivant s'il est accepté
+    /// Un tir peut devancer son créneau d'au plus `FIRE_RATE_TOLERANCE` : la cadence moyenne
+    /// reste bornée par celle de l'arme
+    ///
+    /// # Arguments
+    /// * `client_id` - Tireur
+    /// * `weapon` - Arme utilisée
+    /// * `interval` - Intervalle minimal entre deux tirs de l'arme
+    /// * `now` - Instant de réception du tir
+    ///
+    /// # Returns
+    /// * `bool` - True si le tir respecte la cadence de l'arme
+    pub fn try_fire(
+        &mut self,
+        client_id: ClientId,
+        weapon: Weapon,
+        interval: Duration,
+        now: Instant,
+    ) -> bool {
+        let ready_at = self
+            .0
+            .entry(client_id)
+            .or_default()
+            .entry(weapon)
+            .or_insert(now);
+        if *ready_at > now + FIRE_RATE_TOLERANCE {
+            return false;
+        }
+        *ready_at = (*ready_at).max(now) + interval;
+        true
+    }
+}
+
+/// Avance tolérée sur la préparation d'une arme (rotation, charge), la pression de la gâchette
+/// pouvant arriver en retard sur le serveur
+pub const WIND_UP_TOLERANCE: Duration = Duration::from_millis(150);
+
+/// Ressource contenant la gâchette maintenue par chaque joueur : arme et instant de la pression
+#[derive(Resource, Default)]
+pub struct HeldTriggers(pub HashMap<ClientId, (Weapon, Instant)>);
+
+/// Avance tolérée sur la fin d'un rechargement, le client terminant le sien de son côté
+pub const RELOAD_TOLERANCE: Duration = Duration::from_millis(100);
+
+/// Ressource contenant le rechargement en cours de chaque joueur : arme et instant de la fin
+#[derive(Resource, Default)]
+pub struct Reloads(pub HashMap<ClientId, (Weapon, Instant)>);
+
+impl Reloads {
+    /// Rechargement en cours d'un joueur, tel qu'annoncé dans son inventaire
+    ///
+    /// # Arguments
+    /// * `client_id` - Joueur concerné
+    /// * `now` - Instant courant
+    ///
+    /// # Returns
+    /// * `Option<(Weapon, f32)>` - Arme en cours de rechargement et secondes restantes
+    pub fn remaining(&self, client_id: ClientId, now: Instant) -> Option<(Weapon, f32)> {
+        self.0.get(&client_id).map(|(weapon, ends_at)| {
+            let remaining = ends_at.saturating_duration_since(now);
+            (*weapon, remaining.as_secs_f32())
+        })
+    }
+}
+
+/// Projectile simulé par le serveur
+pub struct Projectile {
+    pub id: u32,                             // Identifiant annoncé aux clients
+    pub owner: ClientId,                     // Joueur ayant tiré
+    pub weapon: Weapon,                      // Arme ayant tiré le projectile
+    pub position: Vec3,                      // Position courante
+    pub velocity: Vec3,                      // Vitesse
+    pub traveled: f32,                       // Distance parcourue depuis le tir
+    pub aura_damage: HashMap<ClientId, f32>, // Dégâts de zone d'effet cumulés, pas encore appliqués
+    pub aura_elapsed: f32,                   // Temps écoulé depuis la dernière application
+}
+
+impl Projectile {
+    /// Retire les dégâts de zone d'effet cumulés depuis la dernière application
     ///
     /// # Returns
-    /// * `SpawnSpots` - Une nouvelle instance avec les points de spawn générés
-    pub fn new() -> Self {
-        return Self(spawn_list_maker());
-    }
-}
-
-/// Fonction qui génère la liste des points de spawn basée sur la grille du labyrinthe
-/// Analyse la grille du niveau et identifie les positions marquées comme points de spawn
-///
-/// # Returns
-/// * `Vec<(f32, f32, f32)>` - Liste des coordonnées 3D des points de spawn
-pub fn spawn_list_maker() -> Vec<(f32, f32, f32)> {
-    // Initialisation de la liste des points de spawn
-    let mut spawn_list: Vec<(f32, f32, f32)> = Vec::new();
-
-    // Définition de la grille du labyrinthe
-    // 0 = espace vide, 1 = mur, 2 = point de spawn
-    let maze_grid = vec![
-        vec![
-            1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
-            1, 1,
-        ],
-        vec![
-            1, 2, 0, 0, 0, 2, 1, 2, 0, 0, 0, 2, 1, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2, 1, 0,
-            2, 1,
-        ],
-        vec![
-            1, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0,
-            0, 1,
-        ],
-        vec![
-            1, 0, 0, 1, 0, 0, 1, 0, 0, 1, 0, 0, 1, 0, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 0, 1, 0,
-            0, 1,
-        ],
-        vec![
-            1, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 1, 2, 0, 0, 0, 2, 1, 0, 0, 0, 0,
-            0, 1,
-        ],
-        vec![
-            1, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 1, 2, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0,
-            0, 1,
-        ],
-        vec![
-            1, 0, 0, 1, 1, 1, 1, 0, 0, 0, 0, 0, 1, 1, 1, 1, 0, 0, 1, 0, 0, 1, 0, 0, 1, 0, 0, 1, 0,
-            0, 1,
-        ],
-        vec![
-            1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 1, 0, 0, 0, 0,
-            0, 1,
-        ],
-        vec![
-            1, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 1, 0, 0, 0, 0,
-            0, 1,
-        ],
-        vec![
-            1, 1, 1, 1, 1, 1, 1, 0, 0, 1, 0, 0, 1, 1, 1, 1, 1, 1, 1, 0, 0, 1, 0, 0, 1, 1, 1, 1, 0,
-            0, 1,
-        ],
-        vec![
-            1, 2, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 1, 2, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0,
-            0, 1,
-        ],
-        vec![
-            1, 0, 0, 0, 0, 0, 1, 2, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 1, 2, 0, 0, 0, 0, 0, 0,
-            0, 1,
-        ],
-        vec![
-            1, 0, 0, 1, 0, 0, 1, 1, 1, 1, 0, 0, 1, 0, 0, 1, 0, 0, 0, 0, 0, 1, 1, 1, 1, 1, 1, 1, 0,
-            0, 1,
-        ],
-        vec![
-            1, 0, 0, 1, 0, 0, 0, 0, 0, 1, 0, 0, 1, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-            0, 1,
-        ],
-        vec![
-            1, 0, 0, 1, 2, 0, 0, 0, 0, 1, 0, 0, 1, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-            2, 1,
-        ],
-        vec![
-            1, 0, 0, 1, 1, 1, 1, 0, 0, 1, 0, 0, 1, 0, 0, 1, 0, 0, 1, 0, 0, 1, 0, 0, 1, 0, 0, 1, 1,
-            1, 1,
-        ],
-        vec![
-            1, 0, 0, 1, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 1, 0, 0, 1, 0, 0, 1, 0, 0, 1, 0,
-            2, 1,
-        ],
-        vec![
-            1, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 1, 0, 0, 1, 0, 0, 1, 0, 0, 1, 0,
-            0, 1,
-        ],
-        vec![
-            1, 0, 0, 1, 1, 1, 1, 0, 0, 0, 0, 0, 1, 1, 1, 1, 0, 0, 1, 0, 0, 1, 1, 1, 1, 0, 0, 1, 0,
-            0, 1,
-        ],
-        vec![
-            1, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0,
-            0, 1,
-        ],
-        vec![
-            1, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2, 1, 2, 0, 0, 0, 0, 1, 0, 0, 0, 0,
-            0, 1,
-        ],
-        vec![
-            1, 0, 0, 1, 0, 0, 1, 1, 1, 1, 1, 1, 1, 0, 0, 1, 1, 1, 1, 1, 1, 1, 0, 0, 1, 0, 0, 1, 1,
-            1, 1,
-        ],
-        vec![
-            1, 0, 0, 0, 0, 0, 1, 2, 0, 1, 0, 0, 0, 0, 0, 1, 2, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 1, 2,
-            0, 1,
-        ],
-        vec![
-            1, 2, 0, 0, 0, 0, 1, 0, 0, 1, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 1, 0,
-            0, 1,
-        ],
-        vec![
-            1, 1, 1, 1, 1, 1, 1, 0, 0, 1, 0, 0, 1, 1, 1, 1, 0, 0, 1, 1, 1, 1, 1, 1, 1, 0, 0, 1, 0,
-            0, 1,
-        ],
-        vec![
-            1, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 1, 2, 0, 0, 0, 0, 1, 2, 0, 0, 0, 0, 0, 0, 0, 1, 0,
-            0, 1,
-        ],
-        vec![
-            1, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 1, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 2, 1, 0,
-            0, 1,
-        ],
-        vec![
-            1, 0, 0, 1, 1, 1, 1, 1, 1, 1, 0, 0, 1, 0, 0, 1, 1, 1, 1, 0, 0, 1, 1, 1, 1, 1, 1, 1, 0,
-            0, 1,
-        ],
-        vec![
-            1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 2, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-            0, 1,
-        ],
-        vec![
-            1, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 2, 0, 0, 0, 0, 1, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-            2, 1,
-        ],
-        vec![
-            1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
-            1, 1,
-        ],
-    ];
-
-    // Taille de chaque cellule de la grille en unités du monde
-    let size = 4.0;
-
-    // Parcours de la grille pour identifier les points de spawn (cellules avec valeur 2)
-    for (y, row) in maze_grid.iter().enumerate() {
-        for (x, &cell) in row.iter().enumerate() {
-            if cell == 2 {
-                // Conversion des coordonnées de la grille en coordonnées 3D du monde
-                // Centrage de la grille autour de l'origine (0,0,0)
-                spawn_list.push((
-                    x as f32 * size - (maze_grid[0].len() as f32 * size / 2.0), // Position X
-                    2.0, // Hauteur fixe à 2 unités au-dessus du sol
-                    y as f32 * size - (maze_grid.len() as f32 * size / 2.0), // Position Z
-                ));
-            }
+    /// * `HashMap<ClientId, f32>` - Dégâts cumulés par joueur touché
+    pub fn take_aura_damage(&mut self) -> HashMap<ClientId, f32> {
+        self.aura_elapsed = 0.0;
+        std::mem::take(&mut self.aura_damage)
+    }
+}
+
+/// Ressource contenant les projectiles en vol
+#[derive(Resource, Default)]
+pub struct Projectiles {
+    pub next_id: u32,            // Identifiant du prochain projectile
+    pub active: Vec<Projectile>, // Projectiles en vol
+}
+
+impl Projectiles {
+    /// Ajoute un projectile en vol
+    ///
+    /// # Arguments
+    /// * `owner` - Joueur ayant tiré
+    /// * `weapon` - Arme ayant tiré le projectile
+    /// * `position` - Position de départ
+    /// * `velocity` - Vitesse
+    ///
+    /// # Returns
+    /// * `u32` - Identifiant du projectile
+    pub fn launch(
+        &mut self,
+        owner: ClientId,
+        weapon: Weapon,
+        position: Vec3,
+        velocity: Vec3,
+    ) -> u32 {
+        let id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1);
+        self.active.push(Projectile {
+            id,
+            owner,
+            weapon,
+            position,
+            velocity,
+            traveled: 0.0,
+            aura_damage: HashMap::new(),
+            aura_elapsed: 0.0,
+        });
+        id
+    }
+}
+
+/// Ressource contenant le numéro du tick serveur courant
+/// Incrémenté à chaque envoi de l'état du lobby, il sert d'horodatage aux tirs des clients
+#[derive(Resource, Default)]
+pub struct ServerTick(pub u32);
+
+/// Ressource de configuration de la compensation de latence
+#[derive(Resource)]
+pub struct LagCompensation {
+    pub max_rewind_ticks: u32, // Nombre maximal de ticks de retour en arrière autorisé
+}
+
+/// Échantillon horodaté de la position d'un joueur
+#[derive(Clone, Copy, Debug)]
+pub struct PositionSample {
+    pub tick: u32,      // Tick serveur de l'échantillon
+    pub position: Vec3, // Position du joueur à ce tick
+    pub rotation: Quat, // Rotation du joueur à ce tick
+    pub crouched: bool, // Posture du joueur à ce tick (zones du corps)
+}
+
+/// Ressource contenant l'historique des positions de chaque joueur
+/// Chaque client possède un tampon circulaire limité à la fenêtre de compensation
+#[derive(Resource, Default)]
+pub struct PositionHistory(pub HashMap<ClientId, VecDeque<PositionSample>>);
+
+impl PositionHistory {
+    /// Ajoute un échantillon pour un joueur en supprimant les plus anciens
+    ///
+    /// # Arguments
+    /// * `client_id` - Joueur concerné
+    /// * `sample` - Échantillon à enregistrer
+    /// * `capacity` - Nombre maximal d'échantillons conservés
+    pub fn record(&mut self, client_id: ClientId, sample: PositionSample, capacity: usize) {
+        let buffer = self.0.entry(client_id).or_default();
+        buffer.push_back(sample);
+        while buffer.len() > capacity.max(1) {
+            buffer.pop_front();
         }
     }
 
-    // Retour de la liste complète des points de spawn
-    spawn_list
+    /// Retrouve l'échantillon d'un joueur au tick demandé
+    /// Retourne le plus récent échantillon antérieur ou égal au tick, sinon le plus ancien connu
+    ///
+    /// # Arguments
+    /// * `client_id` - Joueur concerné
+    /// * `tick` - Tick recherché
+    ///
+    /// # Returns
+    /// * `Option<PositionSample>` - Échantillon trouvé, ou None si le joueur n'a pas d'historique
+    pub fn sample_at(&self, client_id: ClientId, tick: u32) -> Option<PositionSample> {
+        let buffer = self.0.get(&client_id)?;
+        buffer
+            .iter()
+            .rev()
+            .find(|sample| sample.tick <= tick)
+            .or_else(|| buffer.front())
+            .copied()
+    }
+}
+
+/// Nombre maximal de pas de déplacement accumulés par un joueur (rattrapage des commandes groupées)
+pub const MAX_INPUT_BUDGET: f32 = MAX_INPUTS_PER_COMMAND as f32;
+/// Avance tolérée du numéro d'une entrée sur les pas écoulés depuis la dernière entrée appliquée
+pub const MAX_INPUT_LEAD: u32 = MAX_INPUTS_PER_COMMAND as u32;
+
+/// État de déplacement autoritaire d'un joueur
+#[derive(Clone, Copy, Debug)]
+pub struct PlayerMovement {
+    pub state: MovementState, // État physique courant
+    pub last_input: u32,      // Numéro de la dernière entrée appliquée
+    pub budget: f32,          // Pas de déplacement encore autorisés
+    pub idle_steps: f32,      // Pas écoulés depuis la dernière entrée appliquée
 }
+
+impl PlayerMovement {
+    /// Crée l'état de déplacement d'un joueur qui n'a encore envoyé aucune entrée
+    ///
+    /// # Arguments
+    /// * `position` - Position de départ
+    pub fn at(position: Vec3) -> Self {
+        Self {
+            state: MovementState::at(position),
+            last_input: 0,
+            budget: MAX_INPUT_BUDGET,
+            idle_steps: 0.0,
+        }
+    }
+
+    /// Crédite les pas de déplacement d'un tick serveur, dans la limite du rattrapage autorisé
+    ///
+    /// # Arguments
+    /// * `steps` - Pas de déplacement simulés pendant un tick serveur
+    pub fn refill(&mut self, steps: f32) {
+        self.budget = (self.budget + steps).min(MAX_INPUT_BUDGET);
+        self.idle_steps += steps;
+    }
+
+    /// Vérifie que le numéro d'une entrée ne devance pas le temps écoulé depuis la dernière
+    /// entrée appliquée ; la première entrée d'un joueur fixe la numérotation
+    ///
+    /// # Arguments
+    /// * `tick` - Numéro de l'entrée
+    pub fn is_within_lead(&self, tick: u32) -> bool {
+        self.last_input == 0
+            || tick
+                <= self
+                    .last_input
+                    .saturating_add(self.idle_steps.ceil() as u32)
+                    .saturating_add(MAX_INPUT_LEAD)
+    }
+
+    /// Consomme un pas de déplacement
+    ///
+    /// # Returns
+    /// * `bool` - False si le budget du joueur est épuisé
+    pub fn consume(&mut self) -> bool {
+        if self.budget < 1.0 {
+            return false;
+        }
+        self.budget -= 1.0;
+        self.idle_steps = 0.0;
+        true
+    }
+}
+
+/// Ressource contenant l'état de déplacement de chaque joueur
+/// Seul le serveur fait avancer ces états à partir des entrées reçues
+#[derive(Resource, Default)]
+pub struct PlayerMovements(pub HashMap<ClientId, PlayerMovement>);
+
+impl PlayerMovements {
+    /// Replace un joueur à une position de spawn en conservant le numéro de sa dernière entrée
+    /// et son budget de pas de déplacement
+    ///
+    /// # Arguments
+    /// * `client_id` - Joueur concerné
+    /// * `position` - Position de spawn
+    pub fn reset(&mut self, client_id: ClientId, position: Vec3) {
+        self.0
+            .entry(client_id)
+            .and_modify(|movement| movement.state = MovementState::at(position))
+            .or_insert_with(|| PlayerMovement::at(position));
+    }
+}
+
+/// Instantanés envoyés à un client et dernier instantané acquitté
+#[derive(Default)]
+pub struct ClientSnapshots {
+    pub acked: Option<u32>,   // Tick du dernier instantané acquitté par le client
+    sent: VecDeque<Snapshot>, // Derniers instantanés envoyés, du plus ancien au plus récent
+}
+
+/// Ressource contenant les références de l'encodage différentiel de chaque client
+#[derive(Resource, Default)]
+pub struct SnapshotBaselines(pub HashMap<ClientId, ClientSnapshots>);
+
+impl SnapshotBaselines {
+    /// Enregistre l'acquittement d'un instantané par un client
+    /// Seul un instantané plus récent que le précédent et encore conservé est retenu
+    ///
+    /// # Arguments
+    /// * `client_id` - Client ayant acquitté
+    /// * `tick` - Tick de l'instantané acquitté
+    pub fn acknowledge(&mut self, client_id: ClientId, tick: u32) {
+        let Some(client) = self.0.get_mut(&client_id) else {
+            return;
+        };
+        if client.acked.is_some_and(|acked| acked >= tick) {
+            return;
+        }
+        if client.sent.iter().any(|snapshot| snapshot.tick == tick) {
+            client.acked = Some(tick);
+        }
+    }
+
+    /// Instantané de référence pour le prochain envoi à un client
+    ///
+    /// # Returns
+    /// * `Option<&Snapshot>` - Dernier instantané acquitté, ou None pour un envoi complet
+    pub fn baseline(&self, client_id: ClientId) -> Option<&Snapshot> {
+        let client = self.0.get(&client_id)?;
+        let acked = client.acked?;
+        client.sent.iter().find(|snapshot| snapshot.tick == acked)
+    }
+
+    /// Mémorise un instantané envoyé à un client
+    ///
+    /// # Arguments
+    /// * `client_id` - Client destinataire
+    /// * `snapshot` - Instantané envoyé
+    pub fn record(&mut self, client_id: ClientId, snapshot: Snapshot) {
+        let client = self.0.entry(client_id).or_default();
+        client.sent.push_back(snapshot);
+        while client.sent.len() > SNAPSHOT_HISTORY {
+            client.sent.pop_front();
+        }
+    }
+}
+
+/// Ressource associant à chaque client accepté le nom d'utilisateur annoncé à la connexion
+#[derive(Resource, Default)]
+pub struct PlayerNames(pub HashMap<ClientId, String>);
+
+/// Ressource associant à chaque client l'empreinte de la dernière carte qui lui a été envoyée
+/// Une demande répétée est ignorée tant qu'une nouvelle carte n'a pas été annoncée
+#[derive(Resource, Default)]
+pub struct SentMaps(pub HashMap<ClientId, u64>);
+
+/// Ressource contenant les clients refusés en attente de déconnexion
+/// La déconnexion est différée pour laisser au message de refus le temps d'être livré
+#[derive(Resource, Default)]
+pub struct PendingRejections(pub HashMap<ClientId, Instant>);
@@ -2,149 +2,1147 @@
 
 // Import des modules standard pour la gestion des collections et du temps
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     time::{Duration, Instant},
 };
 
 // Import des modules Bevy pour l'ECS et les événements
 use bevy::{
     ecs::{
-        event::EventReader,
+        event::{EventReader, EventWriter},
         system::{Res, ResMut},
     },
-    log::info,
+    log::{info, warn},
     math::{Quat, Vec3},
 };
 // Import des structures de données partagées
 use multiplayer_demo::{
-    PlayerAttributes, PlayerLobby, PlayerShoot, RecentlyRespawned, ServerMessage, Weapon,
+    collision::{CollisionWorld, HitTarget, HitZone, PlayerHull, PLAYER_HULL_RADIUS},
+    movement,
+    pickup::{is_within_reach, PickupKind},
+    protocol::{ConnectData, PROTOCOL_VERSION},
+    snapshot::Snapshot,
+    weapons::{FireMode, WeaponDefinitions},
+    ClientMessage, PlayerAttributes, PlayerLobby, ServerMessage, Weapon, MAX_CHAT_LENGTH,
 };
 // Import pour la génération de nombres aléatoires
 use rand::{seq::SliceRandom, thread_rng};
 // Import des modules renet pour la gestion réseau
-use renet::{ClientId, DefaultChannel, RenetServer, ServerEvent};
+use renet::{
+    transport::{NetcodeServerTransport, NETCODE_USER_DATA_BYTES},
+    ClientId, DefaultChannel, RenetServer, ServerEvent,
+};
 
 // Import des modules locaux
-use crate::{resources::SpawnSpots, SERVER_ADDR};
+use crate::{
+    config::{GameplayTuning, ServerSettings},
+    events::{
+        ChatEvent, DamageEvent, MapChangeEvent, MapRequestEvent, MapVoteEvent, PlayerInputEvent,
+        PlayerLeaveEvent, PlayerReadyEvent, PlayerShootEvent, ReloadEvent, TriggerEvent,
+        WeaponSwitchEvent,
+    },
+    resources::{
+        ActiveMap, FireCooldowns, HeldTriggers, LagCompensation, MapRotation, MatchPhase,
+        MatchState, PendingRejections, PickupSpawner, Pickups, PlayerMovement, PlayerMovements,
+        PlayerNames, PositionHistory, PositionSample, Projectile, Projectiles, Reloads, SentMaps,
+        ServerTick, SnapshotBaselines, SpawnSpots, RELOAD_TOLERANCE, WIND_UP_TOLERANCE,
+    },
+};
+
+/// Distance maximale tolérée entre le point de départ annoncé par le client et sa position
+const MAX_MUZZLE_OFFSET: f32 = 3.0;
+/// Recul du centre d'une explosion devant l'obstacle touché
+const EXPLOSION_OFFSET: f32 = 0.1;
+/// Intervalle d'application des dégâts de zone d'effet cumulés (secondes)
+/// Limite les messages de dégâts envoyés aux joueurs pris dans une zone d'effet
+const AURA_DAMAGE_INTERVAL: f32 = 0.1;
+/// Délai entre l'envoi d'un refus de connexion et la déconnexion du client
+const REJECTION_GRACE: Duration = Duration::from_millis(500);
 
 /// Système de configuration initiale du serveur
 /// Affiche un message de confirmation du démarrage du serveur
-pub fn setup_system() {
-    info!("Server started on {}", SERVER_ADDR);
+///
+/// # Arguments
+/// * `settings` - Référence à la configuration du serveur
+/// * `active_map` - Référence à la carte jouée
+pub fn setup_system(settings: Res<ServerSettings>, active_map: Res<ActiveMap>) {
+    info!(
+        "Server started on {}:{} ({} sur {}, {} ticks/s, {} clients max)",
+        settings.bind_address,
+        settings.port,
+        settings.game_mode,
+        active_map.map.name,
+        settings.tick_rate,
+        settings.max_clients
+    );
+}
+
+/// Système d'avancement du tick serveur et d'enregistrement de l'historique des positions
+/// Conserve pour chaque joueur les positions des derniers ticks afin de rejouer les tirs
+/// dans l'état du monde vu par le tireur
+///
+/// # Arguments
+/// * `server_tick` - Référence mutable au tick serveur courant
+/// * `player_lobby` - Référence au lobby des joueurs
+/// * `history` - Référence mutable à l'historique des positions
+/// * `lag_compensation` - Référence à la configuration de la compensation de latence
+pub fn record_history_system(
+    mut server_tick: ResMut<ServerTick>,
+    player_lobby: Res<PlayerLobby>,
+    mut history: ResMut<PositionHistory>,
+    lag_compensation: Res<LagCompensation>,
+) {
+    server_tick.0 = server_tick.0.wrapping_add(1);
+    let capacity = lag_compensation.max_rewind_ticks as usize + 1;
+
+    for (client_id, attr) in player_lobby.0.iter() {
+        history.record(
+            *client_id,
+            PositionSample {
+                tick: server_tick.0,
+                position: Vec3::from(attr.position),
+                rotation: attr.rotation,
+                crouched: attr.crouched,
+            },
+            capacity,
+        );
+    }
 }
 
-/// Système d'envoi des messages de synchronisation du lobby
-/// Envoie l'état actuel du lobby à tous les clients connectés
+/// Système d'envoi des instantanés de l'état des joueurs
+/// Chaque client reçoit l'instantané du tick encodé par rapport au dernier qu'il a acquitté :
+/// seuls les joueurs et les champs modifiés depuis sont transmis
 /// 
 /// # Arguments
 /// * `server` - Référence mutable au serveur renet
 /// * `player_lobby` - Référence au lobby des joueurs
-pub fn send_message_system(mut server: ResMut<RenetServer>, player_lobby: Res<PlayerLobby>) {
+/// * `server_tick` - Référence au tick serveur courant
+/// * `movements` - Référence aux états de déplacement des joueurs
+/// * `baselines` - Référence mutable aux instantanés envoyés à chaque client
+pub fn send_message_system(
+    mut server: ResMut<RenetServer>,
+    player_lobby: Res<PlayerLobby>,
+    server_tick: Res<ServerTick>,
+    movements: Res<PlayerMovements>,
+    mut baselines: ResMut<SnapshotBaselines>,
+) {
     let channel = DefaultChannel::Unreliable; // Canal non fiable pour les mises à jour fréquentes
-    let lobby = player_lobby.0.clone(); // Copie du lobby pour l'envoi
-    let event = ServerMessage::LobbySync(lobby); // Création du message de synchronisation
-    let message = bincode::serialize(&event).unwrap(); // Sérialisation du message
-    server.broadcast_message(channel, message); // Envoi à tous les clients
+    let snapshot = Snapshot::from_lobby(server_tick.0, &player_lobby);
+
+    for client_id in server.clients_id() {
+        let bytes = snapshot.encode_delta(baselines.baseline(client_id));
+        let message = bincode::serialize(&ServerMessage::Snapshot(bytes)).unwrap();
+        server.send_message(client_id, channel, message);
+        baselines.record(client_id, snapshot.clone());
+    }
+
+    // Envoi à chaque joueur de son état de déplacement autoritaire
+    for (client_id, movement) in movements.0.iter() {
+        let ack = ServerMessage::MovementAck {
+            tick: movement.last_input,
+            state: movement.state,
+        };
+        let message = bincode::serialize(&ack).unwrap();
+        server.send_message(*client_id, channel, message);
+    }
 }
 
-/// Système de réception des messages des clients
-/// Traite les mises à jour de position et d'état envoyées par les clients
-/// 
+/// Canaux sur lesquels les clients envoient leurs messages
+const CLIENT_CHANNELS: [DefaultChannel; 2] =
+    [DefaultChannel::Unreliable, DefaultChannel::ReliableOrdered];
+
+/// Erreurs détectées à la réception d'un message client
+#[derive(Debug)]
+pub enum MessageError {
+    /// Octets impossibles à désérialiser en `ClientMessage`
+    Malformed(bincode::Error),
+    /// Message reçu sur un autre canal que son canal attitré
+    WrongChannel {
+        message: &'static str,     // Nom du message
+        expected: DefaultChannel, // Canal attitré
+        received: DefaultChannel, // Canal de réception
+    },
+    /// Message de jeu envoyé par un client qui n'est pas (encore) dans la partie
+    NotInGame(&'static str),
+}
+
+impl std::fmt::Display for MessageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            MessageError::Malformed(err) => write!(f, "message illisible : {err}"),
+            MessageError::WrongChannel {
+                message,
+                expected,
+                received,
+            } => write!(
+                f,
+                "message {message} reçu sur le canal {received:?} au lieu de {expected:?}"
+            ),
+            MessageError::NotInGame(message) => {
+                write!(f, "message {message} reçu d'un joueur absent de la partie")
+            }
+        }
+    }
+}
+
+/// Désérialise un message client et vérifie qu'il est arrivé sur son canal attitré
+///
+/// # Arguments
+/// * `bytes` - Octets reçus
+/// * `channel` - Canal de réception
+///
+/// # Returns
+/// * `Result<ClientMessage, MessageError>` - Le message décodé ou la raison du rejet
+fn decode_client_message(
+    bytes: &[u8],
+    channel: DefaultChannel,
+) -> Result<ClientMessage, MessageError> {
+    let message = bincode::deserialize::<ClientMessage>(bytes).map_err(MessageError::Malformed)?;
+    let expected = message.channel();
+    if u8::from(expected) != u8::from(channel) {
+        return Err(MessageError::WrongChannel {
+            message: message.name(),
+            expected,
+            received: channel,
+        });
+    }
+    Ok(message)
+}
+
+/// Système de réception et d'aiguillage des messages des clients
+/// Unique point de lecture des canaux clients : chaque message est décodé, vérifié puis
+/// transformé en événement traité par le système correspondant. Les messages rejetés
+/// sont signalés dans les journaux avec leur raison.
+///
 /// # Arguments
 /// * `server` - Référence mutable au serveur renet
-/// * `player_lobby` - Référence mutable au lobby des joueurs
-/// * `recently_respawned` - Référence mutable à la liste des joueurs récemment respawnés
+/// * `player_lobby` - Référence au lobby des joueurs
+/// * `input_events` - Écrivain d'événements d'entrées
+/// * `shoot_events` - Écrivain d'événements de tir
+/// * `switch_events` - Écrivain d'événements de changement d'arme
+/// * `chat_events` - Écrivain d'événements de discussion
+/// * `ready_events` - Écrivain d'événements d'entrée dans la partie
+/// * `leave_events` - Écrivain d'événements de départ
+/// * `map_request_events` - Écrivain d'événements de demande de carte
+/// * `map_vote_events` - Écrivain d'événements de vote pour la carte suivante
+/// * `trigger_events` - Écrivain d'événements de gâchette
+/// * `reload_events` - Écrivain d'événements de rechargement
+#[allow(clippy::too_many_arguments)]
 pub fn receive_message_system(
     mut server: ResMut<RenetServer>,
-    mut player_lobby: ResMut<PlayerLobby>,
-    mut recently_respawned: ResMut<RecentlyRespawned>,
+    player_lobby: Res<PlayerLobby>,
+    mut input_events: EventWriter<PlayerInputEvent>,
+    mut shoot_events: EventWriter<PlayerShootEvent>,
+    mut switch_events: EventWriter<WeaponSwitchEvent>,
+    mut chat_events: EventWriter<ChatEvent>,
+    mut ready_events: EventWriter<PlayerReadyEvent>,
+    mut leave_events: EventWriter<PlayerLeaveEvent>,
+    mut map_request_events: EventWriter<MapRequestEvent>,
+    mut map_vote_events: EventWriter<MapVoteEvent>,
+    mut trigger_events: EventWriter<TriggerEvent>,
+    mut reload_events: EventWriter<ReloadEvent>,
 ) {
-    // Parcours de tous les clients connectés
+    // Parcours de tous les clients connectés et de leurs canaux
     for client_id in server.clients_id() {
-        // Tentative de réception d'un message du client
-        if let Some(message) = server.receive_message(client_id, DefaultChannel::Unreliable) {
-            // Vérification si le client a récemment respawné (pour éviter les glitches de position)
-            let skip_position = recently_respawned
-                .0
-                .get(&client_id)
-                .map(|&t| t.elapsed() < Duration::from_millis(500))
-                .unwrap_or(false);
-
-            // Recherche du joueur dans le lobby
-            if let Some(existing) = player_lobby.0.get_mut(&client_id) {
-                // Tentative de désérialisation des attributs du joueur
-                if let Ok(player_update) = bincode::deserialize::<PlayerAttributes>(&message) {
-                    // Mise à jour des attributs du joueur
-                    existing.username = player_update.username;
-                    if !skip_position {
-                        existing.position = player_update.position; // Mise à jour de la position seulement si pas de respawn récent
+        for channel in CLIENT_CHANNELS {
+            while let Some(bytes) = server.receive_message(client_id, channel) {
+                let message = match decode_client_message(&bytes, channel) {
+                    Ok(message) => message,
+                    Err(err) => {
+                        warn!("⚠️ Message du client {client_id} rejeté : {err}");
+                        continue;
+                    }
+                };
+
+                // Seuls RequestMap, Ready et Leave sont acceptés d'un client qui n'a pas encore de joueur
+                let in_game = player_lobby.0.contains_key(&client_id);
+                match message {
+                    ClientMessage::RequestMap => {
+                        map_request_events.send(MapRequestEvent(client_id));
+                    }
+                    ClientMessage::Ready => {
+                        ready_events.send(PlayerReadyEvent(client_id));
+                    }
+                    ClientMessage::Leave => {
+                        leave_events.send(PlayerLeaveEvent(client_id));
+                    }
+                    message if !in_game => {
+                        let err = MessageError::NotInGame(message.name());
+                        warn!("⚠️ Message du client {client_id} rejeté : {err}");
                     }
-                    existing.rotation = player_update.rotation;
-                    existing.owned_weapon = player_update.owned_weapon;
-                    existing.actual_weapon = player_update.actual_weapon;
-                    existing.ammo = player_update.ammo;
-                } else {
-                    println!(
-                        "Failed to deserialize PlayerAttributes from client {}",
-                        client_id
-                    );
+                    ClientMessage::Input(command) => {
+                        input_events.send(PlayerInputEvent { client_id, command });
+                    }
+                    ClientMessage::Shoot(shoot) => {
+                        shoot_events.send(PlayerShootEvent { client_id, shoot });
+                    }
+                    ClientMessage::SwitchWeapon(weapon) => {
+                        switch_events.send(WeaponSwitchEvent { client_id, weapon });
+                    }
+                    ClientMessage::Chat(text) => {
+                        chat_events.send(ChatEvent { client_id, text });
+                    }
+                    ClientMessage::VoteMap(choice) => {
+                        map_vote_events.send(MapVoteEvent {
+                            client_id,
+                            choice: choice as usize,
+                        });
+                    }
+                    ClientMessage::Trigger { weapon, pressed } => {
+                        trigger_events.send(TriggerEvent {
+                            client_id,
+                            weapon,
+                            pressed,
+                        });
+                    }
+                    ClientMessage::Reload(weapon) => {
+                        reload_events.send(ReloadEvent { client_id, weapon });
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Système d'application des commandes d'entrées des clients
+/// Fait avancer la simulation de déplacement de chaque joueur à partir de ses entrées :
+/// la position du lobby est calculée par le serveur et n'est jamais reprise du client
+/// 
+/// # Arguments
+/// * `input_events` - Lecteur d'événements d'entrées
+/// * `player_lobby` - Référence mutable au lobby des joueurs
+/// * `movements` - Référence mutable aux états de déplacement des joueurs
+/// * `collision_world` - Référence au monde de collision du niveau
+/// * `baselines` - Référence mutable aux instantanés envoyés à chaque client
+/// * `settings` - Référence à la configuration du serveur (durée d'un tick)
+pub fn apply_input_system(
+    mut input_events: EventReader<PlayerInputEvent>,
+    mut player_lobby: ResMut<PlayerLobby>,
+    mut movements: ResMut<PlayerMovements>,
+    collision_world: Res<CollisionWorld>,
+    mut baselines: ResMut<SnapshotBaselines>,
+    settings: Res<ServerSettings>,
+) {
+    // Chaque tick serveur crédite à chaque joueur les pas de déplacement écoulés pendant le tick :
+    // un client ne peut pas avancer plus vite que le temps, même en multipliant les commandes
+    let steps = settings.tick_interval().as_secs_f32() / movement::MOVEMENT_TIMESTEP;
+    for movement in movements.0.values_mut() {
+        movement.refill(steps);
+    }
+
+    for PlayerInputEvent { client_id, command } in input_events.read() {
+        let client_id = *client_id;
+
+        // Recherche du joueur dans le lobby (il a pu mourir depuis l'aiguillage)
+        let Some(existing) = player_lobby.0.get_mut(&client_id) else {
+            continue;
+        };
+
+        // Le dernier instantané reçu par le client devient la référence des prochains envois
+        if let Some(tick) = command.snapshot_ack {
+            baselines.acknowledge(client_id, tick);
+        }
+
+        let movement = movements
+            .0
+            .entry(client_id)
+            .or_insert_with(|| PlayerMovement::at(Vec3::from(existing.position)));
+
+        // Application dans l'ordre des seules entrées encore jamais traitées
+        // Le nombre d'entrées par commande est borné pour limiter les accélérations abusives
+        let skip = command
+            .inputs
+            .len()
+            .saturating_sub(movement::MAX_INPUTS_PER_COMMAND);
+        for input in command.inputs.iter().skip(skip) {
+            if input.tick <= movement.last_input {
+                continue;
+            }
+            // Numéro inventé, trop en avance sur le temps écoulé : la commande est ignorée
+            if !movement.is_within_lead(input.tick) {
+                warn!(
+                    "⚠️ Client {client_id} : entrée {} trop en avance sur {} ignorée",
+                    input.tick, movement.last_input
+                );
+                break;
+            }
+            // Budget épuisé : les entrées restantes seront renvoyées avec la commande suivante
+            if !movement.consume() {
+                break;
+            }
+            movement.state = movement::step(&movement.state, input, &collision_world);
+            movement.last_input = input.tick;
+            existing.rotation = Quat::from_rotation_y(input.yaw);
+        }
+
+        // Mise à jour des attributs du joueur
+        existing.position = movement.state.position.into();
+        existing.velocity = movement.state.velocity;
+        existing.crouched = movement.state.crouched;
+    }
+}
+
+/// Système de changement d'arme
+/// N'accepte que les armes possédées par le joueur
+///
+/// # Arguments
+/// * `switch_events` - Lecteur d'événements de changement d'arme
+/// * `player_lobby` - Référence mutable au lobby des joueurs
+pub fn switch_weapon_system(
+    mut switch_events: EventReader<WeaponSwitchEvent>,
+    mut player_lobby: ResMut<PlayerLobby>,
+) {
+    for WeaponSwitchEvent { client_id, weapon } in switch_events.read() {
+        let Some(attr) = player_lobby.0.get_mut(client_id) else {
+            continue;
+        };
+        if !attr.has_weapon(weapon) {
+            warn!("⚠️ Client {client_id} : changement vers l'arme non possédée {weapon} refusé");
+            continue;
+        }
+        attr.actual_weapon = *weapon;
+    }
+}
+
+/// Système de suivi des gâchettes des armes à préparation
+/// Retient l'instant où chaque joueur a pressé la gâchette ; exécuté après le traitement des tirs
+/// pour qu'un relâchement reçu dans le même tick que les derniers tirs ne les fasse pas refuser
+///
+/// # Arguments
+/// * `trigger_events` - Lecteur d'événements de gâchette
+/// * `player_lobby` - Référence au lobby des joueurs
+/// * `triggers` - Référence mutable aux gâchettes maintenues
+pub fn trigger_system(
+    mut trigger_events: EventReader<TriggerEvent>,
+    player_lobby: Res<PlayerLobby>,
+    mut triggers: ResMut<HeldTriggers>,
+) {
+    // Oubli des gâchettes des joueurs partis
+    triggers.0.retain(|client_id, _| player_lobby.0.contains_key(client_id));
+
+    let now = Instant::now();
+    for TriggerEvent {
+        client_id,
+        weapon,
+        pressed,
+    } in trigger_events.read()
+    {
+        if *pressed {
+            // Chaque pression relance la préparation
+            triggers.0.insert(*client_id, (*weapon, now));
+        } else if triggers.0.get(client_id).map(|(held, _)| held) == Some(weapon) {
+            triggers.0.remove(client_id);
+        }
+    }
+}
+
+/// Système des rechargements
+/// Un rechargement n'est accepté que pour l'arme équipée, avec un chargeur incomplet et des
+/// munitions en réserve ; il dure `reload_secs` et est annulé si le joueur change d'arme. Exécuté
+/// après les tirs, qui lancent d'eux-mêmes le rechargement d'un chargeur vidé
+///
+/// # Arguments
+/// * `reload_events` - Lecteur d'événements de rechargement
+/// * `server` - Référence mutable au serveur renet
+/// * `player_lobby` - Référence mutable au lobby des joueurs
+/// * `weapons` - Référence aux définitions des armes (taille des chargeurs, durée)
+/// * `reloads` - Référence mutable aux rechargements en cours
+pub fn reload_system(
+    mut reload_events: EventReader<ReloadEvent>,
+    mut server: ResMut<RenetServer>,
+    mut player_lobby: ResMut<PlayerLobby>,
+    weapons: Res<WeaponDefinitions>,
+    mut reloads: ResMut<Reloads>,
+) {
+    let now = Instant::now();
+
+    // Oubli des rechargements des joueurs partis et de ceux des armes rangées
+    reloads.0.retain(|client_id, (weapon, _)| {
+        player_lobby
+            .0
+            .get(client_id)
+            .is_some_and(|attr| attr.actual_weapon == *weapon)
+    });
+
+    for ReloadEvent { client_id, weapon } in reload_events.read() {
+        let Some(attr) = player_lobby.0.get(client_id) else {
+            continue;
+        };
+        let definition = weapons.get(*weapon);
+        let magazine = attr.magazine.get(weapon).copied().unwrap_or(0.);
+        let reserve = attr.ammo.get(weapon).copied().unwrap_or(0.);
+        let refusal = if attr.actual_weapon != *weapon {
+            Some("arme non équipée")
+        } else if reloads.0.contains_key(client_id) {
+            Some("rechargement déjà en cours")
+        } else if !definition.can_reload(magazine, reserve) {
+            Some("chargeur plein ou réserve vide")
+        } else {
+            None
+        };
+        if let Some(reason) = refusal {
+            warn!("⚠️ Rechargement du client {client_id} ({weapon}) refusé : {reason}");
+            // Le client a déjà commencé son rechargement : correction de son inventaire
+            let remaining = reloads.remaining(*client_id, now);
+            send_inventory(&mut server, *client_id, attr, remaining);
+            continue;
+        }
+        let ends_at = now + Duration::from_secs_f32(definition.reload_secs);
+        reloads.0.insert(*client_id, (*weapon, ends_at));
+    }
+
+    // Rechargements terminés : le chargeur est complété depuis la réserve
+    let finished: Vec<ClientId> = reloads
+        .0
+        .iter()
+        .filter(|(_, (_, ends_at))| *ends_at <= now)
+        .map(|(client_id, _)| *client_id)
+        .collect();
+    for client_id in finished {
+        let Some((weapon, _)) = reloads.0.remove(&client_id) else {
+            continue;
+        };
+        let Some(attr) = player_lobby.0.get_mut(&client_id) else {
+            continue;
+        };
+        finish_reload(attr, weapon, &weapons);
+        send_inventory(&mut server, client_id, attr, None);
+    }
+}
+
+/// Complète le chargeur d'une arme avec les munitions de sa réserve
+///
+/// # Arguments
+/// * `attr` - Attributs du joueur
+/// * `weapon` - Arme rechargée
+/// * `weapons` - Définitions des armes (taille des chargeurs)
+fn finish_reload(attr: &mut PlayerAttributes, weapon: Weapon, weapons: &WeaponDefinitions) {
+    let magazine = attr.magazine.entry(weapon).or_insert(0.);
+    let reserve = attr.ammo.entry(weapon).or_insert(0.);
+    weapons.get(weapon).reload(magazine, reserve);
+}
+
+/// Système de relais des messages de discussion
+/// Les messages vides sont ignorés et les messages trop longs tronqués
+///
+/// # Arguments
+/// * `chat_events` - Lecteur d'événements de discussion
+/// * `server` - Référence mutable au serveur renet
+pub fn chat_system(mut chat_events: EventReader<ChatEvent>, mut server: ResMut<RenetServer>) {
+    for ChatEvent { client_id, text } in chat_events.read() {
+        let text: String = text.trim().chars().take(MAX_CHAT_LENGTH).collect();
+        if text.is_empty() {
+            warn!("⚠️ Client {client_id} : message de discussion vide ignoré");
+            continue;
+        }
+        println!("💬 {client_id} : {text}");
+
+        let message = bincode::serialize(&ServerMessage::Chat {
+            from: *client_id,
+            text,
+        })
+        .unwrap();
+        server.broadcast_message(DefaultChannel::ReliableOrdered, message);
+    }
+}
+
+/// Système d'entrée dans la partie des clients prêts
+///
+/// # Arguments
+/// * `ready_events` - Lecteur d'événements d'entrée dans la partie
+/// * `server` - Référence mutable au serveur renet
+/// * `player_lobby` - Référence mutable au lobby des joueurs
+/// * `spawn_spots` - Référence aux points de spawn
+/// * `movements` - Référence mutable aux états de déplacement des joueurs
+/// * `names` - Référence aux noms des clients acceptés
+/// * `settings` - Référence à la configuration du serveur
+/// * `pickups` - Référence aux objets à ramasser
+/// * `weapons` - Référence aux définitions des armes
+#[allow(clippy::too_many_arguments)]
+pub fn handle_ready_system(
+    mut ready_events: EventReader<PlayerReadyEvent>,
+    mut server: ResMut<RenetServer>,
+    mut player_lobby: ResMut<PlayerLobby>,
+    spawn_spots: Res<SpawnSpots>,
+    mut movements: ResMut<PlayerMovements>,
+    names: Res<PlayerNames>,
+    settings: Res<ServerSettings>,
+    pickups: Res<Pickups>,
+    weapons: Res<WeaponDefinitions>,
+) {
+    for PlayerReadyEvent(client_id) in ready_events.read() {
+        // Un client refusé à la connexion ne peut pas entrer dans la partie
+        let Some(username) = names.0.get(client_id) else {
+            warn!("⚠️ Client {client_id} non accepté, Ready ignoré");
+            continue;
+        };
+        if player_lobby.0.contains_key(client_id) {
+            warn!("⚠️ Client {client_id} déjà dans la partie, Ready ignoré");
+            continue;
+        }
+        println!("🎮 Client {client_id} prêt");
+
+        // Création du joueur pour le client prêt
+        spawn_player(
+            &mut server,
+            &mut player_lobby,
+            &spawn_spots,
+            *client_id,
+            username.clone(),
+            &mut movements,
+            &settings.gameplay,
+            &weapons,
+        );
+
+        // Envoi des objets actuellement disponibles sur la carte
+        for (id, pickup) in pickups.0.iter().enumerate() {
+            if pickup.respawn_at.is_none() {
+                server.send_message(
+                    *client_id,
+                    DefaultChannel::ReliableOrdered,
+                    pickup_spawn_message(id, pickup),
+                );
+            }
+        }
+    }
+}
+
+/// Système d'envoi de la carte jouée aux clients qui la demandent
+/// La carte part sur le canal fiable : renet la découpe et la réassemble si elle est volumineuse
+///
+/// # Arguments
+/// * `map_request_events` - Lecteur d'événements de demande de carte
+/// * `server` - Référence mutable au serveur renet
+/// * `names` - Référence aux noms des clients acceptés
+/// * `active_map` - Référence à la carte jouée
+/// * `sent_maps` - Référence mutable aux cartes déjà envoyées à chaque client
+pub fn send_map_system(
+    mut map_request_events: EventReader<MapRequestEvent>,
+    mut server: ResMut<RenetServer>,
+    names: Res<PlayerNames>,
+    active_map: Res<ActiveMap>,
+    mut sent_maps: ResMut<SentMaps>,
+) {
+    for MapRequestEvent(client_id) in map_request_events.read() {
+        // Un client refusé à la connexion ne reçoit pas la carte
+        if !names.0.contains_key(client_id) {
+            warn!("⚠️ Client {client_id} non accepté, RequestMap ignoré");
+            continue;
+        }
+        // La carte n'est envoyée qu'une fois par annonce (ServerInfo ou MapChange)
+        if sent_maps.0.get(client_id) == Some(&active_map.hash) {
+            warn!("⚠️ Client {client_id} : carte déjà envoyée, RequestMap ignoré");
+            continue;
+        }
+        sent_maps.0.insert(*client_id, active_map.hash);
+        println!("🗺️ Envoi de la carte '{}' au client {client_id}", active_map.map.name);
+        let message = bincode::serialize(&ServerMessage::MapData(active_map.map.clone())).unwrap();
+        server.send_message(*client_id, DefaultChannel::ReliableOrdered, message);
+    }
+}
+
+/// Système d'enregistrement des votes pour la carte suivante
+/// Un joueur peut changer d'avis tant que le vote est ouvert, seul son dernier choix compte
+///
+/// # Arguments
+/// * `map_vote_events` - Lecteur d'événements de vote
+/// * `match_state` - Référence mutable à l'étape de la partie
+pub fn map_vote_system(
+    mut map_vote_events: EventReader<MapVoteEvent>,
+    mut match_state: ResMut<MatchState>,
+) {
+    for MapVoteEvent { client_id, choice } in map_vote_events.read() {
+        let MatchPhase::Voting {
+            candidates, votes, ..
+        } = &mut match_state.phase
+        else {
+            warn!("⚠️ Client {client_id} : vote reçu hors de la période de vote");
+            continue;
+        };
+        if *choice >= candidates.len() {
+            warn!("⚠️ Client {client_id} : vote pour le choix inexistant {choice}");
+            continue;
+        }
+        votes.insert(*client_id, *choice);
+    }
+}
+
+/// Système de déroulement des parties
+/// À la fin d'une partie, les cartes candidates sont soumises au vote des joueurs ; à la fin
+/// du vote, la carte la plus choisie est jouée (en cas d'égalité, la première dans la rotation).
+/// Sans vote (durée nulle ou une seule candidate), la rotation passe directement à la carte suivante.
+///
+/// # Arguments
+/// * `server` - Référence mutable au serveur renet
+/// * `match_state` - Référence mutable à l'étape de la partie
+/// * `rotation` - Référence mutable à la rotation des cartes
+/// * `settings` - Référence à la configuration du serveur
+/// * `map_change_events` - Écrivain d'événements de changement de carte
+pub fn match_timer_system(
+    mut server: ResMut<RenetServer>,
+    mut match_state: ResMut<MatchState>,
+    mut rotation: ResMut<MapRotation>,
+    settings: Res<ServerSettings>,
+    mut map_change_events: EventWriter<MapChangeEvent>,
+) {
+    let now = Instant::now();
+    match &match_state.phase {
+        MatchPhase::Playing { ends_at } => {
+            // Partie sans fin ou pas encore terminée
+            if !ends_at.is_some_and(|ends_at| now >= ends_at) {
+                return;
+            }
+            let candidates = rotation.candidates(settings.vote_candidates);
+            if candidates.len() < 2 || settings.vote_duration().is_zero() {
+                println!("🏁 Fin de la partie");
+                map_change_events.send(MapChangeEvent(candidates[0]));
+                match_state.phase = MatchPhase::Playing { ends_at: None };
+                return;
+            }
+
+            let names: Vec<String> = candidates
+                .iter()
+                .map(|&index| rotation.maps[index].name.clone())
+                .collect();
+            println!("🏁 Fin de la partie, vote pour la carte suivante : {}", names.join(", "));
+            let message = bincode::serialize(&ServerMessage::MapVote {
+                candidates: names,
+                duration_secs: settings.vote_duration_secs as u32,
+            })
+            .unwrap();
+            server.broadcast_message(DefaultChannel::ReliableOrdered, message);
+
+            match_state.phase = MatchPhase::Voting {
+                ends_at: now + settings.vote_duration(),
+                candidates,
+                votes: HashMap::new(),
+            };
+        }
+        MatchPhase::Voting {
+            ends_at,
+            candidates,
+            votes,
+        } => {
+            if now < *ends_at {
+                return;
+            }
+            // Décompte des voix des joueurs encore connectés
+            let mut counts = vec![0usize; candidates.len()];
+            for (client_id, choice) in votes {
+                if server.is_connected(*client_id) {
+                    counts[*choice] += 1;
                 }
-            } else {
-                println!("Received message from unknown client: {}", client_id);
             }
+            // Le premier maximum l'emporte : à égalité, l'ordre de la rotation départage
+            let winner = (0..counts.len())
+                .rev()
+                .max_by_key(|&choice| counts[choice])
+                .unwrap_or(0);
+            let index = candidates[winner];
+            println!(
+                "🗳️ Vote terminé : {} ({} voix)",
+                rotation.maps[index].name, counts[winner]
+            );
+            map_change_events.send(MapChangeEvent(index));
+            match_state.phase = MatchPhase::Playing { ends_at: None };
         }
     }
+}
+
+/// Système de changement de carte
+/// Remplace la carte, les points de spawn et le monde de collision, annonce la nouvelle carte aux
+/// clients puis fait réapparaître tous les joueurs sur celle-ci avec leurs attributs de départ
+///
+/// # Arguments
+/// * `map_change_events` - Lecteur d'événements de changement de carte
+/// * `server` - Référence mutable au serveur renet
+/// * `rotation` - Référence mutable à la rotation des cartes
+/// * `match_state` - Référence mutable à l'étape de la partie
+/// * `settings` - Référence à la configuration du serveur
+/// * `active_map` - Référence mutable à la carte jouée
+/// * `spawn_spots` - Référence mutable aux points de spawn
+/// * `collision_world` - Référence mutable au monde de collision du niveau
+/// * `player_lobby` - Référence mutable au lobby des joueurs
+/// * `movements` - Référence mutable aux états de déplacement des joueurs
+/// * `history` - Référence mutable à l'historique des positions
+/// * `pickups` - Référence mutable aux objets à ramasser
+/// * `weapons` - Référence aux définitions des armes
+/// * `projectiles` - Référence mutable aux projectiles en vol (supprimés)
+/// * `reloads` - Référence mutable aux rechargements en cours (annulés)
+/// * `sent_maps` - Référence mutable aux cartes déjà envoyées (oubliées à l'annonce de la nouvelle carte)
+#[allow(clippy::too_many_arguments)]
+pub fn change_map_system(
+    mut map_change_events: EventReader<MapChangeEvent>,
+    mut server: ResMut<RenetServer>,
+    mut rotation: ResMut<MapRotation>,
+    mut match_state: ResMut<MatchState>,
+    settings: Res<ServerSettings>,
+    mut active_map: ResMut<ActiveMap>,
+    mut spawn_spots: ResMut<SpawnSpots>,
+    mut collision_world: ResMut<CollisionWorld>,
+    mut player_lobby: ResMut<PlayerLobby>,
+    mut movements: ResMut<PlayerMovements>,
+    mut history: ResMut<PositionHistory>,
+    mut pickups: ResMut<Pickups>,
+    weapons: Res<WeaponDefinitions>,
+    mut projectiles: ResMut<Projectiles>,
+    mut reloads: ResMut<Reloads>,
+    mut sent_maps: ResMut<SentMaps>,
+) {
+    let Some(MapChangeEvent(index)) = map_change_events.read().last() else {
+        return;
+    };
+
+    // Remplacement du niveau
+    let map = rotation.select(*index);
+    println!("🗺️ Nouvelle carte : {}", map.name);
+    *spawn_spots = SpawnSpots::from_map(&map);
+    *collision_world = CollisionWorld::from_map(&map);
+    *pickups = Pickups::from_map(&map, &settings.gameplay.pickups.items);
+    *active_map = ActiveMap::new(map);
+    // Les positions passées et les projectiles en vol appartiennent à l'ancien niveau
+    history.0.clear();
+    projectiles.active.clear();
+    // Les joueurs réapparaissent avec des chargeurs pleins
+    reloads.0.clear();
+
+    // Annonce de la nouvelle carte : chaque client la charge depuis son cache ou la demande
+    sent_maps.0.clear();
+    let message = bincode::serialize(&ServerMessage::MapChange {
+        map: active_map.map.name.clone(),
+        map_hash: active_map.hash,
+    })
+    .unwrap();
+    server.broadcast_message(DefaultChannel::ReliableOrdered, message);
+
+    // Objets de la nouvelle carte, tous disponibles
+    for (id, pickup) in pickups.0.iter().enumerate() {
+        server.broadcast_message(
+            DefaultChannel::ReliableOrdered,
+            pickup_spawn_message(id, pickup),
+        );
+    }
 
-    // Nettoyage automatique des entrées expirées dans la liste des respawns récents
-    // Supprime les entrées de plus de 500ms pour économiser la mémoire
-    recently_respawned
+    // Réapparition de tous les joueurs sur la nouvelle carte
+    let players: Vec<(ClientId, String)> = player_lobby
         .0
-        .retain(|_, &mut t| t.elapsed() < Duration::from_millis(500));
+        .iter()
+        .map(|(client_id, attr)| (*client_id, attr.username.clone()))
+        .collect();
+    for (client_id, username) in players {
+        despawn_player(&mut server, &mut player_lobby, &mut history, client_id);
+        spawn_player(
+            &mut server,
+            &mut player_lobby,
+            &spawn_spots,
+            client_id,
+            username,
+            &mut movements,
+            &settings.gameplay,
+            &weapons,
+        );
+    }
+
+    *match_state = MatchState::playing(settings.match_duration());
+}
+
+/// Système des objets à ramasser
+/// Fait réapparaître les objets dont le délai est écoulé, puis donne chaque objet disponible au
+/// premier joueur à sa portée qui en a l'usage (santé, armure ou munitions déjà au maximum : objet laissé)
+///
+/// # Arguments
+/// * `server` - Référence mutable au serveur renet
+/// * `pickups` - Référence mutable aux objets à ramasser
+/// * `player_lobby` - Référence mutable au lobby des joueurs
+/// * `settings` - Référence à la configuration du serveur
+/// * `weapons` - Référence aux définitions des armes (munitions maximum)
+pub fn pickup_system(
+    mut server: ResMut<RenetServer>,
+    mut pickups: ResMut<Pickups>,
+    mut player_lobby: ResMut<PlayerLobby>,
+    settings: Res<ServerSettings>,
+    weapons: Res<WeaponDefinitions>,
+) {
+    let now = Instant::now();
+    let gameplay = &settings.gameplay;
+
+    for (id, pickup) in pickups.0.iter_mut().enumerate() {
+        // Réapparition d'un objet ramassé
+        if let Some(respawn_at) = pickup.respawn_at {
+            if now < respawn_at {
+                continue;
+            }
+            pickup.respawn_at = None;
+            server.broadcast_message(
+                DefaultChannel::ReliableOrdered,
+                pickup_spawn_message(id, pickup),
+            );
+        }
+
+        // Recherche d'un joueur à portée à qui l'objet profite
+        let mut picker = None;
+        for (client_id, attr) in player_lobby.0.iter_mut() {
+            if is_within_reach(Vec3::from(attr.position), pickup.position)
+                && apply_pickup(attr, pickup.kind, gameplay, &weapons)
+            {
+                picker = Some((
+                    *client_id,
+                    attr.owned_weapon.clone(),
+                    attr.magazine.clone(),
+                    attr.ammo.clone(),
+                ));
+                break;
+            }
+        }
+        let Some((client_id, owned_weapon, magazine, ammo)) = picker else {
+            continue;
+        };
+        println!("🎁 Client {client_id} ramasse {}", pickup.kind);
+        pickup.respawn_at = Some(now + gameplay.pickups.respawn_delay(pickup.kind));
+
+        // L'objet disparaît pour tous, le joueur reçoit son inventaire à jour
+        let message = bincode::serialize(&ServerMessage::PickupDespawn(id as u32)).unwrap();
+        server.broadcast_message(DefaultChannel::ReliableOrdered, message);
+        let message = bincode::serialize(&ServerMessage::PickupTaken {
+            kind: pickup.kind,
+            owned_weapon,
+            magazine,
+            ammo,
+        })
+        .unwrap();
+        server.send_message(client_id, DefaultChannel::ReliableOrdered, message);
+    }
+}
+
+/// Applique l'effet d'un objet ramassé aux attributs d'un joueur
+///
+/// # Arguments
+/// * `attr` - Attributs du joueur
+/// * `kind` - Nature de l'objet
+/// * `gameplay` - Réglages de gameplay (quantités et maximums)
+/// * `weapons` - Définitions des armes (munitions maximum)
+///
+/// # Returns
+/// * `bool` - True si l'objet a servi, False s'il doit rester en place
+fn apply_pickup(
+    attr: &mut PlayerAttributes,
+    kind: PickupKind,
+    gameplay: &GameplayTuning,
+    weapons: &WeaponDefinitions,
+) -> bool {
+    let tuning = &gameplay.pickups;
+    match kind {
+        PickupKind::Health => {
+            if attr.health >= gameplay.max_health {
+                return false;
+            }
+            attr.health = (attr.health + tuning.health).min(gameplay.max_health);
+        }
+        PickupKind::Armor => {
+            if attr.armor >= tuning.max_armor {
+                return false;
+            }
+            attr.armor = (attr.armor + tuning.armor).min(tuning.max_armor);
+        }
+        PickupKind::Ammo(weapon) => {
+            let max_ammo = weapons.get(weapon).max_ammo;
+            let ammo = attr.ammo.entry(weapon).or_insert(0.);
+            if *ammo >= max_ammo {
+                return false;
+            }
+            *ammo = (*ammo + tuning.ammo(weapon)).min(max_ammo);
+        }
+        PickupKind::Weapon(weapon) => {
+            attr.add_weapon(weapon);
+            let max_ammo = weapons.get(weapon).max_ammo;
+            let ammo = attr.ammo.entry(weapon).or_insert(0.);
+            *ammo = (*ammo + tuning.ammo(weapon)).min(max_ammo);
+            // Une arme ramassée arrive chargée
+            finish_reload(attr, weapon, weapons);
+        }
+    }
+    true
+}
+
+/// Applique des dégâts à un joueur
+/// L'armure absorbe la part configurée des dégâts tant qu'elle n'est pas épuisée ;
+/// le reste est retiré des points de vie
+///
+/// # Arguments
+/// * `victim` - Attributs du joueur touché
+/// * `damage` - Dégâts reçus
+/// * `gameplay` - Réglages de gameplay (part absorbée par l'armure)
+fn apply_damage(victim: &mut PlayerAttributes, damage: f32, gameplay: &GameplayTuning) {
+    let absorbed = (damage * gameplay.armor_absorption).min(victim.armor.max(0.));
+    victim.armor -= absorbed;
+    victim.health -= damage - absorbed;
+}
+
+/// Message d'apparition d'un objet à ramasser
+///
+/// # Arguments
+/// * `id` - Identifiant de l'objet (indice dans `Pickups`)
+/// * `pickup` - Objet apparu
+fn pickup_spawn_message(id: usize, pickup: &PickupSpawner) -> Vec<u8> {
+    bincode::serialize(&ServerMessage::PickupSpawn {
+        id: id as u32,
+        kind: pickup.kind,
+        position: pickup.position,
+    })
+    .unwrap()
+}
+
+/// Système de départ volontaire des clients
+/// La déconnexion déclenche ensuite le nettoyage habituel dans `handle_events_system`
+///
+/// # Arguments
+/// * `leave_events` - Lecteur d'événements de départ
+/// * `server` - Référence mutable au serveur renet
+pub fn handle_leave_system(
+    mut leave_events: EventReader<PlayerLeaveEvent>,
+    mut server: ResMut<RenetServer>,
+) {
+    for PlayerLeaveEvent(client_id) in leave_events.read() {
+        println!("👋 Client {client_id} quitte la partie");
+        server.disconnect(*client_id);
+    }
 }
 
 /// Système de gestion des événements réseau (connexions/déconnexions)
 /// Traite les événements de connexion et déconnexion des clients
+/// À la connexion, la version du protocole et le nom d'utilisateur transmis dans les
+/// données de connexion sont vérifiés ; un client refusé reçoit la raison du refus
 /// 
 /// # Arguments
 /// * `server` - Référence mutable au serveur renet
 /// * `server_events` - Lecteur d'événements serveur
+/// * `transport` - Référence au transport réseau (données de connexion des clients)
 /// * `player_lobby` - Référence mutable au lobby des joueurs
-/// * `spawn_spots` - Référence aux points de spawn
-/// * `recently_respawned` - Référence mutable à la liste des respawns récents
+/// * `movements` - Référence mutable aux états de déplacement des joueurs
+/// * `history` - Référence mutable à l'historique des positions
+/// * `baselines` - Référence mutable aux instantanés envoyés à chaque client
+/// * `names` - Référence mutable aux noms des clients acceptés
+/// * `rejections` - Référence mutable aux clients refusés en attente de déconnexion
+/// * `settings` - Référence à la configuration du serveur
+/// * `active_map` - Référence à la carte jouée
+/// * `weapons` - Référence aux définitions des armes (envoyées aux clients acceptés)
+/// * `sent_maps` - Référence mutable aux cartes déjà envoyées à chaque client
+#[allow(clippy::too_many_arguments)]
 pub fn handle_events_system(
     mut server: ResMut<RenetServer>,
     mut server_events: EventReader<ServerEvent>,
+    transport: Res<NetcodeServerTransport>,
     mut player_lobby: ResMut<PlayerLobby>,
-    spawn_spots: Res<SpawnSpots>,
-    mut recently_respawned: ResMut<RecentlyRespawned>,
+    mut movements: ResMut<PlayerMovements>,
+    mut history: ResMut<PositionHistory>,
+    mut baselines: ResMut<SnapshotBaselines>,
+    mut names: ResMut<PlayerNames>,
+    mut rejections: ResMut<PendingRejections>,
+    settings: Res<ServerSettings>,
+    active_map: Res<ActiveMap>,
+    weapons: Res<WeaponDefinitions>,
+    mut sent_maps: ResMut<SentMaps>,
 ) {
     // Parcours de tous les événements serveur
     for event in server_events.read() {
         match event {
             // Événement de connexion d'un nouveau client
+            // Le joueur n'est créé qu'à la réception de son message Ready
             ServerEvent::ClientConnected { client_id } => {
-                println!("✅ Client {client_id} connected");
+                let user_data = transport
+                    .user_data(*client_id)
+                    .unwrap_or([0; NETCODE_USER_DATA_BYTES]);
+                match check_connect_data(&user_data, &names) {
+                    Ok(username) => {
+                        println!("✅ Client {client_id} connected as '{username}'");
+                        names.0.insert(*client_id, username);
 
-                // Création du joueur pour le nouveau client
-                spawn_player(
-                    &mut server,
-                    &mut player_lobby,
-                    &spawn_spots,
-                    *client_id,
-                    &mut recently_respawned,
-                );
+                        // Envoi des paramètres de la partie au client accepté
+                        // (la carte annoncée pourra lui être envoyée une fois)
+                        sent_maps.0.remove(client_id);
+                        let info = ServerMessage::ServerInfo {
+                            tick_rate: settings.tick_rate,
+                            map: active_map.map.name.clone(),
+                            map_hash: active_map.hash,
+                            game_mode: settings.game_mode,
+                            weapons: weapons.clone(),
+                        };
+                        let message = bincode::serialize(&info).unwrap();
+                        server.send_message(*client_id, DefaultChannel::ReliableOrdered, message);
+                    }
+                    Err(reason) => {
+                        println!("🚫 Client {client_id} refusé : {reason}");
+                        let message =
+                            bincode::serialize(&ServerMessage::ConnectionRejected { reason })
+                                .unwrap();
+                        server.send_message(*client_id, DefaultChannel::ReliableOrdered, message);
+                        rejections
+                            .0
+                            .insert(*client_id, Instant::now() + REJECTION_GRACE);
+                    }
+                }
             }
 
             // Événement de déconnexion d'un client
             ServerEvent::ClientDisconnected { client_id, reason } => {
                 println!("❌ Client {client_id} disconnected: {reason}");
                 // Suppression du joueur du lobby
-                despawn_player(&mut server, &mut player_lobby, *client_id);
+                despawn_player(&mut server, &mut player_lobby, &mut history, *client_id);
+                movements.0.remove(client_id);
+                baselines.0.remove(client_id);
+                names.0.remove(client_id);
+                rejections.0.remove(client_id);
+                sent_maps.0.remove(client_id);
             }
         }
     }
 }
 
+/// Vérifie les données de connexion d'un client
+///
+/// # Arguments
+/// * `user_data` - Bloc de données envoyé par le client à la connexion
+/// * `names` - Noms des clients déjà acceptés
+///
+/// # Returns
+/// * `Result<String, String>` - Le nom d'utilisateur accepté, ou la raison lisible du refus
+fn check_connect_data(
+    user_data: &[u8; NETCODE_USER_DATA_BYTES],
+    names: &PlayerNames,
+) -> Result<String, String> {
+    let (version, username) = ConnectData::from_user_data(user_data);
+    if version != PROTOCOL_VERSION {
+        return Err(format!(
+            "version du protocole incompatible (client : {version}, serveur : {PROTOCOL_VERSION}), \
+             mettez à jour le jeu"
+        ));
+    }
+    let username = username.map_err(|err| format!("nom d'utilisateur refusé : {err}"))?;
+    if names.0.values().any(|name| *name == username) {
+        return Err(format!("le nom d'utilisateur '{username}' est déjà utilisé"));
+    }
+    Ok(username)
+}
+
+/// Système de déconnexion des clients refusés une fois le délai de livraison du refus écoulé
+///
+/// # Arguments
+/// * `server` - Référence mutable au serveur renet
+/// * `rejections` - Référence mutable aux clients refusés en attente de déconnexion
+pub fn disconnect_rejected_system(
+    mut server: ResMut<RenetServer>,
+    mut rejections: ResMut<PendingRejections>,
+) {
+    let now = Instant::now();
+    rejections.0.retain(|client_id, deadline| {
+        if *deadline > now {
+            return true;
+        }
+        server.disconnect(*client_id);
+        false
+    });
+}
+
 /// Fonction pour créer un nouveau joueur dans le lobby
 /// Initialise les attributs du joueur et l'ajoute au lobby
 /// 
@@ -153,13 +1151,20 @@ pub fn handle_events_system(
 /// * `player_lobby` - Référence mutable au lobby des joueurs
 /// * `spawn_spots` - Référence aux points de spawn
 /// * `client_id` - ID du client à créer
-/// * `recently_respawned` - Référence mutable à la liste des respawns récents
+/// * `username` - Nom d'utilisateur du joueur
+/// * `movements` - Référence mutable aux états de déplacement des joueurs
+/// * `gameplay` - Réglages de gameplay (santé, armure et armes de départ)
+/// * `weapons` - Définitions des armes (munitions de départ)
+#[allow(clippy::too_many_arguments)]
 fn spawn_player(
     server: &mut RenetServer,
     player_lobby: &mut PlayerLobby,
     spawn_spots: &SpawnSpots,
     client_id: ClientId,
-    recently_respawned: &mut ResMut<RecentlyRespawned>,
+    username: String,
+    movements: &mut PlayerMovements,
+    gameplay: &GameplayTuning,
+    weapons: &WeaponDefinitions,
 ) {
     let mut rng = thread_rng(); // Générateur de nombres aléatoires
     // Sélection aléatoire d'un point de spawn
@@ -169,16 +1174,20 @@ fn spawn_player(
     };
 
     // Ajout du joueur au lobby avec des attributs par défaut
-    player_lobby
-        .0
-        .insert(client_id, default_player_attributes(spawn));
+    let mut attr = default_player_attributes(spawn, gameplay, weapons);
+    attr.username = username;
+    player_lobby.0.insert(client_id, attr.clone());
+
+    // Envoi au joueur de ses attributs complets (armes et munitions ne sont pas dans les instantanés)
+    let message = bincode::serialize(&ServerMessage::PlayerSpawn { attr }).unwrap();
+    server.send_message(client_id, DefaultChannel::ReliableOrdered, message);
 
     // Envoi d'un message de notification de connexion à tous les autres clients
     let message = bincode::serialize(&ServerMessage::PlayerJoin(client_id)).unwrap();
     server.broadcast_message_except(client_id, DefaultChannel::ReliableOrdered, message);
 
-    // Marquage du client comme récemment respawné pour éviter les glitches
-    recently_respawned.0.insert(client_id, Instant::now());
+    // Le serveur replace la simulation de déplacement du joueur au point de spawn
+    movements.reset(client_id, Vec3::from(spawn));
 }
 
 /// Fonction pour supprimer un joueur du lobby
@@ -187,207 +1196,588 @@ fn spawn_player(
 /// # Arguments
 /// * `server` - Référence mutable au serveur renet
 /// * `player_lobby` - Référence mutable au lobby des joueurs
+/// * `history` - Référence mutable à l'historique des positions
 /// * `client_id` - ID du client à supprimer
-fn despawn_player(server: &mut RenetServer, player_lobby: &mut PlayerLobby, client_id: ClientId) {
+fn despawn_player(
+    server: &mut RenetServer,
+    player_lobby: &mut PlayerLobby,
+    history: &mut PositionHistory,
+    client_id: ClientId,
+) {
     // Suppression du joueur du lobby
     player_lobby.0.remove(&client_id);
 
+    // Son ancien corps ne doit plus pouvoir être touché par un tir rejoué
+    history.0.remove(&client_id);
+
     // Envoi d'un message de notification de déconnexion à tous les clients
     let message = bincode::serialize(&ServerMessage::PlayerLeave(client_id)).unwrap();
     server.broadcast_message(DefaultChannel::ReliableOrdered, message);
 }
 
 /// Fonction pour créer des attributs par défaut pour un nouveau joueur
-/// Initialise un joueur avec des valeurs de base (santé, armes, munitions, etc.)
+/// Initialise un joueur avec les valeurs de départ configurées (santé, armes, munitions, etc.)
 /// 
 /// # Arguments
 /// * `spawn` - Position de spawn du joueur
+/// * `gameplay` - Réglages de gameplay
+/// * `weapons` - Définitions des armes (munitions de départ)
 /// 
 /// # Returns
 /// * `PlayerAttributes` - Attributs par défaut du joueur
-fn default_player_attributes(spawn: (f32, f32, f32)) -> PlayerAttributes {
+fn default_player_attributes(
+    spawn: (f32, f32, f32),
+    gameplay: &GameplayTuning,
+    weapons: &WeaponDefinitions,
+) -> PlayerAttributes {
+    // Munitions de départ : chargeur plein, le reste en réserve
+    let mut magazine = HashMap::new();
+    let mut ammo = HashMap::new();
+    for weapon in Weapon::ALL {
+        let definition = weapons.get(weapon);
+        let (mut loaded, mut reserve) = (0., definition.starting_ammo);
+        definition.reload(&mut loaded, &mut reserve);
+        magazine.insert(weapon, loaded);
+        ammo.insert(weapon, reserve);
+    }
+
     PlayerAttributes {
         username: "".to_string(), // Nom d'utilisateur vide par défaut
         position: spawn, // Position de spawn fournie
         rotation: Quat::IDENTITY, // Rotation neutre
-        health: 100., // Santé maximale
-        armor: 0., // Pas d'armure par défaut
+        health: gameplay.max_health, // Santé maximale
+        armor: gameplay.starting_armor, // Armure de départ
         velocity: Vec3::ZERO, // Vélocité nulle
-        // Initialisation des armes possédées (seulement le pistolet par défaut)
-        owned_weapon: HashMap::from([
-            (Weapon::Gun, true),
-            (Weapon::Shotgun, true),
-            (Weapon::Gatling, false),
-            (Weapon::RocketLauncher, false),
-            (Weapon::Bfg, false),
-        ]),
-        actual_weapon: Weapon::Gun, // Arme actuellement équipée
-        // Initialisation des munitions pour chaque arme
-        ammo: HashMap::from([
-            // 100 bullets pour une seule arme sur 5 jouables
-            // (Weapon::Gun, 999.),
-            (Weapon::Gun, 30.),
-            (Weapon::Shotgun, 15.),
-            (Weapon::Gatling, 50.),
-            (Weapon::RocketLauncher, 5.),
-            (Weapon::Bfg, 1.),
-        ]),
+        // Initialisation des armes possédées selon la configuration
+        owned_weapon: Weapon::ALL
+            .into_iter()
+            .map(|weapon| (weapon, gameplay.starting_weapons.contains(&weapon)))
+            .collect(),
+        actual_weapon: gameplay.starting_weapons[0], // Première arme de départ équipée
+        magazine,
+        ammo,
+        crouched: false, // Debout à l'apparition
         entities: HashMap::new(), // Pas d'entités 3D par défaut
     }
 }
 
+/// Envoie à un joueur son inventaire autoritaire (armes possédées, arme équipée, munitions et
+/// rechargement en cours)
+///
+/// # Arguments
+/// * `server` - Référence mutable au serveur renet
+/// * `client_id` - Joueur destinataire
+/// * `attr` - Attributs du joueur
+/// * `reloading` - Arme en cours de rechargement et secondes restantes (`Reloads::remaining`)
+fn send_inventory(
+    server: &mut RenetServer,
+    client_id: ClientId,
+    attr: &PlayerAttributes,
+    reloading: Option<(Weapon, f32)>,
+) {
+    let message = ServerMessage::Inventory {
+        owned_weapon: attr.owned_weapon.clone(),
+        actual_weapon: attr.actual_weapon,
+        magazine: attr.magazine.clone(),
+        ammo: attr.ammo.clone(),
+        reloading,
+    };
+    server.send_message(
+        client_id,
+        DefaultChannel::ReliableOrdered,
+        bincode::serialize(&message).unwrap(),
+    );
+}
+
 /// Système de traitement des tirs des clients
-/// Vérifie les collisions entre les tirs et les joueurs, applique les dégâts
+/// Seul le serveur tient le compte des munitions : un tir n'est accepté qu'avec une arme possédée,
+/// hors rechargement, un chargeur non vide, dans le respect de la cadence de l'arme et, pour les
+/// armes à préparation, la gâchette maintenue assez longtemps ; sinon il est ignoré et le client
+/// reçoit son inventaire à jour. Un tir qui vide le chargeur lance son rechargement.
+/// Les roquettes et boules d'énergie sont lancées comme projectiles simulés (`projectile_system`).
+/// Les autres tirs tracent chaque plomb dans le monde de collision (murs, sol, joueurs) et chaque
+/// joueur touché subit la somme des dégâts de ses plombs. Le point visé par le client ne sert qu'à
+/// donner la direction.
+/// Les joueurs visés sont replacés à leur position au tick affiché par le tireur,
+/// dans la limite de la fenêtre de compensation configurée.
 /// 
 /// # Arguments
+/// * `shoot_events` - Lecteur d'événements de tir
 /// * `server` - Référence mutable au serveur renet
 /// * `player_lobby` - Référence mutable au lobby des joueurs
-/// * `spawn_spots` - Référence aux points de spawn
-/// * `recently_respawned` - Référence mutable à la liste des respawns récents
+/// * `collision_world` - Référence au monde de collision du niveau
+/// * `history` - Référence à l'historique des positions
+/// * `server_tick` - Référence au tick serveur courant
+/// * `lag_compensation` - Référence à la configuration de la compensation de latence
+/// * `weapons` - Référence aux définitions des armes (dégâts, cadence, portée, projectile)
+/// * `cooldowns` - Référence mutable aux cadences de tir des joueurs
+/// * `triggers` - Référence mutable aux gâchettes maintenues (armes à préparation)
+/// * `projectiles` - Référence mutable aux projectiles en vol
+/// * `damage_events` - Écrivain d'événements de dégâts
+/// * `reloads` - Référence mutable aux rechargements en cours
+/// * `settings` - Référence à la configuration du serveur (multiplicateurs des zones du corps)
+#[allow(clippy::too_many_arguments)]
 pub fn receive_shoot_system(
+    mut shoot_events: EventReader<PlayerShootEvent>,
     mut server: ResMut<RenetServer>,
     mut player_lobby: ResMut<PlayerLobby>,
-    spawn_spots: Res<SpawnSpots>,
-    mut recently_respawned: ResMut<RecentlyRespawned>,
+    collision_world: Res<CollisionWorld>,
+    history: Res<PositionHistory>,
+    server_tick: Res<ServerTick>,
+    lag_compensation: Res<LagCompensation>,
+    weapons: Res<WeaponDefinitions>,
+    mut cooldowns: ResMut<FireCooldowns>,
+    mut triggers: ResMut<HeldTriggers>,
+    mut projectiles: ResMut<Projectiles>,
+    mut damage_events: EventWriter<DamageEvent>,
+    mut reloads: ResMut<Reloads>,
+    settings: Res<ServerSettings>,
 ) {
-    let mut should_broadcast_lobby = false; // Flag pour indiquer si le lobby doit être synchronisé
-    let mut hits_to_apply = vec![]; // Liste des impacts à traiter
+    let now = Instant::now();
 
-    // Parcours de tous les clients pour vérifier leurs tirs
-    for client_id in server.clients_id() {
-        // Traitement de tous les messages de tir du client
-        while let Some(message) = server.receive_message(client_id, DefaultChannel::ReliableOrdered)
-        {
-            // Tentative de désérialisation du message de tir
-            if let Ok(shoot) = bincode::deserialize::<PlayerShoot>(&message) {
-                // Vérification de collision avec tous les autres joueurs
-                for (victim_id, victim_attr) in player_lobby.0.iter() {
-                    if *victim_id != client_id { // Pas de tir sur soi-même
-                        let victim_pos = Vec3::from_array(victim_attr.position.into());
-                        let dir = Vec3::from_array(shoot.to.into()) - Vec3::from_array(shoot.from.into());
-                        // Vérification si le tir touche le joueur
-                        let hit = ray_hits_player(victim_pos, shoot.from.into(), dir);
-
-                        if hit {
-                            println!("💥 Client {client_id} a touché {victim_id}");
-
-                            // Calcul des dégâts selon l'arme utilisée
-                            let damage = match shoot.weapon {
-                                Weapon::Gun => 17.,
-                                Weapon::Shotgun => 28.,
-                                Weapon::Gatling => 8.,
-                                Weapon::RocketLauncher => 400.,
-                                Weapon::Bfg => 800.,
-                            };
-
-                            // Ajout de l'impact à la liste de traitement
-                            hits_to_apply.push((client_id, *victim_id, damage));
-                        }
-                    }
-                }
+    // Oubli des cadences des joueurs partis
+    cooldowns
+        .0
+        .retain(|client_id, _| player_lobby.0.contains_key(client_id));
+
+    // Parcours de tous les tirs reçus
+    for PlayerShootEvent { client_id, shoot } in shoot_events.read() {
+        let client_id = *client_id;
+        let definition = weapons.get(shoot.weapon);
+
+        // Le tireur doit être présent dans le lobby
+        let Some(shooter) = player_lobby.0.get_mut(&client_id) else {
+            continue;
+        };
+
+        // Préparation de l'arme : gâchette de cette arme maintenue assez longtemps
+        let wound_up = definition.fire_mode.wind_up().map_or(true, |wind_up| {
+            triggers.0.get(&client_id).is_some_and(|(weapon, since)| {
+                *weapon == shoot.weapon
+                    && now + WIND_UP_TOLERANCE >= *since + Duration::from_secs_f32(wind_up)
+            })
+        });
+
+        // Rechargement de cette arme : achevé d'avance s'il se termine dans la tolérance
+        if let Some(&(weapon, ends_at)) = reloads.0.get(&client_id) {
+            if weapon == shoot.weapon && ends_at <= now + RELOAD_TOLERANCE {
+                reloads.0.remove(&client_id);
+                finish_reload(shooter, weapon, &weapons);
             }
         }
-    }
+        let reloading = reloads
+            .0
+            .get(&client_id)
+            .is_some_and(|(weapon, _)| *weapon == shoot.weapon);
 
-    // Application des dégâts et gestion des morts
-    for (shooter_id, victim_id, damage) in hits_to_apply {
-        if let Some(victim_attr) = player_lobby.0.get_mut(&victim_id) {
-            // Application des dégâts
-            victim_attr.health -= damage;
+        // Le tir n'est accepté qu'avec l'arme équipée, possédée, préparée, chargée et à sa cadence
+        let interval = Duration::from_secs_f32(1.0 / definition.fire_rate);
+        let refusal = if !shooter.has_weapon(&shoot.weapon) {
+            Some("arme non possédée")
+        } else if shoot.weapon != shooter.actual_weapon {
+            Some("arme non équipée")
+        } else if !wound_up {
+            Some("arme pas encore prête")
+        } else if reloading {
+            Some("rechargement en cours")
+        } else if shooter.magazine.get(&shoot.weapon).copied().unwrap_or(0.) < 1.0 {
+            Some("chargeur vide")
+        } else if !cooldowns.try_fire(client_id, shoot.weapon, interval, now) {
+            Some("cadence de tir dépassée")
+        } else {
+            None
+        };
+        if let Some(reason) = refusal {
+            warn!(
+                "⚠️ Tir du client {client_id} ({}) refusé : {reason}",
+                shoot.weapon
+            );
+            // Le client a déjà décompté son tir : correction de son inventaire
+            let remaining = reloads.remaining(client_id, now);
+            send_inventory(&mut server, client_id, shooter, remaining);
+            continue;
+        }
+        let magazine = shooter.magazine.entry(shoot.weapon).or_insert(0.);
+        *magazine -= 1.0;
+        // Chargeur vidé : rechargement automatique s'il reste des munitions en réserve
+        let reserve = shooter.ammo.get(&shoot.weapon).copied().unwrap_or(0.);
+        if *magazine < 1.0 && definition.can_reload(*magazine, reserve) {
+            let ends_at = now + Duration::from_secs_f32(definition.reload_secs);
+            reloads.0.insert(client_id, (shoot.weapon, ends_at));
+        }
+        // Une charge ne sert qu'à un tir
+        if matches!(definition.fire_mode, FireMode::Charge { .. }) {
+            triggers.0.remove(&client_id);
+        }
+        let shooter_pos = Vec3::from(shooter.position);
 
-            // Envoi du message de dégâts au joueur touché
-            let hit_msg = ServerMessage::PlayerHit {
-                new_health: victim_attr.health,
-                client_id: victim_id,
-            };
-            let msg = bincode::serialize(&hit_msg).unwrap();
-            server.send_message(victim_id, DefaultChannel::ReliableOrdered, msg);
-
-            // Vérification si le joueur est mort
-            if victim_attr.health <= 0.0 {
-                if let Some(attr) = player_lobby.0.get_mut(&shooter_id) {
-                    if let Some(ammo) = attr.ammo.get_mut(&attr.actual_weapon) {
-                        // Ajout de munitions selon l'arme utilisée après un frag
-                        let add_ammo = match attr.actual_weapon {
-                            Weapon::Gun => 15.,
-                            Weapon::Shotgun => 6.,
-                            Weapon::Gatling => 30.,
-                            Weapon::RocketLauncher => 1.,
-                            Weapon::Bfg => 1.,
-                        };
-                        *ammo += add_ammo;
-                        
-                    }
-                }
-                // Suppression du joueur mort
-                despawn_player(&mut server, &mut player_lobby, victim_id);
-                // Respawn du joueur
-                spawn_player(
-                    &mut server,
-                    &mut player_lobby,
-                    &spawn_spots,
-                    victim_id,
-                    &mut recently_respawned,
+        // Seule la direction du tir est reprise du client
+        let Some(dir) = (shoot.to - shoot.from).try_normalize() else {
+            println!("⚠️ Tir sans direction ignoré pour le client {client_id}");
+            continue;
+        };
+
+        // Le point de départ n'est accepté que s'il reste proche du tireur
+        let origin = if shoot.from.distance(shooter_pos) <= MAX_MUZZLE_OFFSET {
+            shoot.from
+        } else {
+            shooter_pos + Vec3::Y * movement::STANDING_EYE_HEIGHT
+        };
+
+        // Projectiles simulés : un par plomb, lancé depuis le canon
+        if definition.projectile.is_simulated() {
+            for pellet in definition.pellet_directions(dir, shoot.seed) {
+                let velocity = pellet * definition.speed;
+                let id = projectiles.launch(client_id, shoot.weapon, origin, velocity);
+                let message = ServerMessage::ProjectileSpawn {
+                    id,
+                    weapon: shoot.weapon,
+                    position: origin,
+                    velocity,
+                };
+                server.broadcast_message(
+                    DefaultChannel::ReliableOrdered,
+                    bincode::serialize(&message).unwrap(),
                 );
-                // Envoi du message de mort au joueur
-                if let Some(victim_attr) = player_lobby.0.get_mut(&victim_id) {
-                    let death_msg = ServerMessage::PlayerDeath {
-                        dead: victim_id,
-                        attr: victim_attr.clone(),
-                    };
-                    let death_msg_bytes = bincode::serialize(&death_msg).unwrap();
-                    server.send_message(
-                        victim_id,
-                        DefaultChannel::ReliableOrdered,
-                        death_msg_bytes.clone(),
-                    );
-                    println!("server player_lobby : {:?}", player_lobby);
+            }
+            continue;
+        }
+
+        // Tick rejoué : celui vu par le tireur, borné à la fenêtre de compensation
+        let oldest_tick = server_tick
+            .0
+            .saturating_sub(lag_compensation.max_rewind_ticks);
+        let rewind_tick = shoot.tick.clamp(oldest_tick, server_tick.0);
+
+        // Volumes de tous les autres joueurs (pas de tir sur soi-même) à leur position et dans
+        // leur posture passées
+        let hulls: Vec<PlayerHull> = player_lobby
+            .0
+            .iter()
+            .filter(|(victim_id, _)| **victim_id != client_id)
+            .map(|(victim_id, victim_attr)| {
+                let (position, crouched) = history
+                    .sample_at(*victim_id, rewind_tick)
+                    .map(|sample| (sample.position, sample.crouched))
+                    .unwrap_or_else(|| (Vec3::from(victim_attr.position), victim_attr.crouched));
+                PlayerHull {
+                    client_id: *victim_id,
+                    position,
+                    crouched,
+                }
+            })
+            .collect();
+
+        // Plombs retrouvés à partir de la graine du client, chacun arrêté par le premier élément
+        // touché (un mur ou le sol arrête le plomb) ; les dégâts sont cumulés par joueur touché,
+        // qui retient la zone la plus vulnérable atteinte
+        let hit_zones = &settings.gameplay.hit_zones;
+        let mut damage_by_victim: HashMap<ClientId, (f32, HitZone)> = HashMap::new();
+        for pellet in definition.pellet_directions(dir, shoot.seed) {
+            let hit = collision_world.cast_ray(origin, pellet, definition.range, &hulls);
+            if let Some(hit) = hit {
+                if let HitTarget::Player(victim_id, zone) = hit.target {
+                    // Calcul des dégâts selon l'arme, la distance de l'impact et la zone touchée
+                    let damage = definition.damage_at(hit.distance) * hit_zones.multiplier(zone);
+                    let (total, best_zone) =
+                        damage_by_victim.entry(victim_id).or_insert((0.0, zone));
+                    *total += damage;
+                    *best_zone = (*best_zone).min(zone);
                 }
-                should_broadcast_lobby = true; // Le lobby doit être synchronisé
             }
         }
-    }
 
-    // Synchronisation du lobby si nécessaire
-    if should_broadcast_lobby {
-        let lobby_msg = ServerMessage::LobbySync(player_lobby.0.clone());
-        let msg = bincode::serialize(&lobby_msg).unwrap();
-        server.broadcast_message(DefaultChannel::Unreliable, msg);
+        // Envoi des impacts au système de dégâts
+        for (victim_id, (damage, zone)) in damage_by_victim {
+            println!("💥 Client {client_id} a touché {victim_id} ({zone}, {damage} dégâts)");
+            damage_events.send(DamageEvent {
+                attacker: client_id,
+                weapon: shoot.weapon,
+                victim: victim_id,
+                damage,
+                zone: Some(zone),
+            });
+        }
     }
 }
 
-/// Fonction pour vérifier si un rayon touche un joueur
-/// Utilise une détection de collision basée sur la distance au rayon
-/// 
+/// Système des projectiles simulés (roquettes, boules d'énergie)
+/// Fait avancer chaque projectile d'un tick ; un projectile explose au premier mur, sol ou joueur
+/// touché (le tireur excepté) ou au bout de la portée de son arme. L'explosion blesse les joueurs
+/// à portée qui la voient (aucun mur entre eux et le centre), le tireur compris, et les repousse.
+/// Un projectile doté d'une zone d'effet (boule d'énergie) blesse aussi, tout au long de son
+/// trajet, les autres joueurs à portée qui le voient ; ces dégâts sont cumulés et appliqués toutes
+/// les `AURA_DAMAGE_INTERVAL` secondes, et à l'explosion.
+///
 /// # Arguments
-/// * `pos` - Position du joueur
-/// * `from` - Point de départ du tir
-/// * `dir` - Direction du tir
-/// 
-/// # Returns
-/// * `bool` - True si le joueur est touché, False sinon
-fn ray_hits_player(pos: Vec3, from: [f32; 3], dir: Vec3) -> bool {
-    let start = Vec3::from_array(from);
-    let max_dist = 100.0; // Distance maximale du tir
-    let radius = 1.0; // Rayon de collision du joueur
+/// * `server` - Référence mutable au serveur renet
+/// * `projectiles` - Référence mutable aux projectiles en vol
+/// * `player_lobby` - Référence au lobby des joueurs
+/// * `movements` - Référence mutable aux états de déplacement des joueurs (souffle)
+/// * `collision_world` - Référence au monde de collision du niveau
+/// * `settings` - Référence à la configuration du serveur (durée d'un tick)
+/// * `weapons` - Référence aux définitions des armes (portée, dégâts, explosion)
+/// * `damage_events` - Écrivain d'événements de dégâts
+#[allow(clippy::too_many_arguments)]
+pub fn projectile_system(
+    mut server: ResMut<RenetServer>,
+    mut projectiles: ResMut<Projectiles>,
+    player_lobby: Res<PlayerLobby>,
+    mut movements: ResMut<PlayerMovements>,
+    collision_world: Res<CollisionWorld>,
+    settings: Res<ServerSettings>,
+    weapons: Res<WeaponDefinitions>,
+    mut damage_events: EventWriter<DamageEvent>,
+) {
+    let dt = settings.tick_interval().as_secs_f32();
+    let mut exploded = vec![];
+
+    for projectile in projectiles.active.iter_mut() {
+        let definition = weapons.get(projectile.weapon);
+        let Some(dir) = projectile.velocity.try_normalize() else {
+            exploded.push((projectile.id, projectile.position));
+            continue;
+        };
+
+        // Volumes des joueurs à leur position courante, le tireur excepté
+        let hulls: Vec<PlayerHull> = player_lobby
+            .0
+            .iter()
+            .filter(|(client_id, _)| **client_id != projectile.owner)
+            .map(|(client_id, attr)| PlayerHull {
+                client_id: *client_id,
+                position: Vec3::from(attr.position),
+                crouched: attr.crouched,
+            })
+            .collect();
+
+        // Trajet du tick, borné à la portée restante
+        let step = (projectile.velocity.length() * dt).min(definition.range - projectile.traveled);
+        if let Some(hit) = collision_world.cast_ray(projectile.position, dir, step, &hulls) {
+            // Explosion juste devant l'obstacle, pour que le mur touché ne masque pas l'explosion
+            let point = hit.point - dir * EXPLOSION_OFFSET;
+            exploded.push((projectile.id, point));
+            continue;
+        }
+        projectile.position += dir * step;
+        projectile.traveled += step;
+        if projectile.traveled >= definition.range {
+            exploded.push((projectile.id, projectile.position));
+            continue;
+        }
+
+        // Zone d'effet : dégâts continus aux autres joueurs proches et en vue du projectile
+        let Some(aura) = definition.aura else {
+            continue;
+        };
+        for hull in hulls.iter() {
+            let to_player = hull.position - projectile.position;
+            let distance = to_player.length();
+            if distance - PLAYER_HULL_RADIUS > aura.radius {
+                continue;
+            }
+            let hidden = collision_world
+                .cast_ray(projectile.position, to_player, distance, &[])
+                .is_some();
+            if hidden {
+                continue;
+            }
+            *projectile.aura_damage.entry(hull.client_id).or_insert(0.0) +=
+                aura.damage_per_second * dt;
+        }
+        projectile.aura_elapsed += dt;
+        if projectile.aura_elapsed >= AURA_DAMAGE_INTERVAL {
+            send_aura_damage(projectile, &mut damage_events);
+        }
+    }
+
+    for (id, point) in exploded {
+        let Some(index) = projectiles.active.iter().position(|p| p.id == id) else {
+            continue;
+        };
+        let mut projectile = projectiles.active.swap_remove(index);
+        // Dégâts de zone d'effet restants, appliqués avant ceux de l'explosion
+        send_aura_damage(&mut projectile, &mut damage_events);
+        let Projectile { owner, weapon, .. } = projectile;
+        let definition = weapons.get(weapon);
+        let Some(splash) = definition.splash else {
+            continue;
+        };
+
+        // Joueurs à portée de l'explosion et en vue de son centre
+        for (client_id, attr) in player_lobby.0.iter() {
+            let center = Vec3::from(attr.position);
+            let distance = (point.distance(center) - PLAYER_HULL_RADIUS).max(0.0);
+            let Some(factor) = splash.factor(distance) else {
+                continue;
+            };
+            if collision_world
+                .cast_ray(point, center - point, point.distance(center), &[])
+                .is_some()
+            {
+                continue;
+            }
+
+            // Dégâts réduits pour le tireur lui-même
+            let mut damage = definition.damage * factor;
+            if *client_id == owner {
+                damage *= splash.self_damage;
+            }
+            damage_events.send(DamageEvent {
+                attacker: owner,
+                weapon,
+                victim: *client_id,
+                damage,
+                zone: None,
+            });
+
+            // Souffle dirigé du centre de l'explosion vers le joueur
+            if let Some(movement) = movements.0.get_mut(client_id) {
+                let push = (center - point).try_normalize().unwrap_or(Vec3::Y);
+                movement
+                    .state
+                    .apply_impulse(push * splash.knockback * factor);
+            }
+        }
 
-    let to = start + dir.normalize() * max_dist; // Point de fin du rayon
-    let closest = closest_point_on_line(start, to, pos); // Point le plus proche sur le rayon
-    pos.distance(closest) < radius // Vérification de la collision
+        let message = ServerMessage::ProjectileExplode {
+            id,
+            position: point,
+            radius: splash.radius,
+        };
+        server.broadcast_message(
+            DefaultChannel::ReliableOrdered,
+            bincode::serialize(&message).unwrap(),
+        );
+    }
 }
 
-/// Fonction pour trouver le point le plus proche sur une ligne
-/// Calcule la projection orthogonale d'un point sur une ligne
-/// 
+/// Applique les dégâts de zone d'effet cumulés par un projectile
+///
 /// # Arguments
-/// * `a` - Point de départ de la ligne
-/// * `b` - Point de fin de la ligne
-/// * `p` - Point à projeter
-/// 
-/// # Returns
-/// * `Vec3` - Point le plus proche sur la ligne
-fn closest_point_on_line(a: Vec3, b: Vec3, p: Vec3) -> Vec3 {
-    let ab = b - a; // Vecteur directeur de la ligne
-    let t = ((p - a).dot(ab)) / ab.length_squared(); // Paramètre de projection
-    a + ab * t.clamp(0.0, 1.0) // Point projeté (clampé entre a et b)
+/// * `projectile` - Projectile doté d'une zone d'effet
+/// * `damage_events` - Écrivain d'événements de dégâts
+fn send_aura_damage(projectile: &mut Projectile, damage_events: &mut EventWriter<DamageEvent>) {
+    for (victim, damage) in projectile.take_aura_damage() {
+        damage_events.send(DamageEvent {
+            attacker: projectile.owner,
+            weapon: projectile.weapon,
+            victim,
+            damage,
+            zone: None,
+        });
+    }
+}
+
+/// Système d'application des dégâts et de gestion des morts
+/// Les dégâts sont en partie absorbés par l'armure ; un joueur tué réapparaît aussitôt et son
+/// attaquant (s'il ne s'est pas tué lui-même) récupère des munitions de l'arme utilisée
+///
+/// # Arguments
+/// * `damage_events` - Lecteur d'événements de dégâts
+/// * `server` - Référence mutable au serveur renet
+/// * `player_lobby` - Référence mutable au lobby des joueurs
+/// * `spawn_spots` - Référence aux points de spawn
+/// * `movements` - Référence mutable aux états de déplacement des joueurs
+/// * `history` - Référence mutable à l'historique des positions
+/// * `settings` - Référence à la configuration du serveur
+/// * `weapons` - Référence aux définitions des armes (munitions rendues)
+/// * `reloads` - Référence mutable aux rechargements en cours (annulés à la mort)
+#[allow(clippy::too_many_arguments)]
+pub fn damage_system(
+    mut damage_events: EventReader<DamageEvent>,
+    mut server: ResMut<RenetServer>,
+    mut player_lobby: ResMut<PlayerLobby>,
+    spawn_spots: Res<SpawnSpots>,
+    mut movements: ResMut<PlayerMovements>,
+    mut history: ResMut<PositionHistory>,
+    settings: Res<ServerSettings>,
+    weapons: Res<WeaponDefinitions>,
+    mut reloads: ResMut<Reloads>,
+) {
+    let gameplay = &settings.gameplay;
+    let now = Instant::now();
+    // Joueurs tués pendant ce tick : les dégâts suivants ne touchent pas le joueur réapparu
+    let mut killed = HashSet::new();
+
+    for DamageEvent {
+        attacker,
+        weapon,
+        victim,
+        damage,
+        zone,
+    } in damage_events.read()
+    {
+        let (attacker, weapon, victim_id) = (*attacker, *weapon, *victim);
+        if killed.contains(&victim_id) {
+            continue;
+        }
+        let Some(victim_attr) = player_lobby.0.get_mut(&victim_id) else {
+            continue;
+        };
+
+        // Application des dégâts, en partie absorbés par l'armure
+        apply_damage(victim_attr, *damage, gameplay);
+
+        // Envoi du message de dégâts au joueur touché
+        let hit_msg = ServerMessage::PlayerHit {
+            new_health: victim_attr.health,
+            new_armor: victim_attr.armor,
+            client_id: victim_id,
+            zone: *zone,
+        };
+        let msg = bincode::serialize(&hit_msg).unwrap();
+        server.send_message(victim_id, DefaultChannel::ReliableOrdered, msg);
+
+        // Confirmation de l'impact au tireur (retour visuel, tir à la tête)
+        if attacker != victim_id {
+            let confirm_msg = ServerMessage::HitConfirm {
+                victim: victim_id,
+                zone: *zone,
+            };
+            let msg = bincode::serialize(&confirm_msg).unwrap();
+            server.send_message(attacker, DefaultChannel::ReliableOrdered, msg);
+        }
+
+        // Vérification si le joueur est mort
+        if victim_attr.health > 0.0 {
+            continue;
+        }
+        killed.insert(victim_id);
+        let username = victim_attr.username.clone();
+        if attacker != victim_id {
+            if let Some(attr) = player_lobby.0.get_mut(&attacker) {
+                if let Some(ammo) = attr.ammo.get_mut(&weapon) {
+                    // Ajout de munitions selon l'arme utilisée après un frag
+                    let definition = weapons.get(weapon);
+                    *ammo = (*ammo + definition.kill_refund).min(definition.max_ammo);
+                }
+                let remaining = reloads.remaining(attacker, now);
+                send_inventory(&mut server, attacker, attr, remaining);
+            }
+        }
+        // Suppression du joueur mort, dont le rechargement est perdu
+        reloads.0.remove(&victim_id);
+        despawn_player(&mut server, &mut player_lobby, &mut history, victim_id);
+        // Respawn du joueur
+        spawn_player(
+            &mut server,
+            &mut player_lobby,
+            &spawn_spots,
+            victim_id,
+            username,
+            &mut movements,
+            gameplay,
+            &weapons,
+        );
+        // Envoi du message de mort au joueur
+        if let Some(victim_attr) = player_lobby.0.get(&victim_id) {
+            let death_msg = ServerMessage::PlayerDeath {
+                dead: victim_id,
+                attr: victim_attr.clone(),
+            };
+            let death_msg_bytes = bincode::serialize(&death_msg).unwrap();
+            server.send_message(victim_id, DefaultChannel::ReliableOrdered, death_msg_bytes);
+        }
+    }
 }
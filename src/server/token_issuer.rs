@@ -0,0 +1,269 @@
+// src/server/token_issuer.rs
+
+// Émetteur de jetons de connexion pour le mode sécurisé
+// Petit serveur HTTP local qui délivre des `ConnectToken` renet signés avec la clé privée
+// partagée avec le transport : l'identifiant du client est choisi ici et ne peut plus être falsifié
+
+// Import des modules standard pour le réseau, les threads et les entrées/sorties
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+    time::{Duration, SystemTime},
+};
+
+// Import des données de connexion partagées
+use multiplayer_demo::protocol::{decode_query_value, ConnectData, PROTOCOL_VERSION, TOKEN_PATH};
+// Import des jetons de connexion renet
+use renet::transport::{ConnectToken, NETCODE_KEY_BYTES};
+
+/// Durée de validité d'un jeton avant son utilisation (en secondes)
+const TOKEN_EXPIRE_SECONDS: u64 = 300;
+/// Délai d'inactivité après lequel une connexion établie avec le jeton expire (en secondes)
+const TOKEN_TIMEOUT_SECONDS: i32 = 15;
+/// Délai maximal de lecture de la requête et d'écriture de la réponse d'une demande de jeton
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+/// Taille maximale lue de la ligne de requête HTTP (au-delà, la requête est refusée)
+const MAX_REQUEST_LINE_BYTES: u64 = 1024;
+/// Nombre maximal de demandes traitées en même temps (au-delà, la demande est refusée)
+const MAX_CONCURRENT_REQUESTS: usize = 16;
+
+/// Émetteur de jetons de connexion
+#[derive(Clone)]
+pub struct TokenIssuer {
+    private_key: [u8; NETCODE_KEY_BYTES], // Clé privée partagée avec le transport du serveur
+    protocol_id: u64,                     // Identifiant du protocole netcode
+    server_addresses: Vec<SocketAddr>,    // Adresses publiques du serveur de jeu
+}
+
+impl TokenIssuer {
+    /// Crée un émetteur de jetons
+    ///
+    /// # Arguments
+    /// * `private_key` - Clé privée partagée avec `ServerAuthentication::Secure`
+    /// * `protocol_id` - Identifiant du protocole netcode
+    /// * `server_addresses` - Adresses publiques auxquelles les clients se connecteront
+    pub fn new(
+        private_key: [u8; NETCODE_KEY_BYTES],
+        protocol_id: u64,
+        server_addresses: Vec<SocketAddr>,
+    ) -> Self {
+        Self {
+            private_key,
+            protocol_id,
+            server_addresses,
+        }
+    }
+
+    /// Génère un jeton pour un joueur, avec un identifiant client tiré au hasard par le serveur
+    ///
+    /// # Arguments
+    /// * `connect_data` - Version du protocole et nom d'utilisateur annoncés par le joueur
+    ///
+    /// # Returns
+    /// * `Result<ConnectToken, String>` - Le jeton signé, ou la raison lisible du refus
+    pub fn issue(&self, connect_data: &ConnectData) -> Result<ConnectToken, String> {
+        if connect_data.version != PROTOCOL_VERSION {
+            return Err(format!(
+                "version du protocole incompatible (client : {}, serveur : {PROTOCOL_VERSION}), \
+                 mettez à jour le jeu",
+                connect_data.version
+            ));
+        }
+        let user_data = connect_data
+            .to_user_data()
+            .map_err(|err| format!("nom d'utilisateur refusé : {err}"))?;
+
+        let current_time = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap();
+        ConnectToken::generate(
+            current_time,
+            self.protocol_id,
+            TOKEN_EXPIRE_SECONDS,
+            rand::random::<u64>(),
+            TOKEN_TIMEOUT_SECONDS,
+            self.server_addresses.clone(),
+            Some(&user_data),
+            &self.private_key,
+        )
+        .map_err(|err| format!("génération du jeton impossible : {err}"))
+    }
+}
+
+/// Démarre l'émetteur de jetons sur un thread dédié
+/// Requête attendue : `GET /token?version=<version>&username=<nom>`
+/// Chaque demande est traitée sur son propre thread, borné dans le temps : un client lent ou
+/// silencieux ne bloque pas les demandes suivantes. Au-delà de `MAX_CONCURRENT_REQUESTS`
+/// demandes en cours, les nouvelles sont refusées (503) sans créer de thread
+///
+/// # Arguments
+/// * `listen_addr` - Adresse d'écoute HTTP
+/// * `issuer` - Émetteur de jetons
+///
+/// # Returns
+/// * `std::io::Result<()>` - Erreur si l'adresse d'écoute n'a pas pu être liée
+pub fn spawn_token_server(listen_addr: SocketAddr, issuer: TokenIssuer) -> std::io::Result<()> {
+    let listener = TcpListener::bind(listen_addr)?;
+    println!("🔑 Émetteur de jetons à l'écoute sur http://{listen_addr}{TOKEN_PATH}");
+
+    let in_flight = Arc::new(AtomicUsize::new(0));
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    let Some(slot) = RequestSlot::acquire(&in_flight) else {
+                        if let Err(err) = reject_busy(stream) {
+                            eprintln!("⚠️ Refus d'une demande de jeton interrompu : {err}");
+                        }
+                        continue;
+                    };
+                    let issuer = issuer.clone();
+                    thread::spawn(move || {
+                        if let Err(err) = handle_request(stream, &issuer) {
+                            eprintln!("⚠️ Demande de jeton interrompue : {err}");
+                        }
+                        drop(slot);
+                    });
+                }
+                Err(err) => eprintln!("⚠️ Connexion à l'émetteur de jetons refusée : {err}"),
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Place réservée par une demande de jeton en cours, libérée à la fin de son traitement
+struct RequestSlot(Arc<AtomicUsize>);
+
+impl RequestSlot {
+    /// Réserve une place si moins de `MAX_CONCURRENT_REQUESTS` demandes sont en cours
+    ///
+    /// # Arguments
+    /// * `in_flight` - Compteur des demandes en cours
+    ///
+    /// # Returns
+    /// * `Option<RequestSlot>` - La place réservée, ou None si l'émetteur est saturé
+    fn acquire(in_flight: &Arc<AtomicUsize>) -> Option<Self> {
+        in_flight
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |count| {
+                (count < MAX_CONCURRENT_REQUESTS).then_some(count + 1)
+            })
+            .ok()
+            .map(|_| Self(Arc::clone(in_flight)))
+    }
+}
+
+impl Drop for RequestSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Refuse une demande de jeton quand l'émetteur est saturé
+///
+/// # Arguments
+/// * `stream` - Connexion TCP du client
+fn reject_busy(mut stream: TcpStream) -> std::io::Result<()> {
+    stream.set_write_timeout(Some(REQUEST_TIMEOUT))?;
+    write_response(
+        &mut stream,
+        503,
+        "Service Unavailable",
+        "text/plain; charset=utf-8",
+        "émetteur de jetons saturé, réessayez plus tard".as_bytes(),
+    )
+}
+
+/// Traite une demande de jeton et écrit la réponse HTTP
+///
+/// # Arguments
+/// * `stream` - Connexion TCP du client
+/// * `issuer` - Émetteur de jetons
+fn handle_request(mut stream: TcpStream, issuer: &TokenIssuer) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+    stream.set_write_timeout(Some(REQUEST_TIMEOUT))?;
+
+    // Seule la ligne de requête est lue, dans la limite de `MAX_REQUEST_LINE_BYTES`
+    let mut request_line = String::new();
+    BufReader::new((&stream).take(MAX_REQUEST_LINE_BYTES)).read_line(&mut request_line)?;
+
+    let response = match parse_token_request(&request_line) {
+        Ok(connect_data) => match issuer.issue(&connect_data) {
+            Ok(token) => {
+                let mut body = Vec::new();
+                token.write(&mut body)?;
+                println!("🔑 Jeton délivré à '{}'", connect_data.username);
+                (200, "OK", "application/octet-stream", body)
+            }
+            Err(reason) => (403, "Forbidden", "text/plain; charset=utf-8", reason.into_bytes()),
+        },
+        Err(reason) => (400, "Bad Request", "text/plain; charset=utf-8", reason.into_bytes()),
+    };
+
+    let (status, status_text, content_type, body) = response;
+    write_response(&mut stream, status, status_text, content_type, &body)
+}
+
+/// Écrit une réponse HTTP complète
+///
+/// # Arguments
+/// * `stream` - Connexion TCP du client
+/// * `status` - Code de statut HTTP
+/// * `status_text` - Libellé du statut
+/// * `content_type` - Type du contenu
+/// * `body` - Corps de la réponse
+fn write_response(
+    stream: &mut TcpStream,
+    status: u16,
+    status_text: &str,
+    content_type: &str,
+    body: &[u8],
+) -> std::io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.0 {status} {status_text}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        body.len()
+    )?;
+    stream.write_all(body)?;
+    stream.flush()
+}
+
+/// Extrait les données de connexion de la ligne de requête HTTP
+///
+/// # Arguments
+/// * `request_line` - Première ligne de la requête (ex: `GET /token?version=1&username=Bob HTTP/1.0`)
+///
+/// # Returns
+/// * `Result<ConnectData, String>` - Données de connexion, ou la raison lisible du refus
+fn parse_token_request(request_line: &str) -> Result<ConnectData, String> {
+    let mut parts = request_line.split_whitespace();
+    if parts.next() != Some("GET") {
+        return Err("seule la méthode GET est acceptée".to_string());
+    }
+    let target = parts.next().ok_or("requête incomplète")?;
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    if path != TOKEN_PATH {
+        return Err(format!("chemin inconnu : {path}"));
+    }
+
+    let mut version = None;
+    let mut username = None;
+    for pair in query.split('&') {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        match key {
+            "version" => version = value.parse::<u32>().ok(),
+            "username" => username = decode_query_value(value),
+            _ => {}
+        }
+    }
+
+    Ok(ConnectData {
+        version: version.ok_or("paramètre 'version' manquant ou invalide")?,
+        username: username.ok_or("paramètre 'username' manquant ou invalide")?,
+    })
+}
@@ -0,0 +1,612 @@
+// Instantanés numérotés de l'état des joueurs envoyés à chaque tick
+// Encodés champ par champ par rapport au dernier instantané acquitté par le client :
+// un joueur ou un champ inchangé n'occupe aucun octet
+
+// Import des modules standard pour les collections et l'affichage des erreurs
+use std::{collections::HashMap, fmt};
+
+// Import des modules Bevy pour les mathématiques
+use bevy::math::{EulerRot, Quat, Vec3};
+// Import de renet pour l'identification des joueurs
+use renet::ClientId;
+
+// Import des structures de données partagées
+use crate::{PlayerAttributes, PlayerLobby, Weapon};
+
+/// Nombre de pas de quantification par unité de position (précision de 1/64 d'unité)
+pub const POSITION_SCALE: f32 = 64.0;
+/// Nombre d'instantanés conservés pour servir de référence aux différentiels
+pub const SNAPSHOT_HISTORY: usize = 64;
+
+/// Champ modifié : position
+const FIELD_POSITION: u8 = 1 << 0;
+/// Champ modifié : orientation horizontale
+const FIELD_YAW: u8 = 1 << 1;
+/// Champ modifié : points de vie
+const FIELD_HEALTH: u8 = 1 << 2;
+/// Champ modifié : points d'armure
+const FIELD_ARMOR: u8 = 1 << 3;
+/// Champ modifié : arme équipée
+const FIELD_WEAPON: u8 = 1 << 4;
+/// Champ modifié : posture (debout ou accroupi)
+const FIELD_STANCE: u8 = 1 << 5;
+/// Tous les champs (joueur absent de la référence)
+const ALL_FIELDS: u8 =
+    FIELD_POSITION | FIELD_YAW | FIELD_HEALTH | FIELD_ARMOR | FIELD_WEAPON | FIELD_STANCE;
+
+/// Erreurs possibles lors du décodage d'un instantané
+#[derive(Debug, Clone, PartialEq)]
+pub enum SnapshotError {
+    Truncated,             // Le message se termine avant la fin des données annoncées
+    MissingBaseline(u32),  // L'instantané de référence n'est plus connu du client
+    UnknownWeapon(u8),     // Code d'arme invalide
+    UnknownFields(u8),     // Masque de champs invalide
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SnapshotError::Truncated => write!(f, "instantané tronqué"),
+            SnapshotError::MissingBaseline(tick) => {
+                write!(f, "instantané de référence {tick} inconnu")
+            }
+            SnapshotError::UnknownWeapon(code) => write!(f, "code d'arme inconnu : {code}"),
+            SnapshotError::UnknownFields(mask) => write!(f, "masque de champs inconnu : {mask:#04x}"),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+/// État répliqué d'un joueur, déjà quantifié
+/// Deux états égaux produisent exactement les mêmes octets, ce qui rend la comparaison fiable
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PlayerState {
+    pub position: [i16; 3], // Position en 1/64 d'unité
+    pub yaw: u16,           // Orientation horizontale sur un tour complet
+    pub health: u16,        // Points de vie arrondis
+    pub armor: u16,         // Points d'armure arrondis
+    pub weapon: Weapon,     // Arme équipée
+    pub crouched: bool,     // Joueur accroupi
+}
+
+impl PlayerState {
+    /// Quantifie les attributs répliqués d'un joueur
+    ///
+    /// # Arguments
+    /// * `attr` - Attributs complets du joueur côté serveur
+    pub fn from_attributes(attr: &PlayerAttributes) -> Self {
+        let position = Vec3::from(attr.position);
+        let (yaw, _, _) = attr.rotation.to_euler(EulerRot::YXZ);
+        Self {
+            position: [
+                quantize_coordinate(position.x),
+                quantize_coordinate(position.y),
+                quantize_coordinate(position.z),
+            ],
+            yaw: (yaw.rem_euclid(std::f32::consts::TAU) / std::f32::consts::TAU
+                * u16::MAX as f32)
+                .round() as u16,
+            health: attr.health.round().clamp(0.0, u16::MAX as f32) as u16,
+            armor: attr.armor.round().clamp(0.0, u16::MAX as f32) as u16,
+            weapon: attr.actual_weapon,
+            crouched: attr.crouched,
+        }
+    }
+
+    /// Position du joueur en unités du monde
+    pub fn position(&self) -> Vec3 {
+        Vec3::new(
+            self.position[0] as f32,
+            self.position[1] as f32,
+            self.position[2] as f32,
+        ) / POSITION_SCALE
+    }
+
+    /// Rotation du joueur reconstruite à partir de l'orientation horizontale
+    pub fn rotation(&self) -> Quat {
+        Quat::from_rotation_y(self.yaw as f32 / u16::MAX as f32 * std::f32::consts::TAU)
+    }
+
+    /// Reporte l'état répliqué sur des attributs existants
+    /// Les champs non répliqués (munitions, armes possédées, nom) sont conservés
+    ///
+    /// # Arguments
+    /// * `attr` - Attributs à mettre à jour
+    pub fn apply_to(&self, attr: &mut PlayerAttributes) {
+        attr.position = self.position().into();
+        attr.rotation = self.rotation();
+        attr.health = self.health as f32;
+        attr.armor = self.armor as f32;
+        attr.actual_weapon = self.weapon;
+        attr.crouched = self.crouched;
+    }
+
+    /// Crée les attributs d'un joueur distant à partir de son seul état répliqué
+    pub fn to_attributes(&self) -> PlayerAttributes {
+        PlayerAttributes {
+            username: String::new(),
+            position: self.position().into(),
+            rotation: self.rotation(),
+            health: self.health as f32,
+            armor: self.armor as f32,
+            velocity: Vec3::ZERO,
+            owned_weapon: HashMap::new(),
+            actual_weapon: self.weapon,
+            magazine: HashMap::new(),
+            ammo: HashMap::new(),
+            crouched: self.crouched,
+            entities: HashMap::new(),
+        }
+    }
+
+    /// Masque des champs qui diffèrent de l'état de référence
+    fn changed_fields(&self, baseline: &PlayerState) -> u8 {
+        let mut fields = 0;
+        if self.position != baseline.position {
+            fields |= FIELD_POSITION;
+        }
+        if self.yaw != baseline.yaw {
+            fields |= FIELD_YAW;
+        }
+        if self.health != baseline.health {
+            fields |= FIELD_HEALTH;
+        }
+        if self.armor != baseline.armor {
+            fields |= FIELD_ARMOR;
+        }
+        if self.weapon != baseline.weapon {
+            fields |= FIELD_WEAPON;
+        }
+        if self.crouched != baseline.crouched {
+            fields |= FIELD_STANCE;
+        }
+        fields
+    }
+}
+
+/// État de tous les joueurs à un tick serveur donné
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Snapshot {
+    pub tick: u32,                              // Tick serveur de l'instantané
+    pub players: HashMap<ClientId, PlayerState>, // État répliqué de chaque joueur
+}
+
+impl Snapshot {
+    /// Capture l'état répliqué du lobby
+    ///
+    /// # Arguments
+    /// * `tick` - Tick serveur courant
+    /// * `lobby` - Lobby des joueurs
+    pub fn from_lobby(tick: u32, lobby: &PlayerLobby) -> Self {
+        Self {
+            tick,
+            players: lobby
+                .0
+                .iter()
+                .map(|(client_id, attr)| (*client_id, PlayerState::from_attributes(attr)))
+                .collect(),
+        }
+    }
+
+    /// Encode l'instantané en différentiel par rapport à une référence
+    /// Sans référence, l'instantané est encodé en entier
+    ///
+    /// Format (petit-boutiste) : tick u32, présence de la référence u8 (+ tick u32),
+    /// nombre de joueurs modifiés u16, puis pour chacun id u64, masque u8 et champs présents,
+    /// enfin nombre de joueurs retirés u16 suivi de leurs ids u64
+    ///
+    /// # Arguments
+    /// * `baseline` - Dernier instantané acquitté par le destinataire
+    ///
+    /// # Returns
+    /// * `Vec<u8>` - Octets à envoyer
+    pub fn encode_delta(&self, baseline: Option<&Snapshot>) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&self.tick.to_le_bytes());
+        match baseline {
+            Some(baseline) => {
+                bytes.push(1);
+                bytes.extend_from_slice(&baseline.tick.to_le_bytes());
+            }
+            None => bytes.push(0),
+        }
+
+        // Joueurs nouveaux ou modifiés
+        let changed: Vec<(ClientId, u8, &PlayerState)> = self
+            .players
+            .iter()
+            .filter_map(|(client_id, state)| {
+                let fields = match baseline.and_then(|b| b.players.get(client_id)) {
+                    Some(previous) => state.changed_fields(previous),
+                    None => ALL_FIELDS,
+                };
+                (fields != 0).then_some((*client_id, fields, state))
+            })
+            .collect();
+        bytes.extend_from_slice(&(changed.len() as u16).to_le_bytes());
+        for (client_id, fields, state) in changed {
+            bytes.extend_from_slice(&client_id.raw().to_le_bytes());
+            bytes.push(fields);
+            if fields & FIELD_POSITION != 0 {
+                for coordinate in state.position {
+                    bytes.extend_from_slice(&coordinate.to_le_bytes());
+                }
+            }
+            if fields & FIELD_YAW != 0 {
+                bytes.extend_from_slice(&state.yaw.to_le_bytes());
+            }
+            if fields & FIELD_HEALTH != 0 {
+                bytes.extend_from_slice(&state.health.to_le_bytes());
+            }
+            if fields & FIELD_ARMOR != 0 {
+                bytes.extend_from_slice(&state.armor.to_le_bytes());
+            }
+            if fields & FIELD_WEAPON != 0 {
+                bytes.push(weapon_code(state.weapon));
+            }
+            if fields & FIELD_STANCE != 0 {
+                bytes.push(state.crouched as u8);
+            }
+        }
+
+        // Joueurs présents dans la référence mais plus dans l'instantané
+        let removed: Vec<ClientId> = baseline
+            .map(|baseline| {
+                baseline
+                    .players
+                    .keys()
+                    .filter(|client_id| !self.players.contains_key(client_id))
+                    .copied()
+                    .collect()
+            })
+            .unwrap_or_default();
+        bytes.extend_from_slice(&(removed.len() as u16).to_le_bytes());
+        for client_id in removed {
+            bytes.extend_from_slice(&client_id.raw().to_le_bytes());
+        }
+
+        bytes
+    }
+
+    /// Décode un instantané différentiel
+    ///
+    /// # Arguments
+    /// * `bytes` - Octets reçus du serveur
+    /// * `baseline` - Recherche d'un instantané déjà reçu à partir de son tick
+    ///
+    /// # Returns
+    /// * `Result<Snapshot, SnapshotError>` - L'instantané complet reconstruit
+    pub fn decode_delta<'a>(
+        bytes: &[u8],
+        baseline: impl FnOnce(u32) -> Option<&'a Snapshot>,
+    ) -> Result<Snapshot, SnapshotError> {
+        let mut reader = Reader { bytes, position: 0 };
+        let tick = reader.u32()?;
+
+        // Point de départ : la référence annoncée, ou un instantané vide
+        let mut players = match reader.u8()? {
+            0 => HashMap::new(),
+            _ => {
+                let baseline_tick = reader.u32()?;
+                baseline(baseline_tick)
+                    .ok_or(SnapshotError::MissingBaseline(baseline_tick))?
+                    .players
+                    .clone()
+            }
+        };
+
+        // Application des champs modifiés
+        for _ in 0..reader.u16()? {
+            let client_id = ClientId::from_raw(reader.u64()?);
+            let fields = reader.u8()?;
+            if fields & !ALL_FIELDS != 0 {
+                return Err(SnapshotError::UnknownFields(fields));
+            }
+
+            let mut state = match players.get(&client_id) {
+                Some(state) => *state,
+                None if fields == ALL_FIELDS => PlayerState {
+                    position: [0; 3],
+                    yaw: 0,
+                    health: 0,
+                    armor: 0,
+                    weapon: Weapon::Gun,
+                    crouched: false,
+                },
+                // Un joueur inconnu de la référence doit être envoyé en entier
+                None => return Err(SnapshotError::UnknownFields(fields)),
+            };
+            if fields & FIELD_POSITION != 0 {
+                state.position = [reader.i16()?, reader.i16()?, reader.i16()?];
+            }
+            if fields & FIELD_YAW != 0 {
+                state.yaw = reader.u16()?;
+            }
+            if fields & FIELD_HEALTH != 0 {
+                state.health = reader.u16()?;
+            }
+            if fields & FIELD_ARMOR != 0 {
+                state.armor = reader.u16()?;
+            }
+            if fields & FIELD_WEAPON != 0 {
+                let code = reader.u8()?;
+                state.weapon = weapon_from_code(code).ok_or(SnapshotError::UnknownWeapon(code))?;
+            }
+            if fields & FIELD_STANCE != 0 {
+                state.crouched = reader.u8()? != 0;
+            }
+            players.insert(client_id, state);
+        }
+
+        // Retrait des joueurs partis
+        for _ in 0..reader.u16()? {
+            players.remove(&ClientId::from_raw(reader.u64()?));
+        }
+
+        Ok(Snapshot { tick, players })
+    }
+}
+
+/// Quantifie une coordonnée en 1/64 d'unité (portée de ±512 unités)
+fn quantize_coordinate(value: f32) -> i16 {
+    (value * POSITION_SCALE)
+        .round()
+        .clamp(i16::MIN as f32, i16::MAX as f32) as i16
+}
+
+/// Code sur un octet d'une arme
+fn weapon_code(weapon: Weapon) -> u8 {
+    match weapon {
+        Weapon::Gun => 0,
+        Weapon::Shotgun => 1,
+        Weapon::Gatling => 2,
+        Weapon::RocketLauncher => 3,
+        Weapon::Bfg => 4,
+    }
+}
+
+/// Arme correspondant à un code sur un octet
+fn weapon_from_code(code: u8) -> Option<Weapon> {
+    match code {
+        0 => Some(Weapon::Gun),
+        1 => Some(Weapon::Shotgun),
+        2 => Some(Weapon::Gatling),
+        3 => Some(Weapon::RocketLauncher),
+        4 => Some(Weapon::Bfg),
+        _ => None,
+    }
+}
+
+/// Lecteur séquentiel d'octets petit-boutistes
+struct Reader<'a> {
+    bytes: &'a [u8], // Octets à lire
+    position: usize, // Position de lecture courante
+}
+
+impl Reader<'_> {
+    /// Lit les `N` octets suivants
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], SnapshotError> {
+        let end = self.position + N;
+        let slice = self
+            .bytes
+            .get(self.position..end)
+            .ok_or(SnapshotError::Truncated)?;
+        self.position = end;
+        Ok(slice.try_into().expect("slice of N bytes"))
+    }
+
+    fn u8(&mut self) -> Result<u8, SnapshotError> {
+        Ok(self.take::<1>()?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, SnapshotError> {
+        Ok(u16::from_le_bytes(self.take()?))
+    }
+
+    fn i16(&mut self) -> Result<i16, SnapshotError> {
+        Ok(i16::from_le_bytes(self.take()?))
+    }
+
+    fn u32(&mut self) -> Result<u32, SnapshotError> {
+        Ok(u32::from_le_bytes(self.take()?))
+    }
+
+    fn u64(&mut self) -> Result<u64, SnapshotError> {
+        Ok(u64::from_le_bytes(self.take()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Longueur d'un instantané sans aucun joueur modifié ni retiré, référence comprise
+    const EMPTY_DELTA_LEN: usize = 4 + 1 + 4 + 2 + 2;
+
+    /// État répliqué d'un joueur pour les tests
+    fn state(x: i16, health: u16, weapon: Weapon) -> PlayerState {
+        PlayerState {
+            position: [x, 128, -x],
+            yaw: 1000,
+            health,
+            armor: 25,
+            weapon,
+            crouched: false,
+        }
+    }
+
+    /// Instantané d'un tick donné contenant les joueurs fournis
+    fn snapshot(tick: u32, players: &[(u64, PlayerState)]) -> Snapshot {
+        Snapshot {
+            tick,
+            players: players
+                .iter()
+                .map(|(id, state)| (ClientId::from_raw(*id), *state))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn full_snapshot_round_trips() {
+        let full = snapshot(
+            7,
+            &[
+                (1, state(10, 100, Weapon::Gun)),
+                (2, state(-300, 42, Weapon::Bfg)),
+            ],
+        );
+        let bytes = full.encode_delta(None);
+        let decoded = Snapshot::decode_delta(&bytes, |_| None).expect("instantané complet");
+        assert_eq!(decoded, full);
+    }
+
+    #[test]
+    fn delta_snapshot_round_trips() {
+        let baseline = snapshot(
+            10,
+            &[
+                (1, state(10, 100, Weapon::Gun)),
+                (2, state(20, 100, Weapon::Shotgun)),
+            ],
+        );
+        // Joueur 1 modifié et accroupi, joueur 2 parti, joueur 3 arrivé
+        let mut crouching = state(11, 80, Weapon::Gatling);
+        crouching.crouched = true;
+        let current = snapshot(12, &[(1, crouching), (3, state(-5, 100, Weapon::Gun))]);
+        let bytes = current.encode_delta(Some(&baseline));
+        let decoded = Snapshot::decode_delta(&bytes, |tick| (tick == 10).then_some(&baseline))
+            .expect("instantané différentiel");
+        assert_eq!(decoded, current);
+    }
+
+    #[test]
+    fn unchanged_players_encode_to_nothing() {
+        let baseline = snapshot(
+            3,
+            &[
+                (1, state(10, 100, Weapon::Gun)),
+                (2, state(20, 50, Weapon::Bfg)),
+            ],
+        );
+        let current = Snapshot {
+            tick: 4,
+            ..baseline.clone()
+        };
+        let bytes = current.encode_delta(Some(&baseline));
+        assert_eq!(bytes.len(), EMPTY_DELTA_LEN);
+        let decoded = Snapshot::decode_delta(&bytes, |_| Some(&baseline)).unwrap();
+        assert_eq!(decoded, current);
+    }
+
+    #[test]
+    fn only_changed_fields_are_sent() {
+        let baseline = snapshot(1, &[(1, state(10, 100, Weapon::Gun))]);
+        let mut moved = state(10, 100, Weapon::Gun);
+        moved.health = 90;
+        let current = snapshot(2, &[(1, moved)]);
+        let bytes = current.encode_delta(Some(&baseline));
+        // Id du joueur, masque et points de vie seulement
+        assert_eq!(bytes.len(), EMPTY_DELTA_LEN + 8 + 1 + 2);
+    }
+
+    #[test]
+    fn quantization_error_is_bounded() {
+        use std::f32::consts::TAU;
+
+        let max_position_error = 0.5 / POSITION_SCALE + 1e-4;
+        let max_yaw_error = 0.5 / u16::MAX as f32 * TAU + 1e-4;
+        let mut attr = state(0, 100, Weapon::Gun).to_attributes();
+        for step in 0..200 {
+            let value = step as f32 * 4.987 - 495.0;
+            let yaw = step as f32 * 0.0731 - 7.0;
+            attr.position = (value, value * 0.1, -value * 0.7);
+            attr.rotation = Quat::from_rotation_y(yaw);
+
+            let quantized = PlayerState::from_attributes(&attr);
+            let error = quantized.position() - Vec3::from(attr.position);
+            assert!(
+                error.abs().max_element() <= max_position_error,
+                "{value} : {error}"
+            );
+            let decoded = quantized.yaw as f32 / u16::MAX as f32 * TAU;
+            let difference = (decoded - yaw).rem_euclid(TAU);
+            let error = difference.min(TAU - difference);
+            assert!(error <= max_yaw_error, "{yaw} : {error}");
+        }
+    }
+
+    #[test]
+    fn truncated_snapshots_are_rejected() {
+        let baseline = snapshot(1, &[(1, state(10, 100, Weapon::Gun))]);
+        let current = snapshot(
+            2,
+            &[
+                (1, state(12, 100, Weapon::Gun)),
+                (2, state(0, 100, Weapon::Bfg)),
+            ],
+        );
+        let bytes = current.encode_delta(Some(&baseline));
+        for length in 0..bytes.len() {
+            assert_eq!(
+                Snapshot::decode_delta(&bytes[..length], |_| Some(&baseline)),
+                Err(SnapshotError::Truncated),
+                "{length} octets"
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_snapshots_are_rejected() {
+        let full = snapshot(5, &[(1, state(10, 100, Weapon::Gun))]).encode_delta(None);
+        let mask = 4 + 1 + 2 + 8;
+
+        // Référence inconnue du client
+        let baseline = snapshot(4, &[]);
+        let bytes = snapshot(5, &[]).encode_delta(Some(&baseline));
+        assert_eq!(
+            Snapshot::decode_delta(&bytes, |_| None),
+            Err(SnapshotError::MissingBaseline(4))
+        );
+
+        // Masque de champs invalide
+        let mut bytes = full.clone();
+        bytes[mask] = 0xff;
+        assert_eq!(
+            Snapshot::decode_delta(&bytes, |_| None),
+            Err(SnapshotError::UnknownFields(0xff))
+        );
+
+        // Joueur inconnu envoyé partiellement
+        let mut bytes = full.clone();
+        bytes[mask] = FIELD_HEALTH;
+        assert_eq!(
+            Snapshot::decode_delta(&bytes, |_| None),
+            Err(SnapshotError::UnknownFields(FIELD_HEALTH))
+        );
+
+        // Code d'arme invalide (avant la posture et le nombre de joueurs retirés)
+        let mut bytes = full;
+        let weapon = bytes.len() - 4;
+        bytes[weapon] = 200;
+        assert_eq!(
+            Snapshot::decode_delta(&bytes, |_| None),
+            Err(SnapshotError::UnknownWeapon(200))
+        );
+    }
+
+    #[test]
+    fn garbage_never_panics() {
+        let baseline = snapshot(1, &[(1, state(10, 100, Weapon::Gun))]);
+        let mut seed: u32 = 0x1234_5678;
+        for length in 0..256 {
+            let bytes: Vec<u8> = (0..length)
+                .map(|_| {
+                    seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                    (seed >> 24) as u8
+                })
+                .collect();
+            let _ = Snapshot::decode_delta(&bytes, |_| Some(&baseline));
+        }
+    }
+}
@@ -0,0 +1,547 @@
+// Définitions des armes lues depuis un fichier RON partagé par le serveur et le client
+// Dégâts, cadence, dispersion, portée, munitions, projectile, modèle et icône de chaque arme
+// se règlent sans recompiler le jeu
+
+// Import des modules standard pour les collections, l'affichage et les fichiers
+use std::{
+    collections::HashMap,
+    fmt,
+    path::{Path, PathBuf},
+};
+
+// Import des modules Bevy pour la définition des ressources et les mathématiques
+use bevy::{ecs::system::Resource, math::Vec3};
+// Import de serde pour la lecture du fichier
+use serde::{Deserialize, Serialize};
+
+// Import des structures de données partagées
+use crate::Weapon;
+
+/// Fichier des définitions d'armes lu par défaut (chemins des modèles et icônes relatifs à `assets/`)
+pub const DEFAULT_WEAPONS_PATH: &str = "assets/weapons.ron";
+
+/// Nombre maximal de plombs par tir (borne le coût de résolution d'un tir sur le serveur)
+pub const MAX_PELLETS: u32 = 32;
+
+/// Projectile tiré par une arme
+#[derive(Eq, Hash, PartialEq, Copy, Clone, Debug, Serialize, Deserialize)]
+pub enum ProjectileKind {
+    Hitscan,    // Tir instantané (rayon)
+    Rocket,     // Roquette explosive
+    EnergyBall, // Boule d'énergie
+}
+
+impl ProjectileKind {
+    /// Indique si le projectile est simulé par le serveur (vitesse, trajet et explosion)
+    /// plutôt que résolu instantanément
+    pub fn is_simulated(self) -> bool {
+        !matches!(self, ProjectileKind::Hitscan)
+    }
+}
+
+/// Mode de tir d'une arme
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum FireMode {
+    /// Un tir par pression de la gâchette
+    #[default]
+    SemiAuto,
+    /// Tirs continus tant que la gâchette est maintenue
+    FullAuto,
+    /// Tirs continus après une mise en rotation de `spin_up_secs` gâchette maintenue
+    SpinUp { spin_up_secs: f32 },
+    /// Tir au relâchement de la gâchette, maintenue au moins `charge_secs`
+    Charge { charge_secs: f32 },
+}
+
+impl FireMode {
+    /// Durée pendant laquelle la gâchette doit être maintenue avant de pouvoir tirer
+    ///
+    /// # Returns
+    /// * `Option<f32>` - Durée en secondes, ou None si l'arme tire dès la pression
+    pub fn wind_up(&self) -> Option<f32> {
+        match self {
+            FireMode::SemiAuto | FireMode::FullAuto => None,
+            FireMode::SpinUp { spin_up_secs } => Some(*spin_up_secs),
+            FireMode::Charge { charge_secs } => Some(*charge_secs),
+        }
+    }
+}
+
+/// Atténuation des dégâts avec la distance
+/// Dégâts pleins jusqu'à `start`, réduits linéairement jusqu'à `min_factor` à `end` et au-delà
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Falloff {
+    pub start: f32,      // Distance où l'atténuation commence
+    pub end: f32,        // Distance où l'atténuation est maximale
+    pub min_factor: f32, // Part des dégâts conservée au-delà de `end` (0 à 1)
+}
+
+impl Falloff {
+    /// Part des dégâts conservée à une distance donnée
+    ///
+    /// # Arguments
+    /// * `distance` - Distance entre le tireur et l'impact
+    pub fn factor(&self, distance: f32) -> f32 {
+        if distance <= self.start {
+            return 1.0;
+        }
+        if distance >= self.end {
+            return self.min_factor;
+        }
+        let t = (distance - self.start) / (self.end - self.start);
+        1.0 + (self.min_factor - 1.0) * t
+    }
+}
+
+/// Explosion d'un projectile
+/// Les dégâts de l'arme s'appliquent en entier au centre et diminuent linéairement jusqu'au bord
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Splash {
+    pub radius: f32,      // Rayon de l'explosion
+    pub min_factor: f32,  // Part des dégâts conservée au bord du rayon (0 à 1)
+    pub self_damage: f32, // Part des dégâts subie par le tireur lui-même (0 à 1)
+    pub knockback: f32,   // Vitesse donnée au centre de l'explosion, réduite comme les dégâts
+}
+
+impl Splash {
+    /// Part des dégâts (et du souffle) reçue à une distance du centre de l'explosion
+    ///
+    /// # Arguments
+    /// * `distance` - Distance entre le centre de l'explosion et le joueur
+    ///
+    /// # Returns
+    /// * `Option<f32>` - La part reçue, ou None hors du rayon
+    pub fn factor(&self, distance: f32) -> Option<f32> {
+        if distance > self.radius {
+            return None;
+        }
+        Some(1.0 + (self.min_factor - 1.0) * distance / self.radius)
+    }
+}
+
+/// Zone d'effet entourant un projectile pendant son trajet (boule d'énergie)
+/// Chaque joueur à portée et en vue du projectile subit des dégâts continus, le tireur excepté
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Aura {
+    pub radius: f32,            // Rayon de la zone autour du projectile
+    pub damage_per_second: f32, // Dégâts infligés par seconde passée dans la zone
+}
+
+/// Générateur pseudo-aléatoire de la dispersion des plombs (SplitMix64)
+/// L'algorithme est écrit ici plutôt que pris dans `rand`, dont les générateurs peuvent changer
+/// d'une version à l'autre : le client et le serveur doivent tirer les mêmes plombs d'une graine
+struct PelletRng(u64);
+
+impl PelletRng {
+    /// Entier suivant de la suite
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Réel suivant, uniforme dans [0, 1) (24 bits, représentés exactement en f32)
+    fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u32 << 24) as f32
+    }
+}
+
+/// Caractéristiques d'une arme
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct WeaponDefinition {
+    pub damage: f32,                 // Dégâts d'un projectile (ou d'un plomb) touchant un joueur
+    pub fire_rate: f32,              // Tirs par seconde
+    #[serde(default)]
+    pub fire_mode: FireMode,         // Mode de tir (coup par coup par défaut)
+    pub spread: f32,                 // Demi-angle de dispersion des plombs, en degrés
+    pub pellets: u32,                // Nombre de plombs par tir
+    pub range: f32,                  // Portée maximale
+    #[serde(default)]
+    pub falloff: Option<Falloff>,    // Atténuation des dégâts avec la distance (aucune par défaut)
+    pub magazine_size: f32,          // Munitions par chargeur
+    pub reload_secs: f32,            // Durée d'un rechargement, pendant laquelle l'arme ne tire pas
+    pub max_ammo: f32,               // Munitions maximum en réserve (hors chargeur)
+    pub starting_ammo: f32,          // Munitions à l'apparition (chargeur compris)
+    pub kill_refund: f32,            // Munitions rendues au tireur pour un frag
+    pub projectile: ProjectileKind,  // Projectile tiré
+    #[serde(default)]
+    pub speed: f32,                  // Vitesse des projectiles simulés (roquettes, boules d'énergie)
+    #[serde(default)]
+    pub splash: Option<Splash>,      // Explosion des projectiles simulés (aucune par défaut)
+    #[serde(default)]
+    pub aura: Option<Aura>,          // Zone d'effet le long du trajet (aucune par défaut)
+    pub model: String,               // Modèle 3D de l'arme (dans `assets/`)
+    pub icon: String,                // Icône du HUD (dans `assets/`)
+}
+
+impl WeaponDefinition {
+    /// Dégâts d'un projectile à une distance donnée, atténuation comprise
+    ///
+    /// # Arguments
+    /// * `distance` - Distance entre le tireur et l'impact
+    pub fn damage_at(&self, distance: f32) -> f32 {
+        self.damage * self.falloff.map_or(1.0, |falloff| falloff.factor(distance))
+    }
+
+    /// Indique si un rechargement est utile : chargeur incomplet et munitions en réserve
+    ///
+    /// # Arguments
+    /// * `magazine` - Munitions dans le chargeur
+    /// * `reserve` - Munitions en réserve
+    pub fn can_reload(&self, magazine: f32, reserve: f32) -> bool {
+        magazine < self.magazine_size && reserve >= 1.0
+    }
+
+    /// Recharge l'arme : le chargeur est complété avec les munitions de la réserve
+    ///
+    /// # Arguments
+    /// * `magazine` - Munitions dans le chargeur
+    /// * `reserve` - Munitions en réserve
+    pub fn reload(&self, magazine: &mut f32, reserve: &mut f32) {
+        let loaded = (self.magazine_size - *magazine).min(*reserve).max(0.0);
+        *magazine += loaded;
+        *reserve -= loaded;
+    }
+
+    /// Directions des plombs d'un tir, dispersés dans un cône autour de la direction visée
+    /// Une même graine donne toujours les mêmes directions : le serveur retrouve ainsi les plombs
+    /// tirés par le client
+    ///
+    /// # Arguments
+    /// * `aim` - Direction visée (normalisée)
+    /// * `seed` - Graine du tir, transmise dans le message de tir
+    ///
+    /// # Returns
+    /// * `Vec<Vec3>` - Une direction normalisée par plomb
+    pub fn pellet_directions(&self, aim: Vec3, seed: u32) -> Vec<Vec3> {
+        if self.spread <= 0.0 {
+            return vec![aim; self.pellets as usize];
+        }
+        let mut rng = PelletRng(seed as u64);
+        let (right, up) = aim.any_orthonormal_pair();
+        let max_angle = self.spread.to_radians();
+        (0..self.pellets)
+            .map(|_| {
+                // Répartition uniforme sur le disque de dispersion
+                let angle = max_angle * rng.next_f32().sqrt();
+                let around = std::f32::consts::TAU * rng.next_f32();
+                let offset = right * around.cos() + up * around.sin();
+                (aim * angle.cos() + offset * angle.sin()).normalize()
+            })
+            .collect()
+    }
+
+    /// Vérifie la cohérence de la définition
+    ///
+    /// # Arguments
+    /// * `weapon` - Arme décrite (pour les messages)
+    /// * `errors` - Liste complétée par les erreurs trouvées
+    fn validate(&self, weapon: Weapon, errors: &mut Vec<String>) {
+        for (field, value) in [
+            ("damage", self.damage),
+            ("spread", self.spread),
+            ("reload_secs", self.reload_secs),
+            ("max_ammo", self.max_ammo),
+            ("starting_ammo", self.starting_ammo),
+            ("kill_refund", self.kill_refund),
+        ] {
+            if !(value >= 0.0 && value.is_finite()) {
+                errors.push(format!("{weapon}.{field} : {value} doit être positif"));
+            }
+        }
+        for (field, value) in [("fire_rate", self.fire_rate), ("range", self.range)] {
+            if !(value > 0.0 && value.is_finite()) {
+                errors.push(format!("{weapon}.{field} : {value} doit être strictement positif"));
+            }
+        }
+        if !(self.magazine_size >= 1.0 && self.magazine_size.is_finite()) {
+            errors.push(format!(
+                "{weapon}.magazine_size : {} doit valoir au moins 1",
+                self.magazine_size
+            ));
+        }
+        if let Some(wind_up) = self.fire_mode.wind_up() {
+            if !(wind_up > 0.0 && wind_up.is_finite()) {
+                errors.push(format!(
+                    "{weapon}.fire_mode : durée de préparation {wind_up} doit être strictement positive"
+                ));
+            }
+        }
+        if self.spread >= 90.0 {
+            errors.push(format!("{weapon}.spread : {} doit rester sous 90°", self.spread));
+        }
+        if self.pellets == 0 || self.pellets > MAX_PELLETS {
+            errors.push(format!(
+                "{weapon}.pellets : {} hors de l'intervalle 1..={MAX_PELLETS}",
+                self.pellets
+            ));
+        }
+        // Les munitions de départ remplissent le chargeur puis la réserve
+        if self.starting_ammo > self.max_ammo + self.magazine_size {
+            errors.push(format!(
+                "{weapon}.starting_ammo : {} dépasse max_ammo + magazine_size ({})",
+                self.starting_ammo,
+                self.max_ammo + self.magazine_size
+            ));
+        }
+        if let Some(falloff) = &self.falloff {
+            if !(falloff.start >= 0.0 && falloff.start < falloff.end) {
+                errors.push(format!(
+                    "{weapon}.falloff : start ({}) doit être positif et inférieur à end ({})",
+                    falloff.start, falloff.end
+                ));
+            }
+            if !(0.0..=1.0).contains(&falloff.min_factor) {
+                errors.push(format!(
+                    "{weapon}.falloff.min_factor : {} hors de l'intervalle 0..=1",
+                    falloff.min_factor
+                ));
+            }
+        }
+        if self.projectile.is_simulated() {
+            if !(self.speed > 0.0 && self.speed.is_finite()) {
+                errors.push(format!(
+                    "{weapon}.speed : {} doit être strictement positif pour un projectile simulé",
+                    self.speed
+                ));
+            }
+            if self.splash.is_none() {
+                errors.push(format!(
+                    "{weapon}.splash : explosion nécessaire pour un projectile simulé"
+                ));
+            }
+        }
+        if self.projectile == ProjectileKind::EnergyBall && self.aura.is_none() {
+            errors.push(format!(
+                "{weapon}.aura : zone d'effet nécessaire pour une boule d'énergie"
+            ));
+        }
+        if self.aura.is_some() && !self.projectile.is_simulated() {
+            errors.push(format!("{weapon}.aura : réservée aux projectiles simulés"));
+        }
+        if let Some(splash) = &self.splash {
+            if !(splash.radius > 0.0 && splash.radius.is_finite()) {
+                errors.push(format!(
+                    "{weapon}.splash.radius : {} doit être strictement positif",
+                    splash.radius
+                ));
+            }
+            for (field, value) in [
+                ("min_factor", splash.min_factor),
+                ("self_damage", splash.self_damage),
+            ] {
+                if !(0.0..=1.0).contains(&value) {
+                    errors.push(format!(
+                        "{weapon}.splash.{field} : {value} hors de l'intervalle 0..=1"
+                    ));
+                }
+            }
+            if !(splash.knockback >= 0.0 && splash.knockback.is_finite()) {
+                errors.push(format!(
+                    "{weapon}.splash.knockback : {} doit être positif",
+                    splash.knockback
+                ));
+            }
+        }
+        if let Some(aura) = &self.aura {
+            if !(aura.radius > 0.0 && aura.radius.is_finite()) {
+                errors.push(format!(
+                    "{weapon}.aura.radius : {} doit être strictement positif",
+                    aura.radius
+                ));
+            }
+            if !(aura.damage_per_second >= 0.0 && aura.damage_per_second.is_finite()) {
+                errors.push(format!(
+                    "{weapon}.aura.damage_per_second : {} doit être positif",
+                    aura.damage_per_second
+                ));
+            }
+        }
+        for (field, value) in [("model", &self.model), ("icon", &self.icon)] {
+            if value.trim().is_empty() {
+                errors.push(format!("{weapon}.{field} : chemin vide"));
+            }
+        }
+    }
+}
+
+/// Erreurs possibles lors de la lecture des définitions d'armes
+#[derive(Debug)]
+pub enum WeaponsError {
+    /// Fichier illisible
+    Io {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    /// Fichier mal formé
+    Parse(ron::error::SpannedError),
+    /// Définitions manquantes ou incohérentes (toutes les erreurs trouvées)
+    Invalid(Vec<String>),
+}
+
+impl fmt::Display for WeaponsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WeaponsError::Io { path, error } => {
+                write!(f, "lecture des armes {} impossible : {error}", path.display())
+            }
+            WeaponsError::Parse(error) => write!(f, "définitions d'armes mal formées : {error}"),
+            WeaponsError::Invalid(errors) => {
+                write!(f, "définitions d'armes invalides :")?;
+                for error in errors {
+                    write!(f, "\n  - {error}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for WeaponsError {}
+
+/// Ressource contenant la définition de chaque arme
+/// Toutes les armes sont décrites : la lecture échoue sinon
+/// Le serveur envoie ses définitions aux clients à la connexion (`ServerInfo`), qui les
+/// vérifient avec `validate` avant de les utiliser
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct WeaponDefinitions(HashMap<Weapon, WeaponDefinition>);
+
+impl WeaponDefinitions {
+    /// Lit les définitions depuis le texte d'un fichier RON
+    ///
+    /// # Arguments
+    /// * `text` - Contenu du fichier
+    ///
+    /// # Returns
+    /// * `Result<WeaponDefinitions, WeaponsError>` - Les définitions, ou la raison de l'échec
+    pub fn parse(text: &str) -> Result<Self, WeaponsError> {
+        let definitions = Self(ron::from_str(text).map_err(WeaponsError::Parse)?);
+        definitions.validate()?;
+        Ok(definitions)
+    }
+
+    /// Vérifie que chaque arme est décrite et que ses valeurs sont cohérentes
+    ///
+    /// # Returns
+    /// * `Result<(), WeaponsError>` - Toutes les erreurs trouvées
+    pub fn validate(&self) -> Result<(), WeaponsError> {
+        let mut errors = Vec::new();
+        for weapon in Weapon::ALL {
+            match self.0.get(&weapon) {
+                Some(definition) => definition.validate(weapon, &mut errors),
+                None => errors.push(format!("{weapon} : définition manquante")),
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(WeaponsError::Invalid(errors))
+        }
+    }
+
+    /// Charge les définitions depuis un fichier
+    ///
+    /// # Arguments
+    /// * `path` - Chemin du fichier
+    pub fn load(path: &Path) -> Result<Self, WeaponsError> {
+        let text = std::fs::read_to_string(path).map_err(|error| WeaponsError::Io {
+            path: path.to_path_buf(),
+            error,
+        })?;
+        Self::parse(&text)
+    }
+
+    /// Définition d'une arme
+    ///
+    /// # Arguments
+    /// * `weapon` - Arme recherchée
+    pub fn get(&self, weapon: Weapon) -> &WeaponDefinition {
+        // Toutes les armes sont présentes : vérifié à la lecture
+        &self.0[&weapon]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fusil à pompe du fichier livré, avec la dispersion et le nombre de plombs donnés
+    fn shotgun(spread: f32, pellets: u32) -> WeaponDefinition {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(DEFAULT_WEAPONS_PATH);
+        let weapons = WeaponDefinitions::load(&path).expect("assets/weapons.ron valide");
+        WeaponDefinition {
+            spread,
+            pellets,
+            ..weapons.get(Weapon::Shotgun).clone()
+        }
+    }
+
+    #[test]
+    fn pellet_rng_matches_splitmix64() {
+        // Première valeur de référence de SplitMix64 pour la graine 0
+        assert_eq!(PelletRng(0).next_u64(), 0xE220_A839_7B1D_CDAF);
+    }
+
+    #[test]
+    fn pellet_directions_are_pinned() {
+        // Valeurs figées : une modification change les plombs vus par les clients déjà déployés
+        let expected = [
+            Vec3::new(-0.074596, 0.049262, -0.995996),
+            Vec3::new(-0.016272, -0.044161, -0.998892),
+            Vec3::new(-0.032513, 0.082764, -0.996039),
+            Vec3::new(0.010313, -0.072907, -0.997285),
+        ];
+        let directions = shotgun(6.0, 4).pellet_directions(Vec3::NEG_Z, 1234);
+        assert_eq!(directions.len(), expected.len());
+        for (direction, expected) in directions.iter().zip(expected) {
+            assert!(
+                direction.distance(expected) < 1e-5,
+                "{direction} au lieu de {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn pellets_stay_in_the_cone() {
+        let definition = shotgun(6.0, 32);
+        let aim = Vec3::new(1.0, 0.5, -2.0).normalize();
+        for seed in 0..100 {
+            let directions = definition.pellet_directions(aim, seed);
+            assert_eq!(directions, definition.pellet_directions(aim, seed));
+            for direction in directions {
+                assert!((direction.length() - 1.0).abs() < 1e-5);
+                assert!(direction.angle_between(aim).to_degrees() <= 6.0 + 1e-3);
+            }
+        }
+        assert_ne!(
+            definition.pellet_directions(aim, 1),
+            definition.pellet_directions(aim, 2)
+        );
+    }
+
+    #[test]
+    fn starting_ammo_fills_magazine_then_reserve() {
+        let mut definition = shotgun(6.0, 8);
+        definition.starting_ammo = definition.max_ammo + definition.magazine_size;
+        let mut errors = Vec::new();
+        definition.validate(Weapon::Shotgun, &mut errors);
+        assert_eq!(errors, Vec::<String>::new());
+
+        definition.starting_ammo += 1.0;
+        definition.validate(Weapon::Shotgun, &mut errors);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("Shotgun.starting_ammo"), "{errors:?}");
+    }
+
+    #[test]
+    fn zero_spread_fires_straight() {
+        let aim = Vec3::new(0.0, 0.6, -0.8);
+        assert_eq!(shotgun(0.0, 3).pellet_directions(aim, 7), vec![aim; 3]);
+    }
+}